@@ -36,6 +36,30 @@ pub fn with_lifetime_bound(generics: &Generics, lifetime: &str) -> Generics {
     }
 }
 
+// Adds a lifetime parameter which outlives every lifetime parameter of the
+// type, for the input that a Deserialize impl may borrow from.
+pub fn with_borrowed_lifetime(generics: &Generics, lifetime: &str) -> Generics {
+    let borrowed = LifetimeParam {
+        attrs: Vec::new(),
+        lifetime: Lifetime::new(lifetime, Span::call_site()),
+        colon_token: None,
+        bounds: generics
+            .lifetimes()
+            .map(|param| param.lifetime.clone())
+            .collect(),
+    };
+
+    let params = Some(GenericParam::Lifetime(borrowed))
+        .into_iter()
+        .chain(generics.params.iter().cloned())
+        .collect();
+
+    Generics {
+        params,
+        ..generics.clone()
+    }
+}
+
 pub fn where_clause_with_bound(generics: &Generics, bound: TokenStream) -> WhereClause {
     let new_predicates = generics.type_params().map::<WherePredicate, _>(|param| {
         let param = &param.ident;
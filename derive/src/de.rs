@@ -45,9 +45,16 @@ pub fn derive_struct(input: &DeriveInput, fields: &FieldsNamed) -> Result<TokenS
         .map(attr::name_of_field)
         .collect::<Result<Vec<_>>>()?;
 
+    let de_generics = bound::with_borrowed_lifetime(&input.generics, "'__de");
+    let (de_impl_generics, _, _) = de_generics.split_for_impl();
     let wrapper_generics = bound::with_lifetime_bound(&input.generics, "'__a");
     let (wrapper_impl_generics, wrapper_ty_generics, _) = wrapper_generics.split_for_impl();
-    let bound = parse_quote!(miniserde::Deserialize);
+    let mut wrapper_de_generics = wrapper_generics.clone();
+    wrapper_de_generics
+        .params
+        .insert(0, de_generics.params[0].clone());
+    let (wrapper_de_impl_generics, _, _) = wrapper_de_generics.split_for_impl();
+    let bound = parse_quote!(miniserde::Deserialize<'__de>);
     let bounded_where_clause = bound::where_clause_with_bound(&input.generics, bound);
     let private2 = private;
 
@@ -59,16 +66,16 @@ pub fn derive_struct(input: &DeriveInput, fields: &FieldsNamed) -> Result<TokenS
                 __out: miniserde::#private::Option<#ident #ty_generics>,
             }
 
-            impl #impl_generics miniserde::Deserialize for #ident #ty_generics #bounded_where_clause {
-                fn begin(__out: &mut miniserde::#private::Option<Self>) -> &mut dyn miniserde::de::Visitor {
+            impl #de_impl_generics miniserde::Deserialize<'__de> for #ident #ty_generics #bounded_where_clause {
+                fn begin(__out: &mut miniserde::#private::Option<Self>) -> &mut dyn miniserde::de::Visitor<'__de> {
                     unsafe {
                         &mut *miniserde::#private::ptr::addr_of_mut!(*__out).cast::<__Visitor #ty_generics>()
                     }
                 }
             }
 
-            impl #impl_generics miniserde::de::Visitor for __Visitor #ty_generics #bounded_where_clause {
-                fn map(&mut self) -> miniserde::Result<miniserde::#private::Box<dyn miniserde::de::Map + '_>> {
+            impl #de_impl_generics miniserde::de::Visitor<'__de> for __Visitor #ty_generics #bounded_where_clause {
+                fn map(&mut self) -> miniserde::Result<miniserde::#private::Box<dyn miniserde::de::Map<'__de> + '_>> {
                     Ok(miniserde::#private::Box::new(__State {
                         #(
                             #fieldname: miniserde::Deserialize::default(),
@@ -85,8 +92,8 @@ pub fn derive_struct(input: &DeriveInput, fields: &FieldsNamed) -> Result<TokenS
                 __out: &'__a mut miniserde::#private::Option<#ident #ty_generics>,
             }
 
-            impl #wrapper_impl_generics miniserde::de::Map for __State #wrapper_ty_generics #bounded_where_clause {
-                fn key(&mut self, __k: &miniserde::#private::str) -> miniserde::Result<&mut dyn miniserde::de::Visitor> {
+            impl #wrapper_de_impl_generics miniserde::de::Map<'__de> for __State #wrapper_ty_generics #bounded_where_clause {
+                fn key(&mut self, __k: &miniserde::#private::str) -> miniserde::Result<&mut dyn miniserde::de::Visitor<'__de>> {
                     match __k {
                         #(
                             #fieldstr => miniserde::#private2::Ok(miniserde::Deserialize::begin(&mut self.#fieldname)),
@@ -146,15 +153,15 @@ pub fn derive_enum(input: &DeriveInput, enumeration: &DataEnum) -> Result<TokenS
                 __out: miniserde::#private::Option<#ident>,
             }
 
-            impl miniserde::Deserialize for #ident {
-                fn begin(__out: &mut miniserde::#private::Option<Self>) -> &mut dyn miniserde::de::Visitor {
+            impl<'__de> miniserde::Deserialize<'__de> for #ident {
+                fn begin(__out: &mut miniserde::#private::Option<Self>) -> &mut dyn miniserde::de::Visitor<'__de> {
                     unsafe {
                         &mut *miniserde::#private::ptr::addr_of_mut!(*__out).cast::<__Visitor>()
                     }
                 }
             }
 
-            impl miniserde::de::Visitor for __Visitor {
+            impl<'__de> miniserde::de::Visitor<'__de> for __Visitor {
                 fn string(&mut self, s: &miniserde::#private::str) -> miniserde::Result<()> {
                     let value = match s {
                         #( #names => #ident::#var_idents, )*
@@ -1,4 +1,4 @@
-use crate::private;
+use crate::{bound, private};
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::DeriveInput;
@@ -23,7 +23,9 @@ pub(crate) fn ser(input: &DeriveInput, error: syn::Error) -> TokenStream {
 
 pub(crate) fn de(input: &DeriveInput, error: syn::Error) -> TokenStream {
     let ident = &input.ident;
-    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+    let de_generics = bound::with_borrowed_lifetime(&input.generics, "'__de");
+    let (impl_generics, _, _) = de_generics.split_for_impl();
 
     let error = error.into_compile_error();
 
@@ -31,8 +33,8 @@ pub(crate) fn de(input: &DeriveInput, error: syn::Error) -> TokenStream {
         #error
 
         #[allow(deprecated)]
-        impl #impl_generics miniserde::Deserialize for #ident #ty_generics #where_clause {
-            fn begin(__out: &mut miniserde::#private::Option<Self>) -> &mut dyn miniserde::de::Visitor {
+        impl #impl_generics miniserde::Deserialize<'__de> for #ident #ty_generics #where_clause {
+            fn begin(__out: &mut miniserde::#private::Option<Self>) -> &mut dyn miniserde::de::Visitor<'__de> {
                 miniserde::#private::unreachable!()
             }
         }
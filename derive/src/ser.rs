@@ -44,6 +44,7 @@ fn derive_struct(input: &DeriveInput, fields: &FieldsNamed) -> Result<TokenStrea
         .map(attr::name_of_field)
         .collect::<Result<Vec<_>>>()?;
     let index = 0usize..;
+    let len = fieldname.len();
 
     let wrapper_generics = bound::with_lifetime_bound(&input.generics, "'__a");
     let (wrapper_impl_generics, wrapper_ty_generics, _) = wrapper_generics.split_for_impl();
@@ -82,6 +83,10 @@ fn derive_struct(input: &DeriveInput, fields: &FieldsNamed) -> Result<TokenStrea
                         _ => miniserde::#private::None,
                     }
                 }
+
+                fn size_hint(&self) -> miniserde::#private::Option<miniserde::#private::usize> {
+                    miniserde::#private::Some(#len.saturating_sub(self.state))
+                }
             }
         };
     })
@@ -0,0 +1,193 @@
+use crate::base64;
+use crate::de::{Deserialize, Seq, Visitor};
+use crate::error::Result;
+use crate::ser::{Fragment, Serialize};
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt::{self, Debug};
+use core::mem;
+use core::ops::{Deref, DerefMut};
+
+/// A slice of bytes which serializes as a byte string.
+///
+/// A plain `[u8]` serializes as a sequence of numbers, one at a time. Wrapping
+/// it in `Bytes` sends it through [`Fragment::Bytes`] instead, which binary
+/// formats write compactly and JSON writes as a base64 string.
+///
+/// ```rust
+/// use miniserde::{json, Bytes};
+///
+/// let data = [0xDE, 0xAD, 0xBE, 0xEF];
+/// assert_eq!(json::to_string(&data), "[222,173,190,239]");
+/// assert_eq!(json::to_string(Bytes::new(&data)), r#""3q2+7w==""#);
+/// ```
+#[repr(transparent)]
+pub struct Bytes {
+    bytes: [u8],
+}
+
+impl Bytes {
+    /// Wrap a slice of bytes.
+    pub fn new(bytes: &[u8]) -> &Self {
+        unsafe { &*(bytes as *const [u8] as *const Bytes) }
+    }
+}
+
+impl Deref for Bytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl Debug for Bytes {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Debug::fmt(&self.bytes, formatter)
+    }
+}
+
+impl Serialize for Bytes {
+    fn begin(&self) -> Fragment {
+        Fragment::Bytes(Cow::Borrowed(&self.bytes))
+    }
+}
+
+/// An owned buffer of bytes which serializes and deserializes as a byte
+/// string.
+///
+/// This is the owned counterpart of [`Bytes`]. When deserializing, it accepts
+/// a byte string from a binary format, as well as a base64 string or an array
+/// of numbers as JSON would represent one.
+///
+/// ```rust
+/// use miniserde::{cbor, json, ByteBuf, Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Blob {
+///     name: String,
+///     data: ByteBuf,
+/// }
+///
+/// fn main() -> miniserde::Result<()> {
+///     let j = r#"{"name": "key", "data": "AAEC/w=="}"#;
+///     let blob: Blob = json::from_str(j)?;
+///     assert_eq!(*blob.data, [0, 1, 2, 255]);
+///
+///     let bytes = cbor::to_vec(&blob);
+///     let blob: Blob = cbor::from_slice(&bytes)?;
+///     assert_eq!(*blob.data, [0, 1, 2, 255]);
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ByteBuf {
+    bytes: Vec<u8>,
+}
+
+impl ByteBuf {
+    /// An empty buffer.
+    pub fn new() -> Self {
+        ByteBuf { bytes: Vec::new() }
+    }
+
+    /// Unwrap the vector of bytes.
+    pub fn into_vec(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+impl From<Vec<u8>> for ByteBuf {
+    fn from(bytes: Vec<u8>) -> Self {
+        ByteBuf { bytes }
+    }
+}
+
+impl From<ByteBuf> for Vec<u8> {
+    fn from(buf: ByteBuf) -> Self {
+        buf.bytes
+    }
+}
+
+impl Deref for ByteBuf {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Vec<u8> {
+        &self.bytes
+    }
+}
+
+impl DerefMut for ByteBuf {
+    fn deref_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.bytes
+    }
+}
+
+impl Debug for ByteBuf {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Debug::fmt(&self.bytes, formatter)
+    }
+}
+
+impl Serialize for ByteBuf {
+    fn begin(&self) -> Fragment {
+        Fragment::Bytes(Cow::Borrowed(&self.bytes))
+    }
+}
+
+impl<'de> Deserialize<'de> for ByteBuf {
+    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor<'de> {
+        make_place!(Place);
+
+        impl<'de> Visitor<'de> for Place<ByteBuf> {
+            fn bytes(&mut self, b: &[u8]) -> Result<()> {
+                self.out = Some(ByteBuf::from(b.to_vec()));
+                Ok(())
+            }
+
+            fn string(&mut self, s: &str) -> Result<()> {
+                self.out = Some(ByteBuf::from(base64::decode(s)?));
+                Ok(())
+            }
+
+            fn seq(&mut self) -> Result<Box<dyn Seq<'de> + '_>> {
+                Ok(Box::new(ByteBufBuilder {
+                    out: &mut self.out,
+                    bytes: Vec::new(),
+                    element: None,
+                }))
+            }
+        }
+
+        struct ByteBufBuilder<'a> {
+            out: &'a mut Option<ByteBuf>,
+            bytes: Vec<u8>,
+            element: Option<u8>,
+        }
+
+        impl<'a> ByteBufBuilder<'a> {
+            fn shift(&mut self) {
+                if let Some(b) = self.element.take() {
+                    self.bytes.push(b);
+                }
+            }
+        }
+
+        impl<'a, 'de> Seq<'de> for ByteBufBuilder<'a> {
+            fn element(&mut self) -> Result<&mut dyn Visitor<'de>> {
+                self.shift();
+                Ok(Deserialize::begin(&mut self.element))
+            }
+
+            fn finish(&mut self) -> Result<()> {
+                self.shift();
+                *self.out = Some(ByteBuf::from(mem::take(&mut self.bytes)));
+                Ok(())
+            }
+        }
+
+        Place::new(out)
+    }
+}
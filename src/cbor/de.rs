@@ -0,0 +1,393 @@
+use self::Event::*;
+use self::Reference::{Borrowed, Copied};
+use crate::de::{Deserialize, Map, Seq, Visitor};
+use crate::error::{Error, Result};
+use crate::ptr::NonuniqueBox;
+use alloc::vec::Vec;
+use core::ops::Deref;
+use core::ptr::NonNull;
+use core::str;
+
+/// Deserialize CBOR bytes into any deserializable type.
+///
+/// Both definite and indefinite length items are accepted, as are floats of
+/// any precision. Tag 0 must be followed by a date/time string and tag 1 by
+/// a number; these and any other tags are otherwise ignored, with the tagged
+/// item deserialized as if it were not tagged. Map keys must be text strings.
+///
+/// ```rust
+/// use miniserde::cbor;
+///
+/// fn main() -> miniserde::Result<()> {
+///     // {"a": [1, -2]}, with an indefinite length array.
+///     let bytes = [0xA1, 0x61, b'a', 0x9F, 0x01, 0x21, 0xFF];
+///
+///     let out: std::collections::BTreeMap<String, Vec<i32>> = cbor::from_slice(&bytes)?;
+///     assert_eq!(out["a"], [1, -2]);
+///
+///     Ok(())
+/// }
+/// ```
+pub fn from_slice<'de, T>(bytes: &'de [u8]) -> Result<T>
+where
+    T: Deserialize<'de>,
+{
+    let mut out = None;
+    from_slice_impl(bytes, T::begin(&mut out))?;
+    out.ok_or(Error)
+}
+
+struct Deserializer<'a, 'b> {
+    input: &'a [u8],
+    pos: usize,
+    buffer: Vec<u8>,
+    // Each open array or map, with how many elements or entries are left in
+    // it, or None if it has indefinite length.
+    stack: Vec<(NonNull<dyn Visitor<'a>>, Layer<'a, 'b>, Option<u64>)>,
+}
+
+enum Layer<'de, 'a> {
+    Seq(NonuniqueBox<dyn Seq<'de> + 'a>),
+    Map(NonuniqueBox<dyn Map<'de> + 'a>),
+}
+
+impl<'a, 'b> Drop for Deserializer<'a, 'b> {
+    fn drop(&mut self) {
+        // Drop layers in reverse order.
+        while !self.stack.is_empty() {
+            self.stack.pop();
+        }
+    }
+}
+
+const UNSIGNED: u8 = 0;
+const NEGATIVE: u8 = 1;
+const BYTES: u8 = 2;
+const TEXT: u8 = 3;
+const ARRAY: u8 = 4;
+const MAP: u8 = 5;
+const TAG: u8 = 6;
+const SIMPLE: u8 = 7;
+
+const BREAK: u8 = 0xFF;
+const INDEFINITE: u8 = 31;
+
+fn from_slice_impl<'de>(bytes: &'de [u8], visitor: &mut dyn Visitor<'de>) -> Result<()> {
+    let mut de = Deserializer {
+        input: bytes,
+        pos: 0,
+        buffer: Vec::new(),
+        stack: Vec::new(),
+    };
+    de.visit(visitor)?;
+
+    if de.pos == de.input.len() {
+        Ok(())
+    } else {
+        Err(Error)
+    }
+}
+
+impl<'a, 'b> Deserializer<'a, 'b> {
+    fn visit(&mut self, visitor: &mut dyn Visitor<'a>) -> Result<()>
+    where
+        'a: 'b,
+    {
+        let visitor = NonNull::from(visitor);
+        let mut visitor = unsafe { extend_lifetime!(visitor as NonNull<dyn Visitor<'a>>) };
+
+        loop {
+            let visitor_mut = unsafe { &mut *visitor.as_ptr() };
+            let layer = match self.event()? {
+                Null => {
+                    visitor_mut.null()?;
+                    None
+                }
+                Bool(b) => {
+                    visitor_mut.boolean(b)?;
+                    None
+                }
+                Negative(n) => {
+                    visitor_mut.negative(n)?;
+                    None
+                }
+                Nonnegative(n) => {
+                    visitor_mut.nonnegative(n)?;
+                    None
+                }
+                Float(n) => {
+                    visitor_mut.float(n)?;
+                    None
+                }
+                Bytes(b) => {
+                    visitor_mut.bytes(&b)?;
+                    None
+                }
+                Str(Borrowed(s)) => {
+                    visitor_mut.borrowed_string(s)?;
+                    None
+                }
+                Str(Copied(s)) => {
+                    visitor_mut.string(s)?;
+                    None
+                }
+                SeqStart(len) => {
+                    let seq = visitor_mut.seq()?;
+                    Some((Layer::Seq(NonuniqueBox::from(seq)), len))
+                }
+                MapStart(len) => {
+                    let map = visitor_mut.map()?;
+                    Some((Layer::Map(NonuniqueBox::from(map)), len))
+                }
+            };
+
+            let (mut layer, mut remaining) = match layer {
+                Some(layer) => layer,
+                None => match self.stack.pop() {
+                    Some(frame) => {
+                        visitor = frame.0;
+                        (frame.1, frame.2)
+                    }
+                    None => return Ok(()),
+                },
+            };
+
+            loop {
+                let done = match &mut remaining {
+                    Some(0) => true,
+                    Some(remaining) => {
+                        *remaining -= 1;
+                        false
+                    }
+                    None if self.peek() == Some(BREAK) => {
+                        self.pos += 1;
+                        true
+                    }
+                    None => false,
+                };
+                if !done {
+                    break;
+                }
+                match &mut layer {
+                    Layer::Seq(seq) => seq.finish()?,
+                    Layer::Map(map) => map.finish()?,
+                }
+                let Some(frame) = self.stack.pop() else {
+                    return Ok(());
+                };
+                visitor = frame.0;
+                layer = frame.1;
+                remaining = frame.2;
+            }
+
+            let outer = visitor;
+            match layer {
+                Layer::Seq(mut seq) => {
+                    let element = seq.element()?;
+                    let next = NonNull::from(element);
+                    visitor = unsafe { extend_lifetime!(next as NonNull<dyn Visitor<'a>>) };
+                    self.stack.push((outer, Layer::Seq(seq), remaining));
+                }
+                Layer::Map(mut map) => {
+                    let entry = map.key(&self.parse_key()?)?;
+                    let next = NonNull::from(entry);
+                    visitor = unsafe { extend_lifetime!(next as NonNull<dyn Visitor<'a>>) };
+                    self.stack.push((outer, Layer::Map(map), remaining));
+                }
+            }
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.pos).copied()
+    }
+
+    fn next_byte(&mut self) -> Result<u8> {
+        let byte = self.peek().ok_or(Error)?;
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    fn take(&mut self, len: u64) -> Result<&'a [u8]> {
+        let rest = &self.input[self.pos..];
+        let len = usize::try_from(len).map_err(|_| Error)?;
+        if len > rest.len() {
+            return Err(Error);
+        }
+        self.pos += len;
+        Ok(&rest[..len])
+    }
+
+    // The number which follows the initial byte of an item, for the
+    // additional information given in its low 5 bits.
+    fn parse_argument(&mut self, info: u8) -> Result<u64> {
+        let bytes = match info {
+            0..=23 => return Ok(u64::from(info)),
+            24 => self.take(1)?,
+            25 => self.take(2)?,
+            26 => self.take(4)?,
+            27 => self.take(8)?,
+            _ => return Err(Error),
+        };
+        let mut be = [0; 8];
+        be[8 - bytes.len()..].copy_from_slice(bytes);
+        Ok(u64::from_be_bytes(be))
+    }
+
+    fn parse_length(&mut self, info: u8) -> Result<Option<u64>> {
+        if info == INDEFINITE {
+            Ok(None)
+        } else {
+            self.parse_argument(info).map(Some)
+        }
+    }
+
+    // Parses the content of a byte or text string, whose initial byte has
+    // already been consumed.
+    fn parse_bytes(&mut self, major: u8, info: u8) -> Result<Reference<'a, '_, [u8]>> {
+        if info != INDEFINITE {
+            let len = self.parse_argument(info)?;
+            let bytes = self.take(len)?;
+            if major == TEXT {
+                str::from_utf8(bytes).map_err(|_| Error)?;
+            }
+            return Ok(Borrowed(bytes));
+        }
+
+        // Indefinite length, made of definite length chunks of the same type.
+        self.buffer.clear();
+        loop {
+            let initial = self.next_byte()?;
+            if initial == BREAK {
+                return Ok(Copied(&self.buffer));
+            }
+            if initial >> 5 != major || initial & 0x1F == INDEFINITE {
+                return Err(Error);
+            }
+            let len = self.parse_argument(initial & 0x1F)?;
+            let chunk = self.take(len)?;
+            if major == TEXT {
+                str::from_utf8(chunk).map_err(|_| Error)?;
+            }
+            self.buffer.extend_from_slice(chunk);
+        }
+    }
+
+    fn parse_str(&mut self, info: u8) -> Result<Reference<'a, '_, str>> {
+        // Checked to be UTF-8 by parse_bytes, one chunk at a time.
+        Ok(match self.parse_bytes(TEXT, info)? {
+            Borrowed(b) => Borrowed(unsafe { str::from_utf8_unchecked(b) }),
+            Copied(b) => Copied(unsafe { str::from_utf8_unchecked(b) }),
+        })
+    }
+
+    fn parse_key(&mut self) -> Result<Reference<'a, '_, str>> {
+        let initial = self.next_byte()?;
+        if initial >> 5 != TEXT {
+            return Err(Error);
+        }
+        self.parse_str(initial & 0x1F)
+    }
+
+    fn event(&mut self) -> Result<Event<'a, '_>> {
+        loop {
+            let initial = self.next_byte()?;
+            let info = initial & 0x1F;
+            return match initial >> 5 {
+                UNSIGNED => self.parse_argument(info).map(Nonnegative),
+                NEGATIVE => {
+                    let n = self.parse_argument(info)?;
+                    // The value is -1 - n, which is the bitwise complement.
+                    Ok(match i64::try_from(n) {
+                        Ok(n) => Negative(!n),
+                        Err(_) => Float(-1.0 - n as f64),
+                    })
+                }
+                BYTES => self.parse_bytes(BYTES, info).map(Bytes),
+                TEXT => self.parse_str(info).map(Str),
+                ARRAY => self.parse_length(info).map(SeqStart),
+                MAP => self.parse_length(info).map(MapStart),
+                TAG => {
+                    let tag = self.parse_argument(info)?;
+                    let next = self.peek().ok_or(Error)?;
+                    let valid = match tag {
+                        // Standard date/time string.
+                        0 => next >> 5 == TEXT,
+                        // Epoch-based date/time.
+                        1 => {
+                            matches!(next >> 5, UNSIGNED | NEGATIVE)
+                                || (0xF9..=0xFB).contains(&next)
+                        }
+                        _ => true,
+                    };
+                    if !valid {
+                        return Err(Error);
+                    }
+                    continue;
+                }
+                SIMPLE => match info {
+                    20 => Ok(Bool(false)),
+                    21 => Ok(Bool(true)),
+                    // Null and undefined.
+                    22 | 23 => Ok(Null),
+                    25 => {
+                        let bits = self.parse_argument(info)? as u16;
+                        Ok(Float(f64::from(f16_to_f32(bits))))
+                    }
+                    26 => {
+                        let bits = self.parse_argument(info)? as u32;
+                        Ok(Float(f64::from(f32::from_bits(bits))))
+                    }
+                    27 => {
+                        let bits = self.parse_argument(info)?;
+                        Ok(Float(f64::from_bits(bits)))
+                    }
+                    _ => Err(Error),
+                },
+                _ => unreachable!(),
+            };
+        }
+    }
+}
+
+fn f16_to_f32(bits: u16) -> f32 {
+    let sign = u32::from(bits & 0x8000) << 16;
+    let exponent = u32::from(bits >> 10 & 0x1F);
+    let mantissa = u32::from(bits & 0x3FF);
+    let magnitude = match exponent {
+        // Subnormal, which is exact as a multiple of 2^-24.
+        0 => return f32::from_bits(sign | (mantissa as f32 / 16_777_216.0).to_bits()),
+        // Infinity or NaN.
+        0x1F => 0x7F80_0000 | mantissa << 13,
+        _ => (exponent + 112) << 23 | mantissa << 13,
+    };
+    f32::from_bits(sign | magnitude)
+}
+
+enum Reference<'a, 's, T: ?Sized> {
+    Borrowed(&'a T),
+    Copied(&'s T),
+}
+
+impl<'a, 's, T: ?Sized> Deref for Reference<'a, 's, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match *self {
+            Borrowed(b) => b,
+            Copied(c) => c,
+        }
+    }
+}
+
+enum Event<'a, 's> {
+    Null,
+    Bool(bool),
+    Negative(i64),
+    Nonnegative(u64),
+    Float(f64),
+    Bytes(Reference<'a, 's, [u8]>),
+    Str(Reference<'a, 's, str>),
+    SeqStart(Option<u64>),
+    MapStart(Option<u64>),
+}
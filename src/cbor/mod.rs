@@ -0,0 +1,38 @@
+//! CBOR data format, as specified by [RFC 8949].
+//!
+//! [RFC 8949]: https://www.rfc-editor.org/rfc/rfc8949
+//!
+//! This is a compact binary encoding of the same data model as JSON, plus
+//! byte strings. Any type that implements `Serialize` and `Deserialize` for
+//! JSON works with CBOR too.
+//!
+//! ```rust
+//! use miniserde::{cbor, Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize, Debug, PartialEq)]
+//! struct Reading {
+//!     sensor: String,
+//!     celsius: f64,
+//! }
+//!
+//! fn main() -> miniserde::Result<()> {
+//!     let reading = Reading {
+//!         sensor: "t1".to_owned(),
+//!         celsius: 21.5,
+//!     };
+//!
+//!     let bytes = cbor::to_vec(&reading);
+//!     assert_eq!(bytes.len(), 23);
+//!
+//!     let out: Reading = cbor::from_slice(&bytes)?;
+//!     assert_eq!(out, reading);
+//!
+//!     Ok(())
+//! }
+//! ```
+
+mod ser;
+pub use self::ser::to_vec;
+
+mod de;
+pub use self::de::from_slice;
@@ -0,0 +1,226 @@
+use crate::json::{self, Value};
+use crate::ser::{Fragment, Map, Seq, Serialize};
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+/// Serialize any serializable type into CBOR bytes.
+///
+/// Arrays and maps are written with indefinite length, since their number of
+/// elements is not known in advance. Integers and floats take the fewest
+/// bytes which represent them exactly, so a float may be written in half or
+/// single precision.
+///
+/// ```rust
+/// use miniserde::cbor;
+///
+/// assert_eq!(cbor::to_vec(&[1.5]), [0x9F, 0xF9, 0x3E, 0x00, 0xFF]);
+/// ```
+pub fn to_vec<T>(value: &T) -> Vec<u8>
+where
+    T: ?Sized + Serialize,
+{
+    to_vec_impl(&value)
+}
+
+struct Serializer<'a> {
+    stack: Vec<Layer<'a>>,
+}
+
+enum Layer<'a> {
+    Seq(Box<dyn Seq + 'a>),
+    Map(Box<dyn Map + 'a>),
+}
+
+impl<'a> Drop for Serializer<'a> {
+    fn drop(&mut self) {
+        // Drop layers in reverse order.
+        while !self.stack.is_empty() {
+            self.stack.pop();
+        }
+    }
+}
+
+const UNSIGNED: u8 = 0;
+const NEGATIVE: u8 = 1;
+const BYTES: u8 = 2;
+const TEXT: u8 = 3;
+const ARRAY: u8 = 4;
+const MAP: u8 = 5;
+
+const FALSE: u8 = 0xF4;
+const TRUE: u8 = 0xF5;
+const NULL: u8 = 0xF6;
+const HALF: u8 = 0xF9;
+const SINGLE: u8 = 0xFA;
+const DOUBLE: u8 = 0xFB;
+const BREAK: u8 = 0xFF;
+const INDEFINITE: u8 = 31;
+
+fn to_vec_impl(value: &dyn Serialize) -> Vec<u8> {
+    let mut out = Vec::new();
+    let mut serializer = Serializer { stack: Vec::new() };
+    let mut fragment = value.begin();
+
+    loop {
+        match fragment {
+            Fragment::Null => out.push(NULL),
+            Fragment::Bool(b) => out.push(if b { TRUE } else { FALSE }),
+            Fragment::Str(s) => {
+                write_head(&mut out, TEXT, s.len() as u64);
+                out.extend_from_slice(s.as_bytes());
+            }
+            Fragment::U64(n) => write_head(&mut out, UNSIGNED, n),
+            Fragment::I64(n) => {
+                if n < 0 {
+                    // Encodes -1 - n, which is the bitwise complement.
+                    write_head(&mut out, NEGATIVE, !n as u64);
+                } else {
+                    write_head(&mut out, UNSIGNED, n as u64);
+                }
+            }
+            Fragment::F64(n) => write_float(&mut out, n),
+            Fragment::Bytes(b) => {
+                write_head(&mut out, BYTES, b.len() as u64);
+                out.extend_from_slice(&b);
+            }
+            Fragment::RawJson(raw) => {
+                // A Value never contains raw JSON, so this goes only one level
+                // deep. The text of a RawValue is always valid JSON.
+                let value = json::from_str::<Value>(raw.get()).unwrap();
+                out.extend_from_slice(&to_vec_impl(&value));
+            }
+            Fragment::Seq(mut seq) => {
+                out.push(ARRAY << 5 | INDEFINITE);
+                // invariant: `seq` must outlive `first`
+                match unsafe { extend_lifetime!(seq.next() as Option<&dyn Serialize>) } {
+                    Some(first) => {
+                        serializer.stack.push(Layer::Seq(seq));
+                        fragment = first.begin();
+                        continue;
+                    }
+                    None => out.push(BREAK),
+                }
+            }
+            Fragment::Map(mut map) => {
+                out.push(MAP << 5 | INDEFINITE);
+                // invariant: `map` must outlive `first`
+                match unsafe { extend_lifetime!(map.next() as Option<(Cow<str>, &dyn Serialize)>) }
+                {
+                    Some((key, first)) => {
+                        write_head(&mut out, TEXT, key.len() as u64);
+                        out.extend_from_slice(key.as_bytes());
+                        serializer.stack.push(Layer::Map(map));
+                        fragment = first.begin();
+                        continue;
+                    }
+                    None => out.push(BREAK),
+                }
+            }
+        }
+
+        loop {
+            match serializer.stack.last_mut() {
+                Some(Layer::Seq(seq)) => {
+                    // invariant: `seq` must outlive `next`
+                    match unsafe { extend_lifetime!(seq.next() as Option<&dyn Serialize>) } {
+                        Some(next) => {
+                            fragment = next.begin();
+                            break;
+                        }
+                        None => out.push(BREAK),
+                    }
+                }
+                Some(Layer::Map(map)) => {
+                    // invariant: `map` must outlive `next`
+                    match unsafe {
+                        extend_lifetime!(map.next() as Option<(Cow<str>, &dyn Serialize)>)
+                    } {
+                        Some((key, next)) => {
+                            write_head(&mut out, TEXT, key.len() as u64);
+                            out.extend_from_slice(key.as_bytes());
+                            fragment = next.begin();
+                            break;
+                        }
+                        None => out.push(BREAK),
+                    }
+                }
+                None => return out,
+            }
+            serializer.stack.pop();
+        }
+    }
+}
+
+// Writes the initial byte of an item, followed by its argument in as few
+// bytes as possible.
+fn write_head(out: &mut Vec<u8>, major: u8, n: u64) {
+    let major = major << 5;
+    if n < 24 {
+        out.push(major | n as u8);
+    } else if let Ok(n) = u8::try_from(n) {
+        out.push(major | 24);
+        out.push(n);
+    } else if let Ok(n) = u16::try_from(n) {
+        out.push(major | 25);
+        out.extend_from_slice(&n.to_be_bytes());
+    } else if let Ok(n) = u32::try_from(n) {
+        out.push(major | 26);
+        out.extend_from_slice(&n.to_be_bytes());
+    } else {
+        out.push(major | 27);
+        out.extend_from_slice(&n.to_be_bytes());
+    }
+}
+
+fn write_float(out: &mut Vec<u8>, n: f64) {
+    let single = n as f32;
+    if f64::from(single) == n || n.is_nan() {
+        if let Some(half) = f32_to_f16(single) {
+            out.push(HALF);
+            out.extend_from_slice(&half.to_be_bytes());
+        } else {
+            out.push(SINGLE);
+            out.extend_from_slice(&single.to_bits().to_be_bytes());
+        }
+    } else {
+        out.push(DOUBLE);
+        out.extend_from_slice(&n.to_bits().to_be_bytes());
+    }
+}
+
+// The bits of a half precision float with the same value, if there is one.
+// Every NaN becomes the same quiet NaN.
+fn f32_to_f16(f: f32) -> Option<u16> {
+    let bits = f.to_bits();
+    let sign = (bits >> 16) as u16 & 0x8000;
+    let exponent = (bits >> 23) as i32 & 0xFF;
+    let mantissa = bits & 0x7F_FFFF;
+
+    if exponent == 0xFF {
+        return Some(sign | if mantissa == 0 { 0x7C00 } else { 0x7E00 });
+    }
+    if exponent == 0 && mantissa == 0 {
+        return Some(sign);
+    }
+
+    let exponent = exponent - 127;
+    if (-14..=15).contains(&exponent) {
+        // Normal, if no bits of the mantissa get lost.
+        if mantissa & 0x1FFF != 0 {
+            return None;
+        }
+        let exponent = (exponent + 15) as u16;
+        Some(sign | exponent << 10 | (mantissa >> 13) as u16)
+    } else if (-24..-14).contains(&exponent) {
+        // Subnormal, so the implicit leading bit becomes explicit.
+        let significand = 0x80_0000 | mantissa;
+        let shift = -exponent - 1;
+        if significand & ((1 << shift) - 1) != 0 {
+            return None;
+        }
+        Some(sign | (significand >> shift) as u16)
+    } else {
+        None
+    }
+}
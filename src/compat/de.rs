@@ -0,0 +1,243 @@
+use crate::compat::Serde;
+use crate::de::{self, Deserialize, Visitor};
+use crate::error::Error;
+use alloc::borrow::ToOwned;
+use alloc::string::{String, ToString};
+use core::fmt;
+use serde::de::{
+    DeserializeSeed, Deserializer, EnumAccess, Error as _, MapAccess, SeqAccess, VariantAccess,
+};
+
+/// Feed the value produced by a Serde deserializer into a miniserde visitor.
+///
+/// This is what the `Deserialize` impl of [`Serde<T>`] is built on. The
+/// deserializer is asked for whatever it holds, as by `deserialize_any`, so
+/// the data format must be self-describing like JSON or YAML rather than one
+/// like bincode which relies on being told what type to expect. Map keys may
+/// be strings, integers or bools, and among Serde enums only unit variants are
+/// accepted, as strings.
+///
+/// Any error reported by the visitor is turned into an error of the
+/// deserializer.
+///
+/// ```rust
+/// use miniserde::compat;
+/// use miniserde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// fn main() -> serde_json::Result<()> {
+///     let mut out = None;
+///     let mut deserializer = serde_json::Deserializer::from_str(r#"{"x":1,"y":-2}"#);
+///     compat::deserialize(&mut deserializer, Point::begin(&mut out))?;
+///
+///     let point = out.unwrap();
+///     assert_eq!((point.x, point.y), (1, -2));
+///     Ok(())
+/// }
+/// ```
+pub fn deserialize<'de, D>(deserializer: D, visitor: &mut dyn Visitor<'de>) -> Result<(), D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_any(Adapter(visitor))
+}
+
+impl<'de, T> serde::Deserialize<'de> for Serde<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut out = None;
+        deserialize(deserializer, T::begin(&mut out))?;
+        out.map(Serde).ok_or_else(|| D::Error::custom(Error))
+    }
+}
+
+struct Adapter<'a, 'de>(&'a mut dyn Visitor<'de>);
+
+impl<'a, 'de> DeserializeSeed<'de> for Adapter<'a, 'de> {
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize(deserializer, self.0)
+    }
+}
+
+// Serde deserializers recurse into nested sequences and maps, and this
+// follows along.
+impl<'a, 'de> serde::de::Visitor<'de> for Adapter<'a, 'de> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any value")
+    }
+
+    fn visit_bool<E: serde::de::Error>(self, v: bool) -> Result<(), E> {
+        self.0.boolean(v).map_err(E::custom)
+    }
+
+    fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<(), E> {
+        if v < 0 {
+            self.0.negative(v).map_err(E::custom)
+        } else {
+            self.0.nonnegative(v as u64).map_err(E::custom)
+        }
+    }
+
+    fn visit_i128<E: serde::de::Error>(self, v: i128) -> Result<(), E> {
+        if let Ok(n) = i64::try_from(v) {
+            self.visit_i64(n)
+        } else if let Ok(n) = u64::try_from(v) {
+            self.visit_u64(n)
+        } else {
+            self.0.float(v as f64).map_err(E::custom)
+        }
+    }
+
+    fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<(), E> {
+        self.0.nonnegative(v).map_err(E::custom)
+    }
+
+    fn visit_u128<E: serde::de::Error>(self, v: u128) -> Result<(), E> {
+        match u64::try_from(v) {
+            Ok(n) => self.visit_u64(n),
+            Err(_) => self.0.float(v as f64).map_err(E::custom),
+        }
+    }
+
+    fn visit_f64<E: serde::de::Error>(self, v: f64) -> Result<(), E> {
+        self.0.float(v).map_err(E::custom)
+    }
+
+    fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<(), E> {
+        self.0.string(v).map_err(E::custom)
+    }
+
+    fn visit_borrowed_str<E: serde::de::Error>(self, v: &'de str) -> Result<(), E> {
+        self.0.borrowed_string(v).map_err(E::custom)
+    }
+
+    fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<(), E> {
+        self.0.bytes(v).map_err(E::custom)
+    }
+
+    fn visit_none<E: serde::de::Error>(self) -> Result<(), E> {
+        self.0.null().map_err(E::custom)
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize(deserializer, self.0)
+    }
+
+    fn visit_unit<E: serde::de::Error>(self) -> Result<(), E> {
+        self.0.null().map_err(E::custom)
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize(deserializer, self.0)
+    }
+
+    fn visit_seq<A>(self, mut access: A) -> Result<(), A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut seq = self.0.seq().map_err(A::Error::custom)?;
+        while let Some(()) = access.next_element_seed(Element(&mut *seq))? {}
+        seq.finish().map_err(A::Error::custom)
+    }
+
+    fn visit_map<A>(self, mut access: A) -> Result<(), A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut map = self.0.map().map_err(A::Error::custom)?;
+        while let Some(Key(key)) = access.next_key()? {
+            let visitor = map.key(&key).map_err(A::Error::custom)?;
+            access.next_value_seed(Adapter(visitor))?;
+        }
+        map.finish().map_err(A::Error::custom)
+    }
+
+    fn visit_enum<A>(self, access: A) -> Result<(), A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        let (Key(variant), access) = access.variant()?;
+        access.unit_variant()?;
+        self.0.string(&variant).map_err(A::Error::custom)
+    }
+}
+
+// Asks the sequence for the place of its next element only once the Serde
+// deserializer has said that there is one.
+struct Element<'a, 'b, 'de>(&'a mut (dyn de::Seq<'de> + 'b));
+
+impl<'a, 'b, 'de> DeserializeSeed<'de> for Element<'a, 'b, 'de> {
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let visitor = self.0.element().map_err(D::Error::custom)?;
+        deserialize(deserializer, visitor)
+    }
+}
+
+struct Key(String);
+
+impl<'de> serde::Deserialize<'de> for Key {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(KeyVisitor)
+    }
+}
+
+struct KeyVisitor;
+
+impl<'de> serde::de::Visitor<'de> for KeyVisitor {
+    type Value = Key;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a string, integer or bool")
+    }
+
+    fn visit_bool<E: serde::de::Error>(self, v: bool) -> Result<Key, E> {
+        Ok(Key(v.to_string()))
+    }
+
+    fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<Key, E> {
+        Ok(Key(itoa::Buffer::new().format(v).to_owned()))
+    }
+
+    fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<Key, E> {
+        Ok(Key(itoa::Buffer::new().format(v).to_owned()))
+    }
+
+    fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Key, E> {
+        Ok(Key(v.to_owned()))
+    }
+
+    fn visit_string<E: serde::de::Error>(self, v: String) -> Result<Key, E> {
+        Ok(Key(v))
+    }
+}
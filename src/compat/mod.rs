@@ -0,0 +1,85 @@
+//! Interoperability with Serde.
+//!
+//! *This module is available if miniserde is built with the `"serde"`
+//! feature.*
+//!
+//! [`Serde<T>`] lets a type which implements miniserde's traits travel through
+//! any Serde data format, and [`MiniSer<T>`] lets a type which implements
+//! Serde's `Serialize` be serialized by miniserde, so that neither needs to be
+//! derived twice. Lower down, [`deserialize`] feeds whatever a Serde
+//! deserializer produces into a miniserde [`Visitor`].
+//!
+//! [`Visitor`]: crate::de::Visitor
+//!
+//! ```rust
+//! use miniserde::compat::Serde;
+//! use miniserde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize, Debug, PartialEq)]
+//! struct Point {
+//!     x: i32,
+//!     y: i32,
+//! }
+//!
+//! fn main() {
+//!     let point = Point { x: 1, y: -2 };
+//!
+//!     // Serde's JSON, but any other self-describing Serde format works the
+//!     // same way.
+//!     let j = serde_json::to_string(&Serde(&point)).unwrap();
+//!     assert_eq!(j, r#"{"x":1,"y":-2}"#);
+//!
+//!     let Serde(back): Serde<Point> = serde_json::from_str(&j).unwrap();
+//!     assert_eq!(back, point);
+//! }
+//! ```
+
+mod de;
+mod ser;
+
+pub use self::de::deserialize;
+
+/// Adapter which implements Serde's `Serialize` and `Deserialize` for a type
+/// which implements miniserde's.
+///
+/// Serializing drives the [`Fragment`]s of the value through the Serde
+/// serializer. Sequences and maps become Serde sequences and maps, strings of
+/// bytes become Serde bytes, null becomes Serde's unit, and raw JSON is
+/// parsed and serialized as the value it holds. Deserializing works by way of
+/// [`deserialize`].
+///
+/// Only self-describing formats such as JSON, YAML or CBOR can be read back.
+/// A format like bincode or postcard, which writes no type information and
+/// relies on being told what to expect, can only be serialized to. Even then
+/// miniserde has no notion of an `Option`, so an absent value is written as a
+/// unit, which those formats write as nothing at all, and a present one is
+/// written as itself.
+///
+/// [`Fragment`]: crate::ser::Fragment
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Serde<T>(pub T);
+
+/// Adapter which implements miniserde's `Serialize` for a type which
+/// implements Serde's.
+///
+/// The value is first serialized into an in-memory tree, from which the
+/// fragments are produced. Maps and structs keep their order. Their keys may
+/// be strings, chars, integers or bools, and Serde enum variants are
+/// represented the way Serde's JSON represents them: a unit variant as its
+/// name, and any other variant as a map with one entry from its name to its
+/// content.
+///
+/// # Panics
+///
+/// Miniserde serialization cannot fail, so this panics if serializing through
+/// Serde fails, for example on a map with keys of some other type. Use
+/// [`MiniSer::try_new`] for values where that can happen.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct MiniSer<T>(pub T);
+
+/// A value already serialized through Serde, returned by
+/// [`MiniSer::try_new`].
+///
+/// It implements miniserde's `Serialize` and holds no reference to the value
+/// it came from.
+pub struct Converted(self::ser::Content);
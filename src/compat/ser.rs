@@ -0,0 +1,533 @@
+use crate::compat::{Converted, MiniSer, Serde};
+use crate::error::Error;
+use crate::json;
+use crate::private;
+use crate::ser::{self, Fragment, Serialize};
+use alloc::borrow::{Cow, ToOwned};
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Display;
+use core::slice;
+use serde::ser::{
+    SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant, SerializeTuple,
+    SerializeTupleStruct, SerializeTupleVariant, Serializer,
+};
+
+impl<T> serde::Serialize for Serde<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_fragment(self.0.begin(), serializer)
+    }
+}
+
+// Serde serializers recurse into nested sequences and maps, so this does too.
+fn serialize_fragment<S>(fragment: Fragment, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match fragment {
+        Fragment::Null => serializer.serialize_unit(),
+        Fragment::Bool(b) => serializer.serialize_bool(b),
+        Fragment::Str(s) => serializer.serialize_str(&s),
+        Fragment::U64(n) => serializer.serialize_u64(n),
+        Fragment::I64(n) => serializer.serialize_i64(n),
+        Fragment::F64(n) => serializer.serialize_f64(n),
+        Fragment::Bytes(b) => serializer.serialize_bytes(&b),
+        Fragment::RawJson(raw) => {
+            // The text of a RawValue is always valid JSON.
+            let value = json::from_str::<json::Value>(raw.get()).unwrap();
+            serialize_fragment(value.begin(), serializer)
+        }
+        Fragment::Seq(mut seq) => {
+            let mut out = serializer.serialize_seq(seq.size_hint())?;
+            while let Some(element) = seq.next() {
+                out.serialize_element(&Serde(element))?;
+            }
+            out.end()
+        }
+        Fragment::Map(mut map) => {
+            let mut out = serializer.serialize_map(map.size_hint())?;
+            while let Some((key, value)) = map.next() {
+                out.serialize_entry(&*key, &Serde(value))?;
+            }
+            out.end()
+        }
+    }
+}
+
+impl<T> Serialize for MiniSer<T>
+where
+    T: serde::Serialize,
+{
+    fn begin(&self) -> Fragment {
+        match self.0.serialize(ContentSerializer) {
+            Ok(Content::Seq(elements)) => Fragment::Seq(Box::new(OwnedSeq { elements, next: 0 })),
+            Ok(Content::Map(entries)) => Fragment::Map(Box::new(OwnedMap { entries, next: 0 })),
+            Ok(Content::Str(s)) => Fragment::Str(Cow::Owned(s)),
+            Ok(Content::Bytes(b)) => Fragment::Bytes(Cow::Owned(b)),
+            Ok(Content::Bool(b)) => Fragment::Bool(b),
+            Ok(Content::U64(n)) => Fragment::U64(n),
+            Ok(Content::I64(n)) => Fragment::I64(n),
+            Ok(Content::F64(n)) => Fragment::F64(n),
+            Ok(Content::Null) => Fragment::Null,
+            Err(Error) => panic!("MiniSer: the value failed to serialize through Serde"),
+        }
+    }
+}
+
+impl<T> MiniSer<T>
+where
+    T: serde::Serialize,
+{
+    /// Serialize the value through Serde right away, failing instead of
+    /// panicking later if Serde fails.
+    ///
+    /// ```rust
+    /// use miniserde::compat::MiniSer;
+    /// use miniserde::json;
+    /// use std::collections::BTreeMap;
+    ///
+    /// let mut map = BTreeMap::new();
+    /// map.insert((1, 2), 3);
+    /// assert!(MiniSer::try_new(&map).is_err());
+    ///
+    /// let converted = MiniSer::try_new(vec!["a", "b"]).unwrap();
+    /// assert_eq!(json::to_string(&converted), r#"["a","b"]"#);
+    /// ```
+    pub fn try_new(value: T) -> crate::Result<Converted> {
+        value.serialize(ContentSerializer).map(Converted)
+    }
+}
+
+impl Serialize for Converted {
+    fn begin(&self) -> Fragment {
+        self.0.begin()
+    }
+}
+
+impl serde::ser::Error for Error {
+    fn custom<T: Display>(_msg: T) -> Self {
+        Error
+    }
+}
+
+// What a Serde-serializable value turns into, in the terms of miniserde's
+// fragments.
+pub(super) enum Content {
+    Null,
+    Bool(bool),
+    U64(u64),
+    I64(i64),
+    F64(f64),
+    Str(String),
+    Bytes(Vec<u8>),
+    Seq(Vec<Content>),
+    Map(Vec<(String, Content)>),
+}
+
+impl Serialize for Content {
+    fn begin(&self) -> Fragment {
+        match self {
+            Content::Null => Fragment::Null,
+            Content::Bool(b) => Fragment::Bool(*b),
+            Content::U64(n) => Fragment::U64(*n),
+            Content::I64(n) => Fragment::I64(*n),
+            Content::F64(n) => Fragment::F64(*n),
+            Content::Str(s) => Fragment::Str(Cow::Borrowed(s)),
+            Content::Bytes(b) => Fragment::Bytes(Cow::Borrowed(b)),
+            Content::Seq(elements) => private::stream_slice(elements),
+            Content::Map(entries) => Fragment::Map(Box::new(EntriesStream(entries.iter()))),
+        }
+    }
+}
+
+struct EntriesStream<'a>(slice::Iter<'a, (String, Content)>);
+
+impl<'a> ser::Map for EntriesStream<'a> {
+    fn next(&mut self) -> Option<(Cow<str>, &dyn Serialize)> {
+        let (k, v) = self.0.next()?;
+        Some((Cow::Borrowed(k), v as &dyn Serialize))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.0.len())
+    }
+}
+
+struct OwnedSeq {
+    elements: Vec<Content>,
+    next: usize,
+}
+
+impl ser::Seq for OwnedSeq {
+    fn next(&mut self) -> Option<&dyn Serialize> {
+        let element = self.elements.get(self.next)?;
+        self.next += 1;
+        Some(element)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.elements.len() - self.next)
+    }
+}
+
+struct OwnedMap {
+    entries: Vec<(String, Content)>,
+    next: usize,
+}
+
+impl ser::Map for OwnedMap {
+    fn next(&mut self) -> Option<(Cow<str>, &dyn Serialize)> {
+        let (k, v) = self.entries.get(self.next)?;
+        self.next += 1;
+        Some((Cow::Borrowed(k), v as &dyn Serialize))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len() - self.next)
+    }
+}
+
+struct ContentSerializer;
+
+impl Serializer for ContentSerializer {
+    type Ok = Content;
+    type Error = Error;
+    type SerializeSeq = SeqBuilder;
+    type SerializeTuple = SeqBuilder;
+    type SerializeTupleStruct = SeqBuilder;
+    type SerializeTupleVariant = VariantBuilder<SeqBuilder>;
+    type SerializeMap = MapBuilder;
+    type SerializeStruct = MapBuilder;
+    type SerializeStructVariant = VariantBuilder<MapBuilder>;
+
+    fn serialize_bool(self, v: bool) -> Result<Content, Error> {
+        Ok(Content::Bool(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Content, Error> {
+        Ok(Content::I64(v as i64))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Content, Error> {
+        Ok(Content::I64(v as i64))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Content, Error> {
+        Ok(Content::I64(v as i64))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Content, Error> {
+        Ok(Content::I64(v))
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<Content, Error> {
+        if let Ok(n) = u64::try_from(v) {
+            Ok(Content::U64(n))
+        } else {
+            i64::try_from(v).map(Content::I64).map_err(|_| Error)
+        }
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Content, Error> {
+        Ok(Content::U64(v as u64))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Content, Error> {
+        Ok(Content::U64(v as u64))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Content, Error> {
+        Ok(Content::U64(v as u64))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Content, Error> {
+        Ok(Content::U64(v))
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Content, Error> {
+        u64::try_from(v).map(Content::U64).map_err(|_| Error)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Content, Error> {
+        Ok(Content::F64(v as f64))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Content, Error> {
+        Ok(Content::F64(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Content, Error> {
+        Ok(Content::Str(v.to_string()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Content, Error> {
+        Ok(Content::Str(v.to_owned()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Content, Error> {
+        Ok(Content::Bytes(v.to_owned()))
+    }
+
+    fn serialize_none(self) -> Result<Content, Error> {
+        Ok(Content::Null)
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Content, Error>
+    where
+        T: ?Sized + serde::Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Content, Error> {
+        Ok(Content::Null)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Content, Error> {
+        Ok(Content::Null)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Content, Error> {
+        Ok(Content::Str(variant.to_owned()))
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<Content, Error>
+    where
+        T: ?Sized + serde::Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Content, Error>
+    where
+        T: ?Sized + serde::Serialize,
+    {
+        let content = value.serialize(self)?;
+        Ok(Content::Map(alloc::vec![(variant.to_owned(), content)]))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SeqBuilder, Error> {
+        Ok(SeqBuilder {
+            elements: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SeqBuilder, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<SeqBuilder, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<VariantBuilder<SeqBuilder>, Error> {
+        Ok(VariantBuilder {
+            variant,
+            builder: self.serialize_seq(Some(len))?,
+        })
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<MapBuilder, Error> {
+        Ok(MapBuilder {
+            entries: Vec::with_capacity(len.unwrap_or(0)),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<MapBuilder, Error> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<VariantBuilder<MapBuilder>, Error> {
+        Ok(VariantBuilder {
+            variant,
+            builder: self.serialize_map(Some(len))?,
+        })
+    }
+}
+
+struct SeqBuilder {
+    elements: Vec<Content>,
+}
+
+impl SerializeSeq for SeqBuilder {
+    type Ok = Content;
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + serde::Serialize,
+    {
+        self.elements.push(value.serialize(ContentSerializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Content, Error> {
+        Ok(Content::Seq(self.elements))
+    }
+}
+
+impl SerializeTuple for SeqBuilder {
+    type Ok = Content;
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + serde::Serialize,
+    {
+        SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Content, Error> {
+        SerializeSeq::end(self)
+    }
+}
+
+impl SerializeTupleStruct for SeqBuilder {
+    type Ok = Content;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + serde::Serialize,
+    {
+        SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Content, Error> {
+        SerializeSeq::end(self)
+    }
+}
+
+struct MapBuilder {
+    entries: Vec<(String, Content)>,
+    key: Option<String>,
+}
+
+impl SerializeMap for MapBuilder {
+    type Ok = Content;
+    type Error = Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), Error>
+    where
+        T: ?Sized + serde::Serialize,
+    {
+        self.key = Some(match key.serialize(ContentSerializer)? {
+            Content::Str(s) => s,
+            Content::U64(n) => itoa::Buffer::new().format(n).to_owned(),
+            Content::I64(n) => itoa::Buffer::new().format(n).to_owned(),
+            Content::Bool(b) => b.to_string(),
+            _ => return Err(Error),
+        });
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + serde::Serialize,
+    {
+        let key = self.key.take().ok_or(Error)?;
+        self.entries
+            .push((key, value.serialize(ContentSerializer)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Content, Error> {
+        Ok(Content::Map(self.entries))
+    }
+}
+
+impl SerializeStruct for MapBuilder {
+    type Ok = Content;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + serde::Serialize,
+    {
+        let value = value.serialize(ContentSerializer)?;
+        self.entries.push((key.to_owned(), value));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Content, Error> {
+        SerializeMap::end(self)
+    }
+}
+
+// The content of a tuple or struct variant, to be wrapped in a map from the
+// name of the variant.
+struct VariantBuilder<B> {
+    variant: &'static str,
+    builder: B,
+}
+
+impl<B> VariantBuilder<B> {
+    fn wrap(variant: &'static str, content: Content) -> Content {
+        Content::Map(alloc::vec![(variant.to_owned(), content)])
+    }
+}
+
+impl SerializeTupleVariant for VariantBuilder<SeqBuilder> {
+    type Ok = Content;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + serde::Serialize,
+    {
+        SerializeSeq::serialize_element(&mut self.builder, value)
+    }
+
+    fn end(self) -> Result<Content, Error> {
+        let content = SerializeSeq::end(self.builder)?;
+        Ok(Self::wrap(self.variant, content))
+    }
+}
+
+impl SerializeStructVariant for VariantBuilder<MapBuilder> {
+    type Ok = Content;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + serde::Serialize,
+    {
+        SerializeStruct::serialize_field(&mut self.builder, key, value)
+    }
+
+    fn end(self) -> Result<Content, Error> {
+        let content = SerializeMap::end(self.builder)?;
+        Ok(Self::wrap(self.variant, content))
+    }
+}
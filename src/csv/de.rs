@@ -0,0 +1,344 @@
+use crate::de::{DeserializeOwned, Visitor};
+use crate::error::{Error, Result};
+use crate::text;
+#[cfg(feature = "std")]
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use core::mem;
+#[cfg(feature = "std")]
+use std::io::{self, Read};
+
+/// Deserialize the rows of a CSV string.
+///
+/// The first row is the header, and the iterator yields one record for each
+/// row after it.
+pub fn from_str<'a, T>(s: &'a str) -> Rows<'a, T> {
+    Rows::with_input(Input::Slice(s.as_bytes()))
+}
+
+/// Deserialize the rows of CSV read from a reader.
+///
+/// The reader is read in chunks of up to 8 KiB, so it may be read past the end
+/// of the row being returned; the rest is kept for the following rows. A read
+/// is only made when the buffered input does not yet contain the end of the
+/// next row.
+#[cfg(feature = "std")]
+pub fn from_reader<'a, T, R>(reader: R) -> Rows<'a, T>
+where
+    R: Read + 'a,
+{
+    Rows::with_input(Input::Reader {
+        reader: Box::new(reader),
+        buffer: Vec::new(),
+        eof: false,
+    })
+}
+
+/// Iterator that deserializes the rows of a CSV file.
+///
+/// Each row after the header is fed to the record's map, pairing each cell
+/// with the name of its column. Rows may end with CRLF or with just LF. Blank
+/// lines are skipped, unless there is only one column, in which case they are
+/// rows whose only cell is empty.
+///
+/// A row which has a different number of cells than the header, or which
+/// does not deserialize, yields an error and the iterator moves on to the
+/// next row. Malformed CSV, such as an unterminated quoted field, ends the
+/// iterator after yielding an error.
+pub struct Rows<'a, T> {
+    input: Input<'a>,
+    // Position of the next unread byte within the buffered input.
+    pos: usize,
+    row: Row,
+    header: Option<Vec<String>>,
+    done: bool,
+    marker: PhantomData<fn() -> T>,
+}
+
+enum Input<'a> {
+    Slice(&'a [u8]),
+    #[cfg(feature = "std")]
+    Reader {
+        reader: Box<dyn Read + 'a>,
+        buffer: Vec<u8>,
+        eof: bool,
+    },
+}
+
+struct Field {
+    text: String,
+    quoted: bool,
+}
+
+// The row being parsed. It is kept from one refill of the buffer to the next,
+// so that each byte of the input is looked at only once however long the row.
+#[derive(Default)]
+struct Row {
+    fields: Vec<Field>,
+    text: Vec<u8>,
+    quoted: bool,
+    state: State,
+}
+
+#[derive(Default, Copy, Clone)]
+enum State {
+    #[default]
+    FieldStart,
+    Unquoted,
+    Quoted,
+    // After a quote within a quoted field, which either ends the field or is
+    // the first of two standing for one.
+    Quote,
+    // After the CR of a CRLF.
+    CarriageReturn,
+}
+
+impl<'a, T> Rows<'a, T> {
+    fn with_input(input: Input<'a>) -> Self {
+        Rows {
+            input,
+            pos: 0,
+            row: Row::default(),
+            header: None,
+            done: false,
+            marker: PhantomData,
+        }
+    }
+
+    /// The names of the columns, once the header row has been read.
+    pub fn header(&self) -> Option<&[String]> {
+        self.header.as_deref()
+    }
+
+    // Makes more input available after the end of `bytes()`. Returns false at
+    // the end of the input. Bytes before `pos` may be discarded.
+    fn fill(&mut self) -> Result<bool> {
+        match &mut self.input {
+            Input::Slice(_) => Ok(false),
+            #[cfg(feature = "std")]
+            Input::Reader {
+                reader,
+                buffer,
+                eof,
+            } => {
+                if *eof {
+                    return Ok(false);
+                }
+                buffer.drain(..self.pos);
+                self.pos = 0;
+
+                let len = buffer.len();
+                buffer.resize(len + 8192, 0);
+                loop {
+                    match reader.read(&mut buffer[len..]) {
+                        Ok(n) => {
+                            buffer.truncate(len + n);
+                            *eof = n == 0;
+                            return Ok(n > 0);
+                        }
+                        Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+                        Err(_) => {
+                            buffer.truncate(len);
+                            *eof = true;
+                            return Err(Error);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // Reads the cells of the next row, refilling the buffer for as long as the
+    // row runs past its end.
+    fn next_row(&mut self) -> Result<Option<Vec<Field>>> {
+        loop {
+            let rest = &self.input.bytes()[self.pos..];
+            if rest.is_empty() {
+                if self.input.eof() || !self.fill()? {
+                    if self.row.is_empty() {
+                        return Ok(None);
+                    }
+                    self.row.finish()?;
+                    return Ok(Some(mem::take(&mut self.row.fields)));
+                }
+                continue;
+            }
+            let (len, complete) = self.row.parse(rest)?;
+            self.pos += len;
+            if complete {
+                return Ok(Some(mem::take(&mut self.row.fields)));
+            }
+        }
+    }
+
+    fn next_record(&mut self) -> Result<Option<Vec<Field>>> {
+        loop {
+            let Some(fields) = self.next_row()? else {
+                return Ok(None);
+            };
+            let blank = matches!(&fields[..], [field] if field.text.is_empty() && !field.quoted);
+            let single_column = self.header.as_ref().is_some_and(|h| h.len() == 1);
+            if !blank || single_column {
+                return Ok(Some(fields));
+            }
+        }
+    }
+}
+
+impl<'a> Input<'a> {
+    fn bytes(&self) -> &[u8] {
+        match self {
+            Input::Slice(bytes) => bytes,
+            #[cfg(feature = "std")]
+            Input::Reader { buffer, .. } => buffer,
+        }
+    }
+
+    fn eof(&self) -> bool {
+        match self {
+            Input::Slice(_) => true,
+            #[cfg(feature = "std")]
+            Input::Reader { eof, .. } => *eof,
+        }
+    }
+}
+
+impl<'a, T> Iterator for Rows<'a, T>
+where
+    T: DeserializeOwned,
+{
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        if self.header.is_none() {
+            match self.next_record() {
+                Ok(Some(fields)) => {
+                    let mut names: Vec<String> =
+                        fields.into_iter().map(|field| field.text).collect();
+                    // Written at the start of the file by some spreadsheets.
+                    if names[0].starts_with('\u{feff}') {
+                        names[0].remove(0);
+                    }
+                    self.header = Some(names);
+                }
+                Ok(None) => {
+                    self.done = true;
+                    return None;
+                }
+                Err(error) => {
+                    self.done = true;
+                    return Some(Err(error));
+                }
+            }
+        }
+
+        match self.next_record() {
+            Ok(Some(fields)) => {
+                let header = self.header.as_ref().unwrap();
+                let mut out = None;
+                let result = from_row_impl(header, &fields, T::begin(&mut out));
+                Some(result.and_then(|()| out.ok_or(Error)))
+            }
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(error) => {
+                self.done = true;
+                Some(Err(error))
+            }
+        }
+    }
+}
+
+fn from_row_impl(header: &[String], fields: &[Field], visitor: &mut dyn Visitor) -> Result<()> {
+    if fields.len() != header.len() {
+        return Err(Error);
+    }
+    let mut map = visitor.map()?;
+    for (name, field) in header.iter().zip(fields) {
+        let visitor = map.key(name)?;
+        if field.text.is_empty() && !field.quoted && visitor.null().is_ok() {
+            continue;
+        }
+        if visitor.string(&field.text).is_err() {
+            text::visit_scalar(visitor, &field.text)?;
+        }
+    }
+    map.finish()
+}
+
+impl Row {
+    fn is_empty(&self) -> bool {
+        self.fields.is_empty() && matches!(self.state, State::FieldStart)
+    }
+
+    // Parses as much of the row as `bytes` holds, returning the number of bytes
+    // consumed and whether they completed the row, up to its line break.
+    fn parse(&mut self, bytes: &[u8]) -> Result<(usize, bool)> {
+        for (i, &b) in bytes.iter().enumerate() {
+            match (self.state, b) {
+                (State::FieldStart, b'"') => {
+                    self.quoted = true;
+                    self.state = State::Quoted;
+                }
+                (State::FieldStart | State::Unquoted | State::Quote, b',') => {
+                    self.end_field()?;
+                    self.state = State::FieldStart;
+                }
+                (State::FieldStart | State::Unquoted | State::Quote, b'\n') => {
+                    self.end_field()?;
+                    self.state = State::FieldStart;
+                    return Ok((i + 1, true));
+                }
+                (State::FieldStart | State::Unquoted | State::Quote, b'\r') => {
+                    self.end_field()?;
+                    self.state = State::CarriageReturn;
+                }
+                (State::Unquoted, b'"') => return Err(Error),
+                (State::FieldStart | State::Unquoted, _) => {
+                    self.text.push(b);
+                    self.state = State::Unquoted;
+                }
+                (State::Quoted, b'"') => self.state = State::Quote,
+                (State::Quoted, _) => self.text.push(b),
+                (State::Quote, b'"') => {
+                    self.text.push(b'"');
+                    self.state = State::Quoted;
+                }
+                // Anything after the closing quote of a field.
+                (State::Quote, _) => return Err(Error),
+                (State::CarriageReturn, b'\n') => {
+                    self.state = State::FieldStart;
+                    return Ok((i + 1, true));
+                }
+                (State::CarriageReturn, _) => return Err(Error),
+            }
+        }
+        Ok((bytes.len(), false))
+    }
+
+    // Completes the row at the end of the input, which has no line break.
+    fn finish(&mut self) -> Result<()> {
+        match self.state {
+            State::FieldStart | State::Unquoted | State::Quote => {
+                self.state = State::FieldStart;
+                self.end_field()
+            }
+            State::Quoted | State::CarriageReturn => Err(Error),
+        }
+    }
+
+    fn end_field(&mut self) -> Result<()> {
+        let text = String::from_utf8(mem::take(&mut self.text)).map_err(|_| Error)?;
+        let quoted = mem::take(&mut self.quoted);
+        self.fields.push(Field { text, quoted });
+        Ok(())
+    }
+}
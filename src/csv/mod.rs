@@ -0,0 +1,68 @@
+//! Comma-separated values as described by [RFC 4180].
+//!
+//! Each row of a CSV file is a record, and the first row holds the names of
+//! the columns. Records are serialized from and deserialized into maps or
+//! structs whose keys are the column names and whose values are scalars or
+//! options.
+//!
+//! [RFC 4180]: https://tools.ietf.org/html/rfc4180
+//!
+//! ```rust
+//! use miniserde::{csv, Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize, Debug, PartialEq)]
+//! struct Sale {
+//!     item: String,
+//!     quantity: u32,
+//!     price: f64,
+//!     note: Option<String>,
+//! }
+//!
+//! fn main() -> miniserde::Result<()> {
+//!     let sales = vec![
+//!         Sale {
+//!             item: "Widget".to_owned(),
+//!             quantity: 3,
+//!             price: 2.5,
+//!             note: None,
+//!         },
+//!         Sale {
+//!             item: "Gadget, large".to_owned(),
+//!             quantity: 1,
+//!             price: 10.0,
+//!             note: Some("say \"hi\"".to_owned()),
+//!         },
+//!     ];
+//!
+//!     let mut out = Vec::new();
+//!     csv::to_writer(&mut out, &sales)?;
+//!     let text = String::from_utf8(out).unwrap();
+//!     assert_eq!(
+//!         text,
+//!         "item,quantity,price,note\r\n\
+//!          Widget,3,2.5,\r\n\
+//!          \"Gadget, large\",1,10.0,\"say \"\"hi\"\"\"\r\n",
+//!     );
+//!
+//!     let rows = csv::from_str::<Sale>(&text).collect::<miniserde::Result<Vec<_>>>()?;
+//!     assert_eq!(rows, sales);
+//!
+//!     Ok(())
+//! }
+//! ```
+//!
+//! A cell is offered to its field as a string first. If the field does not
+//! accept a string, the text of the cell is parsed as a bool, an integer or a
+//! float instead. An empty cell which is not quoted stands for null, so that
+//! it reads back as `None` for an `Option<String>`, while the empty string is
+//! written as `""`.
+
+#[cfg(feature = "std")]
+mod ser;
+#[cfg(feature = "std")]
+pub use self::ser::to_writer;
+
+mod de;
+#[cfg(feature = "std")]
+pub use self::de::from_reader;
+pub use self::de::{from_str, Rows};
@@ -0,0 +1,116 @@
+use crate::base64;
+use crate::error::{Error, Result};
+use crate::ser::{Fragment, Serialize};
+use alloc::string::String;
+use alloc::vec::Vec;
+use std::io::Write;
+
+/// Serialize records as CSV, preceded by a header row.
+///
+/// The header row holds the keys of the first record. Every record after it
+/// must have the same keys in the same order. Fields are quoted only if they
+/// contain a comma, a double quote or a line break, or if they are the empty
+/// string, and each row ends with CRLF.
+///
+/// Nothing at all is written if there are no records. This fails if a record
+/// is not a map or struct, if any of its values is a sequence or map, or if a
+/// float is `NaN` or infinite.
+pub fn to_writer<W, I>(mut writer: W, records: I) -> Result<()>
+where
+    W: Write,
+    I: IntoIterator,
+    I::Item: Serialize,
+{
+    let mut header = None;
+    let mut out = String::new();
+    for record in records {
+        out.clear();
+        write_record(&record, &mut header, &mut out)?;
+        writer.write_all(out.as_bytes()).map_err(|_| Error)?;
+    }
+    Ok(())
+}
+
+fn write_record(
+    record: &dyn Serialize,
+    header: &mut Option<Vec<String>>,
+    out: &mut String,
+) -> Result<()> {
+    let Fragment::Map(mut map) = record.begin() else {
+        return Err(Error);
+    };
+
+    let mut row = String::new();
+    let mut keys = Vec::new();
+    let mut len = 0;
+    while let Some((key, value)) = map.next() {
+        match header {
+            Some(header) => {
+                if header.get(len).map(String::as_str) != Some(&key) {
+                    return Err(Error);
+                }
+            }
+            None => keys.push(key.into_owned()),
+        }
+        if len > 0 {
+            row.push(',');
+        }
+        write_cell(value.begin(), &mut row)?;
+        len += 1;
+    }
+
+    match header {
+        Some(header) => {
+            if header.len() != len {
+                return Err(Error);
+            }
+        }
+        None => {
+            for (i, key) in keys.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_field(key, out);
+            }
+            out.push_str("\r\n");
+            *header = Some(keys);
+        }
+    }
+    out.push_str(&row);
+    out.push_str("\r\n");
+    Ok(())
+}
+
+fn write_cell(fragment: Fragment, out: &mut String) -> Result<()> {
+    match fragment {
+        Fragment::Null => {}
+        Fragment::Bool(b) => out.push_str(if b { "true" } else { "false" }),
+        Fragment::Str(s) => write_field(&s, out),
+        Fragment::U64(n) => out.push_str(itoa::Buffer::new().format(n)),
+        Fragment::I64(n) => out.push_str(itoa::Buffer::new().format(n)),
+        Fragment::F64(n) => {
+            if !n.is_finite() {
+                return Err(Error);
+            }
+            out.push_str(zmij::Buffer::new().format_finite(n));
+        }
+        Fragment::Bytes(b) => base64::encode(&b, out),
+        Fragment::Seq(_) | Fragment::Map(_) | Fragment::RawJson(_) => return Err(Error),
+    }
+    Ok(())
+}
+
+fn write_field(s: &str, out: &mut String) {
+    if !s.is_empty() && !s.contains([',', '"', '\r', '\n']) {
+        out.push_str(s);
+        return;
+    }
+    out.push('"');
+    for (i, part) in s.split('"').enumerate() {
+        if i > 0 {
+            out.push_str("\"\"");
+        }
+        out.push_str(part);
+    }
+    out.push('"');
+}
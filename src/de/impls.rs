@@ -2,11 +2,15 @@ use crate::de::{Deserialize, Map, Seq, Visitor};
 use crate::error::{Error, Result};
 use crate::ignore::Ignore;
 use crate::ptr::NonuniqueBox;
-use alloc::borrow::ToOwned;
+use alloc::borrow::{Cow, ToOwned};
 use alloc::boxed::Box;
 use alloc::collections::BTreeMap;
+use alloc::rc::Rc;
 use alloc::string::String;
+#[cfg(target_has_atomic = "ptr")]
+use alloc::sync::Arc;
 use alloc::vec::Vec;
+use core::marker::PhantomData;
 use core::mem::{self, ManuallyDrop, MaybeUninit};
 use core::ptr;
 use core::str::{self, FromStr};
@@ -15,11 +19,30 @@ use std::collections::HashMap;
 #[cfg(feature = "std")]
 use std::hash::{BuildHasher, Hash};
 
-impl Deserialize for () {
-    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor {
+// Like make_place!, but the place also carries the input lifetime. This tells
+// its visitor methods that the input outlives the borrow of self, which they
+// need in order to hand out the Seq or Map of an inner value.
+macro_rules! make_forwarding_place {
+    ($name:ident) => {
+        #[repr(C)]
+        struct $name<'de, T> {
+            out: Option<T>,
+            marker: PhantomData<&'de ()>,
+        }
+
+        impl<'de, T> $name<'de, T> {
+            fn new(out: &mut Option<T>) -> &mut Self {
+                unsafe { &mut *ptr::addr_of_mut!(*out).cast::<$name<T>>() }
+            }
+        }
+    };
+}
+
+impl<'de> Deserialize<'de> for () {
+    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor<'de> {
         make_place!(Place);
 
-        impl Visitor for Place<()> {
+        impl<'de> Visitor<'de> for Place<()> {
             fn null(&mut self) -> Result<()> {
                 self.out = Some(());
                 Ok(())
@@ -30,11 +53,11 @@ impl Deserialize for () {
     }
 }
 
-impl Deserialize for bool {
-    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor {
+impl<'de> Deserialize<'de> for bool {
+    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor<'de> {
         make_place!(Place);
 
-        impl Visitor for Place<bool> {
+        impl<'de> Visitor<'de> for Place<bool> {
             fn boolean(&mut self, b: bool) -> Result<()> {
                 self.out = Some(b);
                 Ok(())
@@ -45,11 +68,11 @@ impl Deserialize for bool {
     }
 }
 
-impl Deserialize for String {
-    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor {
+impl<'de> Deserialize<'de> for String {
+    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor<'de> {
         make_place!(Place);
 
-        impl Visitor for Place<String> {
+        impl<'de> Visitor<'de> for Place<String> {
             fn string(&mut self, s: &str) -> Result<()> {
                 self.out = Some(s.to_owned());
                 Ok(())
@@ -60,13 +83,72 @@ impl Deserialize for String {
     }
 }
 
+impl<'de: 'a, 'a> Deserialize<'de> for &'a str {
+    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor<'de> {
+        make_place!(Place);
+
+        impl<'de: 'a, 'a> Visitor<'de> for Place<&'a str> {
+            fn borrowed_string(&mut self, s: &'de str) -> Result<()> {
+                self.out = Some(s);
+                Ok(())
+            }
+        }
+
+        Place::new(out)
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for Cow<'a, str> {
+    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor<'de> {
+        make_place!(Place);
+
+        impl<'de: 'a, 'a> Visitor<'de> for Place<Cow<'a, str>> {
+            fn string(&mut self, s: &str) -> Result<()> {
+                self.out = Some(Cow::Owned(s.to_owned()));
+                Ok(())
+            }
+
+            fn borrowed_string(&mut self, s: &'de str) -> Result<()> {
+                self.out = Some(Cow::Borrowed(s));
+                Ok(())
+            }
+        }
+
+        Place::new(out)
+    }
+}
+
+macro_rules! owned_str {
+    ($($ty:ty)*) => {
+        $(
+            impl<'de> Deserialize<'de> for $ty {
+                fn begin(out: &mut Option<Self>) -> &mut dyn Visitor<'de> {
+                    make_place!(Place);
+
+                    impl<'de> Visitor<'de> for Place<$ty> {
+                        fn string(&mut self, s: &str) -> Result<()> {
+                            self.out = Some(<$ty>::from(s));
+                            Ok(())
+                        }
+                    }
+
+                    Place::new(out)
+                }
+            }
+        )*
+    };
+}
+owned_str!(Box<str> Rc<str>);
+#[cfg(target_has_atomic = "ptr")]
+owned_str!(Arc<str>);
+
 macro_rules! signed {
     ($ty:ident) => {
-        impl Deserialize for $ty {
-            fn begin(out: &mut Option<Self>) -> &mut dyn Visitor {
+        impl<'de> Deserialize<'de> for $ty {
+            fn begin(out: &mut Option<Self>) -> &mut dyn Visitor<'de> {
                 make_place!(Place);
 
-                impl Visitor for Place<$ty> {
+                impl<'de> Visitor<'de> for Place<$ty> {
                     fn negative(&mut self, n: i64) -> Result<()> {
                         if n >= $ty::MIN as i64 {
                             self.out = Some(n as $ty);
@@ -99,11 +181,11 @@ signed!(isize);
 
 macro_rules! unsigned {
     ($ty:ident) => {
-        impl Deserialize for $ty {
-            fn begin(out: &mut Option<Self>) -> &mut dyn Visitor {
+        impl<'de> Deserialize<'de> for $ty {
+            fn begin(out: &mut Option<Self>) -> &mut dyn Visitor<'de> {
                 make_place!(Place);
 
-                impl Visitor for Place<$ty> {
+                impl<'de> Visitor<'de> for Place<$ty> {
                     fn nonnegative(&mut self, n: u64) -> Result<()> {
                         if n <= $ty::MAX as u64 {
                             self.out = Some(n as $ty);
@@ -127,11 +209,11 @@ unsigned!(usize);
 
 macro_rules! float {
     ($ty:ident) => {
-        impl Deserialize for $ty {
-            fn begin(out: &mut Option<Self>) -> &mut dyn Visitor {
+        impl<'de> Deserialize<'de> for $ty {
+            fn begin(out: &mut Option<Self>) -> &mut dyn Visitor<'de> {
                 make_place!(Place);
 
-                impl Visitor for Place<$ty> {
+                impl<'de> Visitor<'de> for Place<$ty> {
                     fn negative(&mut self, n: i64) -> Result<()> {
                         self.out = Some(n as $ty);
                         Ok(())
@@ -156,16 +238,16 @@ macro_rules! float {
 float!(f32);
 float!(f64);
 
-impl<T> Deserialize for Box<T>
+impl<'de, T> Deserialize<'de> for Box<T>
 where
-    T: Deserialize,
+    T: Deserialize<'de>,
 {
-    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor {
-        make_place!(Place);
+    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor<'de> {
+        make_forwarding_place!(Place);
 
-        impl<T> Visitor for Place<Box<T>>
+        impl<'de, T> Visitor<'de> for Place<'de, Box<T>>
         where
-            T: Deserialize,
+            T: Deserialize<'de>,
         {
             fn null(&mut self) -> Result<()> {
                 let mut out = None;
@@ -188,6 +270,13 @@ where
                 Ok(())
             }
 
+            fn borrowed_string(&mut self, s: &'de str) -> Result<()> {
+                let mut out = None;
+                Deserialize::begin(&mut out).borrowed_string(s)?;
+                self.out = Some(Box::new(out.unwrap()));
+                Ok(())
+            }
+
             fn negative(&mut self, n: i64) -> Result<()> {
                 let mut out = None;
                 Deserialize::begin(&mut out).negative(n)?;
@@ -209,7 +298,25 @@ where
                 Ok(())
             }
 
-            fn seq(&mut self) -> Result<Box<dyn Seq + '_>> {
+            fn bytes(&mut self, b: &[u8]) -> Result<()> {
+                let mut out = None;
+                Deserialize::begin(&mut out).bytes(b)?;
+                self.out = Some(Box::new(out.unwrap()));
+                Ok(())
+            }
+
+            fn wants_raw_json(&mut self) -> bool {
+                T::begin(&mut None).wants_raw_json()
+            }
+
+            fn raw_json(&mut self, j: &str) -> Result<()> {
+                let mut out = None;
+                Deserialize::begin(&mut out).raw_json(j)?;
+                self.out = Some(Box::new(out.unwrap()));
+                Ok(())
+            }
+
+            fn seq(&mut self) -> Result<Box<dyn Seq<'de> + '_>> {
                 let mut value = NonuniqueBox::new(None);
                 let ptr = unsafe { extend_lifetime!(&mut *value as &mut Option<T>) };
                 Ok(Box::new(BoxSeq {
@@ -219,7 +326,7 @@ where
                 }))
             }
 
-            fn map(&mut self) -> Result<Box<dyn Map + '_>> {
+            fn map(&mut self) -> Result<Box<dyn Map<'de> + '_>> {
                 let mut value = NonuniqueBox::new(None);
                 let ptr = unsafe { extend_lifetime!(&mut *value as &mut Option<T>) };
                 Ok(Box::new(BoxMap {
@@ -230,24 +337,24 @@ where
             }
         }
 
-        struct BoxSeq<'a, T: 'a> {
+        struct BoxSeq<'a, 'de, T: 'a> {
             out: &'a mut Option<Box<T>>,
             value: NonuniqueBox<Option<T>>,
             // May borrow from self.value, so must drop first.
-            seq: ManuallyDrop<Box<dyn Seq + 'a>>,
+            seq: ManuallyDrop<Box<dyn Seq<'de> + 'a>>,
         }
 
-        impl<'a, T: 'a> Drop for BoxSeq<'a, T> {
+        impl<'a, 'de, T: 'a> Drop for BoxSeq<'a, 'de, T> {
             fn drop(&mut self) {
                 unsafe { ManuallyDrop::drop(&mut self.seq) }
             }
         }
 
-        impl<'a, T> Seq for BoxSeq<'a, T>
+        impl<'a, 'de, T> Seq<'de> for BoxSeq<'a, 'de, T>
         where
-            T: Deserialize,
+            T: Deserialize<'de>,
         {
-            fn element(&mut self) -> Result<&mut dyn Visitor> {
+            fn element(&mut self) -> Result<&mut dyn Visitor<'de>> {
                 self.seq.element()
             }
 
@@ -259,24 +366,24 @@ where
             }
         }
 
-        struct BoxMap<'a, T: 'a> {
+        struct BoxMap<'a, 'de, T: 'a> {
             out: &'a mut Option<Box<T>>,
             value: NonuniqueBox<Option<T>>,
             // May borrow from self.value, so must drop first.
-            map: ManuallyDrop<Box<dyn Map + 'a>>,
+            map: ManuallyDrop<Box<dyn Map<'de> + 'a>>,
         }
 
-        impl<'a, T: 'a> Drop for BoxMap<'a, T> {
+        impl<'a, 'de, T: 'a> Drop for BoxMap<'a, 'de, T> {
             fn drop(&mut self) {
                 unsafe { ManuallyDrop::drop(&mut self.map) }
             }
         }
 
-        impl<'a, T> Map for BoxMap<'a, T>
+        impl<'a, 'de, T> Map<'de> for BoxMap<'a, 'de, T>
         where
-            T: Deserialize,
+            T: Deserialize<'de>,
         {
-            fn key(&mut self, k: &str) -> Result<&mut dyn Visitor> {
+            fn key(&mut self, k: &str) -> Result<&mut dyn Visitor<'de>> {
                 self.map.key(k)
             }
 
@@ -292,21 +399,21 @@ where
     }
 }
 
-impl<T> Deserialize for Option<T>
+impl<'de, T> Deserialize<'de> for Option<T>
 where
-    T: Deserialize,
+    T: Deserialize<'de>,
 {
     #[inline]
     fn default() -> Option<Self> {
         Some(None)
     }
 
-    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor {
-        make_place!(Place);
+    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor<'de> {
+        make_forwarding_place!(Place);
 
-        impl<T> Visitor for Place<Option<T>>
+        impl<'de, T> Visitor<'de> for Place<'de, Option<T>>
         where
-            T: Deserialize,
+            T: Deserialize<'de>,
         {
             fn null(&mut self) -> Result<()> {
                 self.out = Some(None);
@@ -323,6 +430,11 @@ where
                 Deserialize::begin(self.out.as_mut().unwrap()).string(s)
             }
 
+            fn borrowed_string(&mut self, s: &'de str) -> Result<()> {
+                self.out = Some(None);
+                Deserialize::begin(self.out.as_mut().unwrap()).borrowed_string(s)
+            }
+
             fn negative(&mut self, n: i64) -> Result<()> {
                 self.out = Some(None);
                 Deserialize::begin(self.out.as_mut().unwrap()).negative(n)
@@ -338,35 +450,52 @@ where
                 Deserialize::begin(self.out.as_mut().unwrap()).float(n)
             }
 
-            fn seq(&mut self) -> Result<Box<dyn Seq + '_>> {
+            fn bytes(&mut self, b: &[u8]) -> Result<()> {
+                self.out = Some(None);
+                Deserialize::begin(self.out.as_mut().unwrap()).bytes(b)
+            }
+
+            fn seq(&mut self) -> Result<Box<dyn Seq<'de> + '_>> {
                 self.out = Some(None);
                 Deserialize::begin(self.out.as_mut().unwrap()).seq()
             }
 
-            fn map(&mut self) -> Result<Box<dyn Map + '_>> {
+            fn map(&mut self) -> Result<Box<dyn Map<'de> + '_>> {
                 self.out = Some(None);
                 Deserialize::begin(self.out.as_mut().unwrap()).map()
             }
+
+            fn wants_raw_json(&mut self) -> bool {
+                T::begin(&mut None).wants_raw_json()
+            }
+
+            fn raw_json(&mut self, j: &str) -> Result<()> {
+                self.out = Some(None);
+                if j == "null" {
+                    return Ok(());
+                }
+                Deserialize::begin(self.out.as_mut().unwrap()).raw_json(j)
+            }
         }
 
         Place::new(out)
     }
 }
 
-impl<A, B> Deserialize for (A, B)
+impl<'de, A, B> Deserialize<'de> for (A, B)
 where
-    A: Deserialize,
-    B: Deserialize,
+    A: Deserialize<'de>,
+    B: Deserialize<'de>,
 {
-    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor {
+    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor<'de> {
         make_place!(Place);
 
-        impl<A, B> Visitor for Place<(A, B)>
+        impl<'de, A, B> Visitor<'de> for Place<(A, B)>
         where
-            A: Deserialize,
-            B: Deserialize,
+            A: Deserialize<'de>,
+            B: Deserialize<'de>,
         {
-            fn seq(&mut self) -> Result<Box<dyn Seq + '_>> {
+            fn seq(&mut self) -> Result<Box<dyn Seq<'de> + '_>> {
                 Ok(Box::new(TupleBuilder {
                     out: &mut self.out,
                     tuple: (None, None),
@@ -379,12 +508,12 @@ where
             tuple: (Option<A>, Option<B>),
         }
 
-        impl<'a, A, B> Seq for TupleBuilder<'a, A, B>
+        impl<'a, 'de, A, B> Seq<'de> for TupleBuilder<'a, A, B>
         where
-            A: Deserialize,
-            B: Deserialize,
+            A: Deserialize<'de>,
+            B: Deserialize<'de>,
         {
-            fn element(&mut self) -> Result<&mut dyn Visitor> {
+            fn element(&mut self) -> Result<&mut dyn Visitor<'de>> {
                 if self.tuple.0.is_none() {
                     Ok(Deserialize::begin(&mut self.tuple.0))
                 } else if self.tuple.1.is_none() {
@@ -408,18 +537,18 @@ where
     }
 }
 
-impl<T> Deserialize for Vec<T>
+impl<'de, T> Deserialize<'de> for Vec<T>
 where
-    T: Deserialize,
+    T: Deserialize<'de>,
 {
-    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor {
+    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor<'de> {
         make_place!(Place);
 
-        impl<T> Visitor for Place<Vec<T>>
+        impl<'de, T> Visitor<'de> for Place<Vec<T>>
         where
-            T: Deserialize,
+            T: Deserialize<'de>,
         {
-            fn seq(&mut self) -> Result<Box<dyn Seq + '_>> {
+            fn seq(&mut self) -> Result<Box<dyn Seq<'de> + '_>> {
                 Ok(Box::new(VecBuilder {
                     out: &mut self.out,
                     vec: Vec::new(),
@@ -442,11 +571,11 @@ where
             }
         }
 
-        impl<'a, T> Seq for VecBuilder<'a, T>
+        impl<'a, 'de, T> Seq<'de> for VecBuilder<'a, T>
         where
-            T: Deserialize,
+            T: Deserialize<'de>,
         {
-            fn element(&mut self) -> Result<&mut dyn Visitor> {
+            fn element(&mut self) -> Result<&mut dyn Visitor<'de>> {
                 self.shift();
                 Ok(Deserialize::begin(&mut self.element))
             }
@@ -462,18 +591,18 @@ where
     }
 }
 
-impl<T, const N: usize> Deserialize for [T; N]
+impl<'de, T, const N: usize> Deserialize<'de> for [T; N]
 where
-    T: Deserialize,
+    T: Deserialize<'de>,
 {
-    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor {
+    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor<'de> {
         make_place!(Place);
 
-        impl<T, const N: usize> Visitor for Place<[T; N]>
+        impl<'de, T, const N: usize> Visitor<'de> for Place<[T; N]>
         where
-            T: Deserialize,
+            T: Deserialize<'de>,
         {
-            fn seq(&mut self) -> Result<Box<dyn Seq + '_>> {
+            fn seq(&mut self) -> Result<Box<dyn Seq<'de> + '_>> {
                 Ok(Box::new(ArrayBuilder {
                     out: &mut self.out,
                     array: unsafe { MaybeUninit::<[MaybeUninit<T>; N]>::uninit().assume_init() },
@@ -500,11 +629,11 @@ where
             }
         }
 
-        impl<'a, T, const N: usize> Seq for ArrayBuilder<'a, T, N>
+        impl<'a, 'de, T, const N: usize> Seq<'de> for ArrayBuilder<'a, T, N>
         where
-            T: Deserialize,
+            T: Deserialize<'de>,
         {
-            fn element(&mut self) -> Result<&mut dyn Visitor> {
+            fn element(&mut self) -> Result<&mut dyn Visitor<'de>> {
                 self.shift()?;
                 Ok(Deserialize::begin(&mut self.element))
             }
@@ -538,22 +667,22 @@ where
 }
 
 #[cfg(feature = "std")]
-impl<K, V, H> Deserialize for HashMap<K, V, H>
+impl<'de, K, V, H> Deserialize<'de> for HashMap<K, V, H>
 where
     K: FromStr + Hash + Eq,
-    V: Deserialize,
+    V: Deserialize<'de>,
     H: BuildHasher + Default,
 {
-    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor {
+    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor<'de> {
         make_place!(Place);
 
-        impl<K, V, H> Visitor for Place<HashMap<K, V, H>>
+        impl<'de, K, V, H> Visitor<'de> for Place<HashMap<K, V, H>>
         where
             K: FromStr + Hash + Eq,
-            V: Deserialize,
+            V: Deserialize<'de>,
             H: BuildHasher + Default,
         {
-            fn map(&mut self) -> Result<Box<dyn Map + '_>> {
+            fn map(&mut self) -> Result<Box<dyn Map<'de> + '_>> {
                 Ok(Box::new(MapBuilder {
                     out: &mut self.out,
                     map: HashMap::with_hasher(H::default()),
@@ -582,13 +711,13 @@ where
             }
         }
 
-        impl<'a, K, V, H> Map for MapBuilder<'a, K, V, H>
+        impl<'a, 'de, K, V, H> Map<'de> for MapBuilder<'a, K, V, H>
         where
             K: FromStr + Hash + Eq,
-            V: Deserialize,
+            V: Deserialize<'de>,
             H: BuildHasher + Default,
         {
-            fn key(&mut self, k: &str) -> Result<&mut dyn Visitor> {
+            fn key(&mut self, k: &str) -> Result<&mut dyn Visitor<'de>> {
                 self.shift();
                 self.key = Some(match K::from_str(k) {
                     Ok(key) => key,
@@ -609,20 +738,20 @@ where
     }
 }
 
-impl<K, V> Deserialize for BTreeMap<K, V>
+impl<'de, K, V> Deserialize<'de> for BTreeMap<K, V>
 where
     K: FromStr + Ord,
-    V: Deserialize,
+    V: Deserialize<'de>,
 {
-    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor {
+    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor<'de> {
         make_place!(Place);
 
-        impl<K, V> Visitor for Place<BTreeMap<K, V>>
+        impl<'de, K, V> Visitor<'de> for Place<BTreeMap<K, V>>
         where
             K: FromStr + Ord,
-            V: Deserialize,
+            V: Deserialize<'de>,
         {
-            fn map(&mut self) -> Result<Box<dyn Map + '_>> {
+            fn map(&mut self) -> Result<Box<dyn Map<'de> + '_>> {
                 Ok(Box::new(MapBuilder {
                     out: &mut self.out,
                     map: BTreeMap::new(),
@@ -650,12 +779,12 @@ where
             }
         }
 
-        impl<'a, K, V> Map for MapBuilder<'a, K, V>
+        impl<'a, 'de, K, V> Map<'de> for MapBuilder<'a, K, V>
         where
             K: FromStr + Ord,
-            V: Deserialize,
+            V: Deserialize<'de>,
         {
-            fn key(&mut self, k: &str) -> Result<&mut dyn Visitor> {
+            fn key(&mut self, k: &str) -> Result<&mut dyn Visitor<'de>> {
                 self.shift();
                 self.key = Some(match K::from_str(k) {
                     Ok(key) => key,
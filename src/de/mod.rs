@@ -37,15 +37,15 @@
 //! // output place.
 //! //
 //! // These methods may perform validation and decide to return an error.
-//! impl Visitor for Place<MyBoolean> {
+//! impl<'de> Visitor<'de> for Place<MyBoolean> {
 //!     fn boolean(&mut self, b: bool) -> Result<()> {
 //!         self.out = Some(MyBoolean(b));
 //!         Ok(())
 //!     }
 //! }
 //!
-//! impl Deserialize for MyBoolean {
-//!     fn begin(out: &mut Option<Self>) -> &mut dyn Visitor {
+//! impl<'de> Deserialize<'de> for MyBoolean {
+//!     fn begin(out: &mut Option<Self>) -> &mut dyn Visitor<'de> {
 //!         // All Deserialize impls will look exactly like this. There is no
 //!         // other correct implementation of Deserialize.
 //!         Place::new(out)
@@ -67,11 +67,11 @@
 //!
 //! struct MyVec<T>(Vec<T>);
 //!
-//! impl<T> Visitor for Place<MyVec<T>>
+//! impl<'de, T> Visitor<'de> for Place<MyVec<T>>
 //! where
-//!     T: Deserialize,
+//!     T: Deserialize<'de>,
 //! {
-//!     fn seq(&mut self) -> Result<Box<dyn Seq + '_>> {
+//!     fn seq(&mut self) -> Result<Box<dyn Seq<'de> + '_>> {
 //!         Ok(Box::new(VecBuilder {
 //!             out: &mut self.out,
 //!             vec: Vec::new(),
@@ -89,11 +89,11 @@
 //!     element: Option<T>,
 //! }
 //!
-//! impl<'a, T> Seq for VecBuilder<'a, T>
+//! impl<'a, 'de, T> Seq<'de> for VecBuilder<'a, T>
 //! where
-//!     T: Deserialize,
+//!     T: Deserialize<'de>,
 //! {
-//!     fn element(&mut self) -> Result<&mut dyn Visitor> {
+//!     fn element(&mut self) -> Result<&mut dyn Visitor<'de>> {
 //!         // Free up the place by transferring the most recent element
 //!         // into self.vec.
 //!         self.vec.extend(self.element.take());
@@ -111,11 +111,11 @@
 //!     }
 //! }
 //!
-//! impl<T> Deserialize for MyVec<T>
+//! impl<'de, T> Deserialize<'de> for MyVec<T>
 //! where
-//!     T: Deserialize,
+//!     T: Deserialize<'de>,
 //! {
-//!     fn begin(out: &mut Option<Self>) -> &mut dyn Visitor {
+//!     fn begin(out: &mut Option<Self>) -> &mut dyn Visitor<'de> {
 //!         // As mentioned, all Deserialize impls will look like this.
 //!         Place::new(out)
 //!     }
@@ -139,8 +139,8 @@
 //!     message: String,
 //! }
 //!
-//! impl Visitor for Place<Demo> {
-//!     fn map(&mut self) -> Result<Box<dyn Map + '_>> {
+//! impl<'de> Visitor<'de> for Place<Demo> {
+//!     fn map(&mut self) -> Result<Box<dyn Map<'de> + '_>> {
 //!         // Like for sequences, we produce a builder that can hand out places
 //!         // to write one struct field at a time.
 //!         Ok(Box::new(DemoBuilder {
@@ -157,8 +157,8 @@
 //!     out: &'a mut Option<Demo>,
 //! }
 //!
-//! impl<'a> Map for DemoBuilder<'a> {
-//!     fn key(&mut self, k: &str) -> Result<&mut dyn Visitor> {
+//! impl<'a, 'de> Map<'de> for DemoBuilder<'a> {
+//!     fn key(&mut self, k: &str) -> Result<&mut dyn Visitor<'de>> {
 //!         // Figure out which field is being deserialized and return a place
 //!         // to write it.
 //!         //
@@ -183,13 +183,45 @@
 //!     }
 //! }
 //!
-//! impl Deserialize for Demo {
-//!     fn begin(out: &mut Option<Self>) -> &mut dyn Visitor {
+//! impl<'de> Deserialize<'de> for Demo {
+//!     fn begin(out: &mut Option<Self>) -> &mut dyn Visitor<'de> {
 //!         // All Deserialize impls look like this.
 //!         Place::new(out)
 //!     }
 //! }
 //! ```
+//!
+//! ## Borrowing from the input
+//!
+//! The `'de` lifetime is the lifetime of the input being deserialized from.
+//! Strings that appear in the input without any escape sequences are passed
+//! to `Visitor::borrowed_string` as a `&'de str`, which an impl may hold on
+//! to instead of copying. Strings which had to be unescaped, or which come
+//! from input that is not held in memory in its entirety, are passed to
+//! `Visitor::string` instead. This is how `&str` and `Cow<str>` deserialize.
+//!
+//! ```rust
+//! use miniserde::{json, Deserialize};
+//! use std::borrow::Cow;
+//!
+//! #[derive(Deserialize)]
+//! struct Request<'a> {
+//!     method: &'a str,
+//!     path: Cow<'a, str>,
+//! }
+//!
+//! fn main() -> miniserde::Result<()> {
+//!     let j = r#" {"method": "GET", "path": "/a\u0062c"} "#;
+//!     let request: Request = json::from_str(j)?;
+//!     assert_eq!(request.method, "GET");
+//!     assert!(matches!(request.path, Cow::Owned(_)));
+//!     assert_eq!(request.path, "/abc");
+//!     Ok(())
+//! }
+//! ```
+//!
+//! A type which does not borrow anything implements `Deserialize<'de>` for
+//! every `'de`, which is expressed by the [`DeserializeOwned`] bound.
 
 mod impls;
 
@@ -199,7 +231,7 @@ use alloc::boxed::Box;
 /// Trait for data structures that can be deserialized from a JSON string.
 ///
 /// [Refer to the module documentation for examples.][crate::de]
-pub trait Deserialize: Sized {
+pub trait Deserialize<'de>: Sized {
     /// The only correct implementation of this method is:
     ///
     /// ```rust
@@ -208,15 +240,15 @@ pub trait Deserialize: Sized {
     /// #
     /// # make_place!(Place);
     /// # struct S;
-    /// # impl Visitor for Place<S> {}
+    /// # impl<'de> Visitor<'de> for Place<S> {}
     /// #
-    /// # impl Deserialize for S {
-    /// fn begin(out: &mut Option<Self>) -> &mut dyn Visitor {
+    /// # impl<'de> Deserialize<'de> for S {
+    /// fn begin(out: &mut Option<Self>) -> &mut dyn Visitor<'de> {
     ///     Place::new(out)
     /// }
     /// # }
     /// ```
-    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor;
+    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor<'de>;
 
     // Not public API. This method is only intended for Option<T>, should not
     // need to be implemented outside of this crate.
@@ -227,10 +259,19 @@ pub trait Deserialize: Sized {
     }
 }
 
+/// Trait for data structures that can be deserialized without borrowing from
+/// the input.
+///
+/// This is a shorthand for `for<'de> Deserialize<'de>`, and is implemented for
+/// every such type.
+pub trait DeserializeOwned: for<'de> Deserialize<'de> {}
+
+impl<T> DeserializeOwned for T where T: for<'de> Deserialize<'de> {}
+
 /// Trait that can write data into an output place.
 ///
 /// [Refer to the module documentation for examples.][crate::de]
-pub trait Visitor {
+pub trait Visitor<'de> {
     fn null(&mut self) -> Result<()> {
         Err(Error)
     }
@@ -245,6 +286,12 @@ pub trait Visitor {
         Err(Error)
     }
 
+    /// A string which lives as long as the input, and may be borrowed from
+    /// it. By default this is forwarded to `string`.
+    fn borrowed_string(&mut self, s: &'de str) -> Result<()> {
+        self.string(s)
+    }
+
     fn negative(&mut self, n: i64) -> Result<()> {
         let _ = n;
         Err(Error)
@@ -260,11 +307,31 @@ pub trait Visitor {
         Err(Error)
     }
 
-    fn seq(&mut self) -> Result<Box<dyn Seq + '_>> {
+    /// A string of arbitrary bytes, which binary formats can produce.
+    fn bytes(&mut self, b: &[u8]) -> Result<()> {
+        let _ = b;
         Err(Error)
     }
 
-    fn map(&mut self) -> Result<Box<dyn Map + '_>> {
+    fn seq(&mut self) -> Result<Box<dyn Seq<'de> + '_>> {
+        Err(Error)
+    }
+
+    fn map(&mut self) -> Result<Box<dyn Map<'de> + '_>> {
+        Err(Error)
+    }
+
+    // Not public API. These methods are only intended for json::RawValue,
+    // which receives the JSON text of a value instead of being visited, and
+    // for types forwarding to it.
+    #[doc(hidden)]
+    fn wants_raw_json(&mut self) -> bool {
+        false
+    }
+
+    #[doc(hidden)]
+    fn raw_json(&mut self, j: &str) -> Result<()> {
+        let _ = j;
         Err(Error)
     }
 }
@@ -272,15 +339,15 @@ pub trait Visitor {
 /// Trait that can hand out places to write sequence elements.
 ///
 /// [Refer to the module documentation for examples.][crate::de]
-pub trait Seq {
-    fn element(&mut self) -> Result<&mut dyn Visitor>;
+pub trait Seq<'de> {
+    fn element(&mut self) -> Result<&mut dyn Visitor<'de>>;
     fn finish(&mut self) -> Result<()>;
 }
 
 /// Trait that can hand out places to write values of a map.
 ///
 /// [Refer to the module documentation for examples.][crate::de]
-pub trait Map {
-    fn key(&mut self, k: &str) -> Result<&mut dyn Visitor>;
+pub trait Map<'de> {
+    fn key(&mut self, k: &str) -> Result<&mut dyn Visitor<'de>>;
     fn finish(&mut self) -> Result<()>;
 }
@@ -0,0 +1,204 @@
+//! Configuration from environment variables.
+//!
+//! Variables whose name starts with a given prefix are deserialized as the
+//! entries of a map or struct. The rest of the name, lowercased, is the key.
+//! A double underscore in the name separates the keys of nested structs, so
+//! that with a prefix of `APP_` the variable `APP_DB__HOST` fills in the
+//! `host` field of the struct in the `db` field.
+//!
+//! ```rust
+//! use miniserde::{env, Deserialize};
+//!
+//! #[derive(Deserialize)]
+//! struct Config {
+//!     debug: bool,
+//!     db: Database,
+//!     allowed_origins: Vec<String>,
+//! }
+//!
+//! #[derive(Deserialize)]
+//! struct Database {
+//!     host: String,
+//!     port: u16,
+//! }
+//!
+//! fn main() -> miniserde::Result<()> {
+//!     let vars = [
+//!         ("APP_DEBUG", "false"),
+//!         ("APP_DB__HOST", "localhost"),
+//!         ("APP_DB__PORT", "5432"),
+//!         ("APP_ALLOWED_ORIGINS", "a.example,b.example"),
+//!         ("PATH", "/usr/bin"),
+//!     ];
+//!     let vars = vars.map(|(k, v)| (k.to_owned(), v.to_owned()));
+//!
+//!     let config: Config = env::from_iter("APP_", vars)?;
+//!     assert!(!config.debug);
+//!     assert_eq!(config.db.host, "localhost");
+//!     assert_eq!(config.db.port, 5432);
+//!     assert_eq!(config.allowed_origins, ["a.example", "b.example"]);
+//!
+//!     Ok(())
+//! }
+//! ```
+//!
+//! Every value is offered to its field as a string first. If the field does
+//! not accept a string, the value is parsed as a bool, an integer or a float
+//! instead, with an empty value standing for null. If none of those fit either,
+//! the value is split on commas into the elements of a sequence, each of which
+//! is converted the same way.
+
+use crate::de::{DeserializeOwned, Map, Visitor};
+use crate::error::{Error, Result};
+use crate::ptr::NonuniqueBox;
+use crate::text;
+use alloc::collections::btree_map::{BTreeMap, Entry};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ptr::NonNull;
+use std::env;
+
+/// Deserialize the variables of the current process's environment whose name
+/// starts with `prefix`.
+///
+/// Variables whose name or value is not valid Unicode are skipped.
+pub fn from_env<T>(prefix: &str) -> Result<T>
+where
+    T: DeserializeOwned,
+{
+    let vars = env::vars_os()
+        .filter_map(|(name, value)| Some((name.into_string().ok()?, value.into_string().ok()?)));
+    from_iter(prefix, vars)
+}
+
+/// Deserialize the `(name, value)` pairs whose name starts with `prefix`, as
+/// if they were the environment.
+///
+/// This fails if a variable's name has nothing after the prefix, or an empty
+/// key between double underscores, or if one variable names a struct into
+/// which another one nests.
+pub fn from_iter<T, I>(prefix: &str, vars: I) -> Result<T>
+where
+    T: DeserializeOwned,
+    I: IntoIterator<Item = (String, String)>,
+{
+    // Sorting by path puts every variable nested under some key right after
+    // one another, so that each map is opened and finished exactly once.
+    let mut vars_by_path = BTreeMap::new();
+    for (name, value) in vars {
+        let Some(rest) = name.strip_prefix(prefix) else {
+            continue;
+        };
+        let path: Vec<String> = rest.split("__").map(str::to_ascii_lowercase).collect();
+        if path.iter().any(String::is_empty) {
+            return Err(Error);
+        }
+        match vars_by_path.entry(path) {
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+            }
+            Entry::Occupied(_) => return Err(Error),
+        }
+    }
+
+    let mut out = None;
+    from_vars_impl(&vars_by_path, T::begin(&mut out))?;
+    out.ok_or(Error)
+}
+
+struct Deserializer<'a, 'b> {
+    // The map being filled in for each key of the current variable's path,
+    // the outermost first.
+    stack: Vec<NonuniqueBox<dyn Map<'a> + 'b>>,
+}
+
+impl<'a, 'b> Drop for Deserializer<'a, 'b> {
+    fn drop(&mut self) {
+        // Drop layers in reverse order.
+        while !self.stack.is_empty() {
+            self.stack.pop();
+        }
+    }
+}
+
+fn from_vars_impl<'a>(
+    vars: &BTreeMap<Vec<String>, String>,
+    visitor: &mut dyn Visitor<'a>,
+) -> Result<()> {
+    let mut de = Deserializer { stack: Vec::new() };
+    de.visit(vars, visitor)
+}
+
+impl<'a, 'b> Deserializer<'a, 'b> {
+    fn visit(
+        &mut self,
+        vars: &BTreeMap<Vec<String>, String>,
+        visitor: &mut dyn Visitor<'a>,
+    ) -> Result<()>
+    where
+        'a: 'b,
+    {
+        let visitor = NonNull::from(visitor);
+        // invariant: `visitor` outlives every map on the stack
+        let visitor =
+            unsafe { &mut *extend_lifetime!(visitor as NonNull<dyn Visitor<'a>>).as_ptr() };
+        self.stack.push(NonuniqueBox::from(visitor.map()?));
+
+        // Keys of the maps on the stack after the outermost one.
+        let mut open: Vec<&str> = Vec::new();
+        let mut previous: Option<&[String]> = None;
+        for (path, value) in vars {
+            // A variable whose name is a prefix of this one's, which would need
+            // to be a map and a value at the same time.
+            if previous.is_some_and(|previous| path.starts_with(previous)) {
+                return Err(Error);
+            }
+            previous = Some(path);
+
+            let (last, parents) = path.split_last().ok_or(Error)?;
+            let common = open
+                .iter()
+                .zip(parents)
+                .take_while(|(open, key)| **open == key.as_str())
+                .count();
+            while open.len() > common {
+                open.pop();
+                let mut map = self.stack.pop().ok_or(Error)?;
+                map.finish()?;
+            }
+            for key in &parents[common..] {
+                let map = self.stack.last_mut().ok_or(Error)?;
+                let entry = NonNull::from(map.key(key)?);
+                // invariant: `entry` outlives the map pushed above `map`
+                let entry =
+                    unsafe { &mut *extend_lifetime!(entry as NonNull<dyn Visitor<'a>>).as_ptr() };
+                self.stack.push(NonuniqueBox::from(entry.map()?));
+                open.push(key);
+            }
+            let map = self.stack.last_mut().ok_or(Error)?;
+            visit_value(map.key(last)?, value)?;
+        }
+
+        while let Some(mut map) = self.stack.pop() {
+            map.finish()?;
+        }
+        Ok(())
+    }
+}
+
+fn visit_value(visitor: &mut dyn Visitor, value: &str) -> Result<()> {
+    if visitor.string(value).is_ok() || text::visit_scalar(visitor, value).is_ok() {
+        return Ok(());
+    }
+
+    let mut seq = visitor.seq()?;
+    if !value.is_empty() {
+        for element in value.split(',') {
+            let visitor = seq.element()?;
+            if visitor.string(element).is_err() {
+                text::visit_scalar(visitor, element)?;
+            }
+        }
+    }
+    seq.finish()
+}
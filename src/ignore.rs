@@ -3,8 +3,8 @@ use crate::error::Result;
 use alloc::boxed::Box;
 use core::ptr;
 
-impl dyn Visitor {
-    pub fn ignore() -> &'static mut dyn Visitor {
+impl<'a, 'de> dyn Visitor<'de> + 'a {
+    pub fn ignore() -> &'a mut (dyn Visitor<'de> + 'a) {
         static mut IGNORE: Ignore = Ignore;
 
         // Conceptually we have an array of type [Ignore; ∞] in a static, which
@@ -17,7 +17,7 @@ impl dyn Visitor {
 
 pub(crate) struct Ignore;
 
-impl Visitor for Ignore {
+impl<'de> Visitor<'de> for Ignore {
     fn null(&mut self) -> Result<()> {
         Ok(())
     }
@@ -42,17 +42,21 @@ impl Visitor for Ignore {
         Ok(())
     }
 
-    fn seq(&mut self) -> Result<Box<dyn Seq + '_>> {
+    fn bytes(&mut self, _b: &[u8]) -> Result<()> {
+        Ok(())
+    }
+
+    fn seq(&mut self) -> Result<Box<dyn Seq<'de> + '_>> {
         Ok(Box::new(Ignore))
     }
 
-    fn map(&mut self) -> Result<Box<dyn Map + '_>> {
+    fn map(&mut self) -> Result<Box<dyn Map<'de> + '_>> {
         Ok(Box::new(Ignore))
     }
 }
 
-impl Seq for Ignore {
-    fn element(&mut self) -> Result<&mut dyn Visitor> {
+impl<'de> Seq<'de> for Ignore {
+    fn element(&mut self) -> Result<&mut dyn Visitor<'de>> {
         Ok(<dyn Visitor>::ignore())
     }
 
@@ -61,8 +65,8 @@ impl Seq for Ignore {
     }
 }
 
-impl Map for Ignore {
-    fn key(&mut self, _k: &str) -> Result<&mut dyn Visitor> {
+impl<'de> Map<'de> for Ignore {
+    fn key(&mut self, _k: &str) -> Result<&mut dyn Visitor<'de>> {
         Ok(<dyn Visitor>::ignore())
     }
 
@@ -1,11 +1,13 @@
 use crate::de::{Deserialize, Seq, Visitor};
 use crate::error::Result;
-use crate::json::{drop, Value};
+use crate::json::{cmp, debug, drop, Value};
 use crate::private;
 use crate::ser::{Fragment, Serialize};
 use alloc::boxed::Box;
 use alloc::vec::Vec;
+use core::cmp::Ordering;
 use core::fmt::{self, Debug};
+use core::hash::{Hash, Hasher};
 use core::mem::{self, ManuallyDrop};
 use core::ops::{Deref, DerefMut};
 use core::ptr;
@@ -97,10 +99,41 @@ impl FromIterator<Value> for Array {
     }
 }
 
+impl From<Vec<Value>> for Array {
+    fn from(inner: Vec<Value>) -> Self {
+        Array { inner }
+    }
+}
+
+impl PartialEq for Array {
+    fn eq(&self, other: &Self) -> bool {
+        cmp::cmp_arrays(self, other) == Ordering::Equal
+    }
+}
+
+impl Eq for Array {}
+
+impl PartialOrd for Array {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Array {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp::cmp_arrays(self, other)
+    }
+}
+
+impl Hash for Array {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        cmp::hash_array(self, state);
+    }
+}
+
 impl Debug for Array {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        formatter.write_str("Array ")?;
-        formatter.debug_list().entries(self).finish()
+        debug::debug_array(self, formatter)
     }
 }
 
@@ -110,12 +143,12 @@ impl Serialize for Array {
     }
 }
 
-impl Deserialize for Array {
-    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor {
+impl<'de> Deserialize<'de> for Array {
+    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor<'de> {
         make_place!(Place);
 
-        impl Visitor for Place<Array> {
-            fn seq(&mut self) -> Result<Box<dyn Seq + '_>> {
+        impl<'de> Visitor<'de> for Place<Array> {
+            fn seq(&mut self) -> Result<Box<dyn Seq<'de> + '_>> {
                 Ok(Box::new(ArrayBuilder {
                     out: &mut self.out,
                     array: Array::new(),
@@ -138,8 +171,8 @@ impl Deserialize for Array {
             }
         }
 
-        impl<'a> Seq for ArrayBuilder<'a> {
-            fn element(&mut self) -> Result<&mut dyn Visitor> {
+        impl<'a, 'de> Seq<'de> for ArrayBuilder<'a> {
+            fn element(&mut self) -> Result<&mut dyn Visitor<'de>> {
                 self.shift();
                 Ok(Deserialize::begin(&mut self.element))
             }
@@ -0,0 +1,302 @@
+use crate::error::{Error, Result};
+use crate::json::ser::{escape_str, write_base64, ESCAPE};
+use crate::json::{from_str, Value};
+use crate::ser::{Fragment, Map, Seq, Serialize};
+use alloc::borrow::{Cow, ToOwned};
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::{self, Vec};
+use core::mem;
+use core::ops::Range;
+
+/// Serialize any serializable type into canonical JSON, following the JSON
+/// Canonicalization Scheme of [RFC 8785].
+///
+/// [RFC 8785]: https://www.rfc-editor.org/rfc/rfc8785
+///
+/// The same data always produces the same bytes, which makes the output
+/// suitable for hashing and signing. There is no whitespace, the entries of
+/// every object are sorted by their keys compared as UTF-16 code units,
+/// strings escape only what JSON requires, and floats are formatted the way
+/// ECMAScript's `Number.prototype.toString` does.
+///
+/// Floats which are `NaN` or infinite have no canonical form, and make this
+/// fail. So do integers which an ECMAScript number cannot represent exactly,
+/// such as 2<sup>53</sup> + 1, rather than being changed to a different one.
+///
+/// ```rust
+/// use miniserde::json;
+/// use std::collections::HashMap;
+///
+/// let mut map = HashMap::new();
+/// map.insert("b", vec![1e21, 1e-7, 100.0]);
+/// map.insert("a", vec![0.5]);
+///
+/// let j = json::to_canonical_string(&map).unwrap();
+/// assert_eq!(j, r#"{"a":[0.5],"b":[1e+21,1e-7,100]}"#);
+/// ```
+pub fn to_canonical_string<T>(value: &T) -> Result<String>
+where
+    T: ?Sized + Serialize,
+{
+    to_canonical_string_impl(&value)
+}
+
+struct Serializer<'a> {
+    stack: Vec<Layer<'a>>,
+}
+
+enum Layer<'a> {
+    Seq(Box<dyn Seq + 'a>),
+    // With the pieces of the enclosing value written before this map, the
+    // entries finished so far, and the key of the entry being written.
+    Map(Box<dyn Map + 'a>, Vec<Piece>, Vec<Entry>, String),
+}
+
+// Values are written to a single buffer in the order they are serialized,
+// except that every object is cut out of it, to be put back together in sorted
+// order at the end. Each byte of the buffer is copied to the output once.
+enum Piece {
+    Text(Range<usize>),
+    // Index of a finished object.
+    Object(usize),
+}
+
+struct Entry {
+    key: String,
+    value: Vec<Piece>,
+}
+
+impl<'a> Drop for Serializer<'a> {
+    fn drop(&mut self) {
+        // Drop layers in reverse order.
+        while !self.stack.is_empty() {
+            self.stack.pop();
+        }
+    }
+}
+
+// Ends the pieces of the value being written at the current end of the text.
+fn cut(text: &str, start: &mut usize, pieces: &mut Vec<Piece>) {
+    if *start < text.len() {
+        pieces.push(Piece::Text(*start..text.len()));
+        *start = text.len();
+    }
+}
+
+fn to_canonical_string_impl(value: &dyn Serialize) -> Result<String> {
+    let mut out = String::new();
+    // Where the text which is not yet part of any piece begins.
+    let mut start = 0;
+    let mut pieces = Vec::new();
+    let mut objects: Vec<Vec<Entry>> = Vec::new();
+    let mut serializer = Serializer { stack: Vec::new() };
+    let mut fragment = value.begin();
+
+    loop {
+        match fragment {
+            Fragment::Null => out.push_str("null"),
+            Fragment::Bool(b) => out.push_str(if b { "true" } else { "false" }),
+            Fragment::Str(s) => escape_str(&s, &mut out, &ESCAPE),
+            // Numbers are ECMAScript numbers, which hold integers beyond 2^53
+            // only if they happen to be a multiple of a large enough power of
+            // two.
+            Fragment::U64(n) => {
+                let f = n as f64;
+                if f >= 18446744073709551616.0 || f as u64 != n {
+                    return Err(Error);
+                }
+                format_number(f, &mut out);
+            }
+            Fragment::I64(n) => {
+                let f = n as f64;
+                if f >= 9223372036854775808.0 || f as i64 != n {
+                    return Err(Error);
+                }
+                format_number(f, &mut out);
+            }
+            Fragment::F64(n) => {
+                if !n.is_finite() {
+                    return Err(Error);
+                }
+                format_number(n, &mut out);
+            }
+            Fragment::Bytes(b) => write_base64(&b, &mut out),
+            Fragment::RawJson(raw) => {
+                // A Value never contains raw JSON, so this goes only one level
+                // deep.
+                let value: Value = from_str(raw.get())?;
+                out.push_str(&to_canonical_string_impl(&value)?);
+            }
+            Fragment::Seq(mut seq) => {
+                out.push('[');
+                // invariant: `seq` must outlive `first`
+                match unsafe { extend_lifetime!(seq.next() as Option<&dyn Serialize>) } {
+                    Some(first) => {
+                        serializer.stack.push(Layer::Seq(seq));
+                        fragment = first.begin();
+                        continue;
+                    }
+                    None => out.push(']'),
+                }
+            }
+            Fragment::Map(mut map) => {
+                // invariant: `map` must outlive `first`
+                match unsafe { extend_lifetime!(map.next() as Option<(Cow<str>, &dyn Serialize)>) }
+                {
+                    Some((key, first)) => {
+                        cut(&out, &mut start, &mut pieces);
+                        let outer = mem::take(&mut pieces);
+                        let layer = Layer::Map(map, outer, Vec::new(), key.into_owned());
+                        serializer.stack.push(layer);
+                        fragment = first.begin();
+                        continue;
+                    }
+                    None => out.push_str("{}"),
+                }
+            }
+        }
+
+        loop {
+            match serializer.stack.last_mut() {
+                Some(Layer::Seq(seq)) => {
+                    // invariant: `seq` must outlive `next`
+                    match unsafe { extend_lifetime!(seq.next() as Option<&dyn Serialize>) } {
+                        Some(next) => {
+                            out.push(',');
+                            fragment = next.begin();
+                            break;
+                        }
+                        None => out.push(']'),
+                    }
+                }
+                Some(Layer::Map(map, outer, entries, key)) => {
+                    cut(&out, &mut start, &mut pieces);
+                    let value = mem::take(&mut pieces);
+                    // invariant: `map` must outlive `next`
+                    match unsafe {
+                        extend_lifetime!(map.next() as Option<(Cow<str>, &dyn Serialize)>)
+                    } {
+                        Some((next_key, next)) => {
+                            let key = mem::replace(key, next_key.into_owned());
+                            entries.push(Entry { key, value });
+                            fragment = next.begin();
+                            break;
+                        }
+                        None => {
+                            let key = mem::take(key);
+                            let mut entries = mem::take(entries);
+                            entries.push(Entry { key, value });
+                            entries.sort_by(|a, b| a.key.encode_utf16().cmp(b.key.encode_utf16()));
+                            pieces = mem::take(outer);
+                            pieces.push(Piece::Object(objects.len()));
+                            objects.push(entries);
+                        }
+                    }
+                }
+                None => {
+                    if objects.is_empty() {
+                        return Ok(out);
+                    }
+                    cut(&out, &mut start, &mut pieces);
+                    return Ok(assemble(&out, pieces, objects));
+                }
+            }
+            serializer.stack.pop();
+        }
+    }
+}
+
+// Writes out the pieces of the top-level value, with each object's entries in
+// sorted order.
+fn assemble(text: &str, pieces: Vec<Piece>, mut objects: Vec<Vec<Entry>>) -> String {
+    enum Layer {
+        Value(vec::IntoIter<Piece>),
+        Object(vec::IntoIter<Entry>, bool),
+    }
+
+    let mut out = String::with_capacity(text.len());
+    let mut stack = alloc::vec![Layer::Value(pieces.into_iter())];
+    while let Some(layer) = stack.last_mut() {
+        match layer {
+            Layer::Value(pieces) => match pieces.next() {
+                Some(Piece::Text(range)) => out.push_str(&text[range]),
+                Some(Piece::Object(index)) => {
+                    out.push('{');
+                    let entries = mem::take(&mut objects[index]);
+                    stack.push(Layer::Object(entries.into_iter(), true));
+                }
+                None => {
+                    stack.pop();
+                }
+            },
+            Layer::Object(entries, first) => match entries.next() {
+                Some(entry) => {
+                    if !mem::replace(first, false) {
+                        out.push(',');
+                    }
+                    escape_str(&entry.key, &mut out, &ESCAPE);
+                    out.push(':');
+                    stack.push(Layer::Value(entry.value.into_iter()));
+                }
+                None => {
+                    out.push('}');
+                    stack.pop();
+                }
+            },
+        }
+    }
+    out
+}
+
+// Formats a finite float following the Number::toString algorithm of
+// ECMAScript, from the shortest digits which round trip.
+fn format_number(n: f64, out: &mut String) {
+    if n == 0.0 {
+        // Including negative zero.
+        out.push('0');
+        return;
+    }
+    if n < 0.0 {
+        out.push('-');
+    }
+
+    let mut buffer = zmij::Buffer::new();
+    let repr = buffer.format_finite(n.abs());
+    let (mantissa, exp) = match repr.find(['e', 'E']) {
+        Some(i) => (&repr[..i], repr[i + 1..].parse::<i32>().unwrap()),
+        None => (repr, 0),
+    };
+    let (int, frac) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+
+    // The value is 0.DIGITS times 10 to the power of `point`.
+    let mut digits = int.to_owned();
+    digits.push_str(frac);
+    let mut point = int.len() as i32 + exp;
+    let leading_zeros = digits.len() - digits.trim_start_matches('0').len();
+    point -= leading_zeros as i32;
+    let digits = digits[leading_zeros..].trim_end_matches('0');
+    let k = digits.len() as i32;
+
+    if k <= point && point <= 21 {
+        out.push_str(digits);
+        out.extend((k..point).map(|_| '0'));
+    } else if 0 < point && point <= 21 {
+        out.push_str(&digits[..point as usize]);
+        out.push('.');
+        out.push_str(&digits[point as usize..]);
+    } else if -6 < point && point <= 0 {
+        out.push_str("0.");
+        out.extend((point..0).map(|_| '0'));
+        out.push_str(digits);
+    } else {
+        out.push_str(&digits[..1]);
+        if k > 1 {
+            out.push('.');
+            out.push_str(&digits[1..]);
+        }
+        out.push('e');
+        out.push(if point > 0 { '+' } else { '-' });
+        out.push_str(itoa::Buffer::new().format((point - 1).abs()));
+    }
+}
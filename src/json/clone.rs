@@ -0,0 +1,76 @@
+use crate::json::{Array, Object, Value};
+use alloc::collections::btree_map;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::mem;
+use core::slice;
+
+enum Layer<'a> {
+    Array(slice::Iter<'a, Value>, Array),
+    Object(btree_map::Iter<'a, String, Value>, Object, String),
+}
+
+// Deep copy of a value using an explicit stack in place of recursion.
+pub(crate) fn clone_value(value: &Value) -> Value {
+    let mut stack = Vec::new();
+    let mut value = value;
+
+    loop {
+        let mut clone = match value {
+            Value::Null => Value::Null,
+            Value::Bool(b) => Value::Bool(*b),
+            Value::Number(number) => Value::Number(number.clone()),
+            Value::String(string) => Value::String(string.clone()),
+            Value::Array(array) => {
+                let mut iter = array.iter();
+                match iter.next() {
+                    Some(first) => {
+                        let mut clone = Array::new();
+                        clone.reserve_exact(array.len());
+                        stack.push(Layer::Array(iter, clone));
+                        value = first;
+                        continue;
+                    }
+                    None => Value::Array(Array::new()),
+                }
+            }
+            Value::Object(object) => {
+                let mut iter = object.iter();
+                match iter.next() {
+                    Some((key, first)) => {
+                        stack.push(Layer::Object(iter, Object::new(), key.clone()));
+                        value = first;
+                        continue;
+                    }
+                    None => Value::Object(Object::new()),
+                }
+            }
+        };
+
+        loop {
+            match stack.last_mut() {
+                Some(Layer::Array(iter, array)) => {
+                    array.push(clone);
+                    if let Some(next) = iter.next() {
+                        value = next;
+                        break;
+                    }
+                }
+                Some(Layer::Object(iter, object, key)) => {
+                    object.insert(mem::take(key), clone);
+                    if let Some((next_key, next)) = iter.next() {
+                        key.clone_from(next_key);
+                        value = next;
+                        break;
+                    }
+                }
+                None => return clone,
+            }
+            clone = match stack.pop() {
+                Some(Layer::Array(_, array)) => Value::Array(array),
+                Some(Layer::Object(_, object, _)) => Value::Object(object),
+                None => unreachable!(),
+            };
+        }
+    }
+}
@@ -0,0 +1,232 @@
+use crate::json::{Array, Number, Object, Value};
+use alloc::collections::btree_map;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
+use core::slice;
+
+// The smallest power of two not representable in i128.
+const I128_BOUND: f64 = 170_141_183_460_469_231_731_687_303_715_884_105_728.0;
+
+// Floats of this magnitude or greater have no fractional part.
+const F64_INTEGRAL: f64 = 4_503_599_627_370_496.0;
+
+// f64::trunc is not available in core.
+fn trunc(f: f64) -> f64 {
+    if -F64_INTEGRAL < f && f < F64_INTEGRAL {
+        f as i64 as f64
+    } else {
+        f
+    }
+}
+
+enum Canonical {
+    Int(i128),
+    Float(f64),
+}
+
+fn canonical(number: &Number) -> Canonical {
+    match *number {
+        Number::U64(n) => Canonical::Int(n as i128),
+        Number::I64(n) => Canonical::Int(n as i128),
+        Number::F64(n) => Canonical::Float(n),
+    }
+}
+
+// NaN sorts after every other number and is equal to itself. Negative zero is
+// equal to positive zero.
+fn cmp_floats(a: f64, b: f64) -> Ordering {
+    match (a.is_nan(), b.is_nan()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        (false, false) => a.partial_cmp(&b).unwrap(),
+    }
+}
+
+// Exact comparison of an integer against a float, without rounding the integer
+// to the nearest float.
+fn cmp_int_float(i: i128, f: f64) -> Ordering {
+    if f.is_nan() || f >= I128_BOUND {
+        return Ordering::Less;
+    }
+    if f < -I128_BOUND {
+        return Ordering::Greater;
+    }
+    let trunc = trunc(f);
+    match i.cmp(&(trunc as i128)) {
+        Ordering::Equal => cmp_floats(trunc, f),
+        ordering => ordering,
+    }
+}
+
+pub(crate) fn cmp_numbers(a: &Number, b: &Number) -> Ordering {
+    match (canonical(a), canonical(b)) {
+        (Canonical::Int(a), Canonical::Int(b)) => a.cmp(&b),
+        (Canonical::Float(a), Canonical::Float(b)) => cmp_floats(a, b),
+        (Canonical::Int(a), Canonical::Float(b)) => cmp_int_float(a, b),
+        (Canonical::Float(a), Canonical::Int(b)) => cmp_int_float(b, a).reverse(),
+    }
+}
+
+pub(crate) fn hash_number(number: &Number, state: &mut dyn Hasher) {
+    match canonical(number) {
+        Canonical::Int(n) => {
+            state.write_u8(0);
+            state.write_i128(n);
+        }
+        // Floats with an integral value hash the same as the equal integer.
+        Canonical::Float(n) if trunc(n) == n && -I128_BOUND < n && n < I128_BOUND => {
+            state.write_u8(0);
+            state.write_i128(n as i128);
+        }
+        Canonical::Float(n) => {
+            let bits = if n.is_nan() { f64::NAN } else { n }.to_bits();
+            state.write_u8(1);
+            state.write_u64(bits);
+        }
+    }
+}
+
+fn rank(value: &Value) -> u8 {
+    match value {
+        Value::Null => 0,
+        Value::Bool(_) => 1,
+        Value::Number(_) => 2,
+        Value::String(_) => 3,
+        Value::Array(_) => 4,
+        Value::Object(_) => 5,
+    }
+}
+
+enum Layer<'a> {
+    Array(slice::Iter<'a, Value>, slice::Iter<'a, Value>),
+    Object(
+        btree_map::Iter<'a, String, Value>,
+        btree_map::Iter<'a, String, Value>,
+    ),
+}
+
+// Values of different kinds are ordered null < bool < number < string < array
+// < object. Arrays are ordered lexicographically by element, and objects
+// lexicographically by their sorted sequence of entries.
+pub(crate) fn cmp_values(a: &Value, b: &Value) -> Ordering {
+    match shallow(a, b) {
+        Ok(Some(layer)) => walk(layer),
+        Ok(None) => Ordering::Equal,
+        Err(ordering) => ordering,
+    }
+}
+
+pub(crate) fn cmp_arrays(a: &Array, b: &Array) -> Ordering {
+    walk(Layer::Array(a.iter(), b.iter()))
+}
+
+pub(crate) fn cmp_objects(a: &Object, b: &Object) -> Ordering {
+    walk(Layer::Object(a.iter(), b.iter()))
+}
+
+// Compares everything about two values except the contents of arrays and
+// objects, which are returned as a layer for the caller to iterate.
+fn shallow<'a>(a: &'a Value, b: &'a Value) -> Result<Option<Layer<'a>>, Ordering> {
+    let ordering = match (a, b) {
+        (Value::Null, Value::Null) => Ordering::Equal,
+        (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+        (Value::Number(a), Value::Number(b)) => cmp_numbers(a, b),
+        (Value::String(a), Value::String(b)) => a.cmp(b),
+        (Value::Array(a), Value::Array(b)) => {
+            return Ok(Some(Layer::Array(a.iter(), b.iter())));
+        }
+        (Value::Object(a), Value::Object(b)) => {
+            return Ok(Some(Layer::Object(a.iter(), b.iter())));
+        }
+        _ => rank(a).cmp(&rank(b)),
+    };
+    match ordering {
+        Ordering::Equal => Ok(None),
+        ordering => Err(ordering),
+    }
+}
+
+fn walk(layer: Layer) -> Ordering {
+    let mut stack = Vec::new();
+    stack.push(layer);
+
+    loop {
+        let (a, b) = match stack.last_mut() {
+            Some(Layer::Array(iter_a, iter_b)) => match (iter_a.next(), iter_b.next()) {
+                (Some(a), Some(b)) => (a, b),
+                (Some(_), None) => return Ordering::Greater,
+                (None, Some(_)) => return Ordering::Less,
+                (None, None) => {
+                    stack.pop();
+                    continue;
+                }
+            },
+            Some(Layer::Object(iter_a, iter_b)) => match (iter_a.next(), iter_b.next()) {
+                (Some((key_a, a)), Some((key_b, b))) => match key_a.cmp(key_b) {
+                    Ordering::Equal => (a, b),
+                    ordering => return ordering,
+                },
+                (Some(_), None) => return Ordering::Greater,
+                (None, Some(_)) => return Ordering::Less,
+                (None, None) => {
+                    stack.pop();
+                    continue;
+                }
+            },
+            None => return Ordering::Equal,
+        };
+        match shallow(a, b) {
+            Ok(Some(layer)) => stack.push(layer),
+            Ok(None) => {}
+            Err(ordering) => return ordering,
+        }
+    }
+}
+
+pub(crate) fn hash_value(value: &Value, state: &mut dyn Hasher) {
+    let mut stack = Vec::new();
+    stack.push(value);
+    hash_stack(stack, state);
+}
+
+pub(crate) fn hash_array(array: &Array, state: &mut dyn Hasher) {
+    state.write_usize(array.len());
+    hash_stack(array.iter().rev().collect(), state);
+}
+
+pub(crate) fn hash_object(object: &Object, mut state: &mut dyn Hasher) {
+    state.write_usize(object.len());
+    for key in object.keys() {
+        key.hash(&mut state);
+    }
+    hash_stack(object.values().rev().collect(), state);
+}
+
+// Hashes values in the order they are popped from the stack. Each value writes
+// its kind and, for arrays and objects, its length and keys before its
+// children, so equal values always produce the same sequence of writes.
+fn hash_stack(mut stack: Vec<&Value>, mut state: &mut dyn Hasher) {
+    while let Some(value) = stack.pop() {
+        state.write_u8(rank(value));
+        match value {
+            Value::Null => {}
+            Value::Bool(b) => b.hash(&mut state),
+            Value::Number(number) => hash_number(number, state),
+            Value::String(string) => string.hash(&mut state),
+            Value::Array(array) => {
+                state.write_usize(array.len());
+                stack.extend(array.iter().rev());
+            }
+            Value::Object(object) => {
+                state.write_usize(object.len());
+                for key in object.keys() {
+                    key.hash(&mut state);
+                }
+                stack.extend(object.values().rev());
+            }
+        }
+    }
+}
@@ -1,9 +1,16 @@
 use self::Event::*;
+use self::Reference::{Borrowed, Copied};
 use crate::de::{Deserialize, Map, Seq, Visitor};
 use crate::error::{Error, Result};
+use crate::json::{DuplicateKeys, Options};
 use crate::ptr::NonuniqueBox;
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeSet;
+use alloc::string::String;
 use alloc::vec::Vec;
 use core::char;
+use core::mem;
+use core::ops::Deref;
 use core::ptr::NonNull;
 use core::str;
 
@@ -27,12 +34,33 @@ use core::str;
 ///     Ok(())
 /// }
 /// ```
-pub fn from_str<T>(j: &str) -> Result<T>
+pub fn from_str<'de, T>(j: &'de str) -> Result<T>
 where
-    T: Deserialize,
+    T: Deserialize<'de>,
+{
+    from_str_with(j, &Options::new())
+}
+
+/// Deserialize a JSON string with the given options, such as limits on the
+/// size of the input.
+///
+/// ```rust
+/// use miniserde::json::{self, Options};
+///
+/// let options = Options::new().max_input_len(1024).max_string_len(16);
+///
+/// let tags: Vec<String> = json::from_str_with(r#"["a", "b"]"#, &options).unwrap();
+/// assert_eq!(tags, ["a", "b"]);
+///
+/// let j = r#"["this string is too long"]"#;
+/// assert!(json::from_str_with::<Vec<String>>(j, &options).is_err());
+/// ```
+pub fn from_str_with<'de, T>(j: &'de str, options: &Options) -> Result<T>
+where
+    T: Deserialize<'de>,
 {
     let mut out = None;
-    from_str_impl(j, T::begin(&mut out))?;
+    from_str_impl(j, options, T::begin(&mut out))?;
     out.ok_or(Error)
 }
 
@@ -40,12 +68,19 @@ struct Deserializer<'a, 'b> {
     input: &'a [u8],
     pos: usize,
     buffer: Vec<u8>,
-    stack: Vec<(NonNull<dyn Visitor>, Layer<'b>)>,
+    // Each open array or object, with how many elements or entries it has
+    // been handed so far.
+    stack: Vec<(NonNull<dyn Visitor<'a>>, Layer<'a, 'b>, usize)>,
+    options: Options,
+    // Number of values begun so far.
+    values: usize,
 }
 
-enum Layer<'a> {
-    Seq(NonuniqueBox<dyn Seq + 'a>),
-    Map(NonuniqueBox<dyn Map + 'a>),
+enum Layer<'de, 'a> {
+    Seq(NonuniqueBox<dyn Seq<'de> + 'a>),
+    // With the keys seen so far, unless duplicate keys are allowed to
+    // overwrite each other anyway.
+    Map(NonuniqueBox<dyn Map<'de> + 'a>, BTreeSet<String>),
 }
 
 impl<'a, 'b> Drop for Deserializer<'a, 'b> {
@@ -57,135 +92,287 @@ impl<'a, 'b> Drop for Deserializer<'a, 'b> {
     }
 }
 
-fn from_str_impl(j: &str, visitor: &mut dyn Visitor) -> Result<()> {
-    let visitor = NonNull::from(visitor);
-    let mut visitor = unsafe { extend_lifetime!(visitor as NonNull<dyn Visitor>) };
+// Checks that the input is a single valid JSON value.
+pub(crate) fn validate(j: &str) -> Result<()> {
+    from_str_impl(j, &Options::new(), <dyn Visitor>::ignore())
+}
+
+fn from_str_impl<'de>(
+    j: &'de str,
+    options: &Options,
+    visitor: &mut dyn Visitor<'de>,
+) -> Result<()> {
+    if j.len() > options.max_input_len {
+        return Err(Error);
+    }
     let mut de = Deserializer {
         input: j.as_bytes(),
         pos: 0,
         buffer: Vec::new(),
         stack: Vec::new(),
+        options: *options,
+        values: 0,
     };
+    de.visit(visitor)?;
 
-    'outer: loop {
-        let visitor_mut = unsafe { &mut *visitor.as_ptr() };
-        let layer = match de.event()? {
-            Null => {
-                visitor_mut.null()?;
-                None
-            }
-            Bool(b) => {
-                visitor_mut.boolean(b)?;
-                None
-            }
-            Negative(n) => {
-                visitor_mut.negative(n)?;
-                None
-            }
-            Nonnegative(n) => {
-                visitor_mut.nonnegative(n)?;
-                None
-            }
-            Float(n) => {
-                visitor_mut.float(n)?;
-                None
-            }
-            Str(s) => {
-                visitor_mut.string(s)?;
-                None
-            }
-            SeqStart => {
-                let seq = visitor_mut.seq()?;
-                Some(Layer::Seq(NonuniqueBox::from(seq)))
-            }
-            MapStart => {
-                let map = visitor_mut.map()?;
-                Some(Layer::Map(NonuniqueBox::from(map)))
-            }
-        };
+    match de.parse_whitespace() {
+        Some(_) => Err(Error),
+        None => Ok(()),
+    }
+}
 
-        let mut accept_comma;
-        let mut layer = match layer {
-            Some(layer) => {
-                accept_comma = false;
-                layer
-            }
-            None => match de.stack.pop() {
-                Some(frame) => {
-                    accept_comma = true;
-                    visitor = frame.0;
-                    frame.1
-                }
-                None => break 'outer,
-            },
-        };
+impl<'a, 'b> Deserializer<'a, 'b> {
+    // Deserializes one value, leaving the position right after it.
+    fn visit(&mut self, visitor: &mut dyn Visitor<'a>) -> Result<()>
+    where
+        'a: 'b,
+    {
+        let visitor = NonNull::from(visitor);
+        let mut visitor = unsafe { extend_lifetime!(visitor as NonNull<dyn Visitor<'a>>) };
 
+        let relaxed = self.options.relaxed;
         loop {
-            match de.parse_whitespace().unwrap_or(b'\0') {
-                b',' if accept_comma => {
-                    de.bump();
-                    break;
+            self.values += 1;
+            if self.values > self.options.max_values {
+                return Err(Error);
+            }
+            let at_max_depth = self.stack.len() >= self.options.max_depth;
+            let visitor_mut = unsafe { &mut *visitor.as_ptr() };
+            let event = if visitor_mut.wants_raw_json() {
+                visitor_mut.raw_json(self.parse_raw()?)?;
+                None
+            } else {
+                Some(self.event()?)
+            };
+            let layer = match event {
+                None => None,
+                Some(Null) => {
+                    visitor_mut.null()?;
+                    None
                 }
-                close @ (b']' | b'}') => {
-                    de.bump();
-                    match &mut layer {
-                        Layer::Seq(seq) if close == b']' => seq.finish()?,
-                        Layer::Map(map) if close == b'}' => map.finish()?,
-                        _ => return Err(Error),
-                    }
-                    let Some(frame) = de.stack.pop() else {
-                        break 'outer;
+                Some(Bool(b)) => {
+                    visitor_mut.boolean(b)?;
+                    None
+                }
+                Some(Negative(n)) => {
+                    visitor_mut.negative(n)?;
+                    None
+                }
+                Some(Nonnegative(n)) => {
+                    visitor_mut.nonnegative(n)?;
+                    None
+                }
+                Some(Float(n)) => {
+                    visitor_mut.float(n)?;
+                    None
+                }
+                Some(Str(s)) => {
+                    let result = match s {
+                        Borrowed(s) => visitor_mut.borrowed_string(s),
+                        Copied(s) => visitor_mut.string(s),
                     };
-                    accept_comma = true;
-                    visitor = frame.0;
-                    layer = frame.1;
+                    // In relaxed mode, the strings written by
+                    // NonFinite::String are also accepted as floats.
+                    match result {
+                        Err(_) if relaxed => {
+                            let n = non_finite_str(&s).ok_or(Error)?;
+                            visitor_mut.float(n)?;
+                        }
+                        result => result?,
+                    }
+                    None
                 }
-                _ => {
-                    if accept_comma {
-                        return Err(Error);
-                    } else {
-                        break;
+                Some(SeqStart | MapStart) if at_max_depth => {
+                    return Err(Error);
+                }
+                Some(SeqStart) => {
+                    let seq = visitor_mut.seq()?;
+                    Some(Layer::Seq(NonuniqueBox::from(seq)))
+                }
+                Some(MapStart) => {
+                    let map = visitor_mut.map()?;
+                    Some(Layer::Map(NonuniqueBox::from(map), BTreeSet::new()))
+                }
+            };
+
+            let mut accept_comma;
+            let mut len;
+            let mut layer = match layer {
+                Some(layer) => {
+                    accept_comma = false;
+                    len = 0;
+                    layer
+                }
+                None => match self.stack.pop() {
+                    Some(frame) => {
+                        accept_comma = true;
+                        visitor = frame.0;
+                        len = frame.2;
+                        frame.1
+                    }
+                    None => return Ok(()),
+                },
+            };
+
+            loop {
+                match self.parse_whitespace().unwrap_or(b'\0') {
+                    b',' if accept_comma => {
+                        self.bump();
+                        if !self.options.relaxed {
+                            break;
+                        }
+                        // Next is either another element, or a close bracket
+                        // after a trailing comma.
+                        accept_comma = false;
+                    }
+                    close @ (b']' | b'}') => {
+                        self.bump();
+                        match &mut layer {
+                            Layer::Seq(seq) if close == b']' => seq.finish()?,
+                            Layer::Map(map, _) if close == b'}' => map.finish()?,
+                            _ => return Err(Error),
+                        }
+                        let Some(frame) = self.stack.pop() else {
+                            return Ok(());
+                        };
+                        accept_comma = true;
+                        visitor = frame.0;
+                        layer = frame.1;
+                        len = frame.2;
+                    }
+                    _ => {
+                        if accept_comma {
+                            return Err(Error);
+                        } else {
+                            break;
+                        }
                     }
                 }
             }
-        }
 
-        let outer = visitor;
-        match layer {
-            Layer::Seq(mut seq) => {
-                let element = seq.element()?;
-                let next = NonNull::from(element);
-                visitor = unsafe { extend_lifetime!(next as NonNull<dyn Visitor>) };
-                de.stack.push((outer, Layer::Seq(seq)));
-            }
-            Layer::Map(mut map) => {
-                match de.parse_whitespace() {
-                    Some(b'"') => de.bump(),
-                    _ => return Err(Error),
-                }
-                let key = de.parse_str()?;
-                let entry = map.key(key)?;
-                let next = NonNull::from(entry);
-                visitor = unsafe { extend_lifetime!(next as NonNull<dyn Visitor>) };
-                match de.parse_whitespace() {
-                    Some(b':') => de.bump(),
-                    _ => return Err(Error),
-                }
-                de.stack.push((outer, Layer::Map(map)));
+            if len >= self.options.max_elements {
+                return Err(Error);
+            }
+            let outer = visitor;
+            match layer {
+                Layer::Seq(mut seq) => {
+                    let element = seq.element()?;
+                    let next = NonNull::from(element);
+                    visitor = unsafe { extend_lifetime!(next as NonNull<dyn Visitor<'a>>) };
+                    self.stack.push((outer, Layer::Seq(seq), len + 1));
+                }
+                Layer::Map(mut map, mut keys) => {
+                    let policy = self.options.duplicate_keys;
+                    let key = self.parse_key()?;
+                    let entry = match policy {
+                        DuplicateKeys::Last => map.key(&key)?,
+                        _ if keys.insert((*key).to_owned()) => map.key(&key)?,
+                        DuplicateKeys::First => <dyn Visitor>::ignore(),
+                        DuplicateKeys::Error => return Err(Error),
+                    };
+                    let next = NonNull::from(entry);
+                    visitor = unsafe { extend_lifetime!(next as NonNull<dyn Visitor<'a>>) };
+                    match self.parse_whitespace() {
+                        Some(b':') => self.bump(),
+                        _ => return Err(Error),
+                    }
+                    self.stack.push((outer, Layer::Map(map, keys), len + 1));
+                }
             }
         }
     }
 
-    match de.parse_whitespace() {
-        Some(_) => Err(Error),
-        None => Ok(()),
+    // Skips over one value, checking its syntax, and returns its text.
+    fn parse_raw(&mut self) -> Result<&'a str> {
+        self.parse_whitespace();
+        let start = self.pos;
+        let mut de = Deserializer {
+            input: self.input,
+            pos: start,
+            buffer: mem::take(&mut self.buffer),
+            stack: Vec::new(),
+            options: Options {
+                max_depth: self.options.max_depth - self.stack.len(),
+                // The captured text must be JSON that can be written back out
+                // as is, even when the surrounding input is relaxed.
+                relaxed: false,
+                ..self.options
+            },
+            // This value has already been counted.
+            values: self.values - 1,
+        };
+        // Ignore never asks for raw JSON, so this goes no deeper.
+        let result = de.visit(<dyn Visitor>::ignore());
+        self.pos = de.pos;
+        self.buffer = mem::take(&mut de.buffer);
+        self.values = de.values;
+        result?;
+        let raw = &self.input[start..self.pos];
+        // Checked to be UTF-8 by the same means as the input to parse_str.
+        Ok(unsafe { str::from_utf8_unchecked(raw) })
+    }
+}
+
+// The push parser in json::parser finds where each token ends by itself, then
+// hands over the complete token to be parsed by one of these.
+
+pub(crate) fn visit_token(
+    token: &[u8],
+    scratch: &mut Vec<u8>,
+    visitor: &mut dyn Visitor<'_>,
+) -> Result<()> {
+    with_token(token, scratch, |de| match de.event()? {
+        Null => visitor.null(),
+        Bool(b) => visitor.boolean(b),
+        Negative(n) => visitor.negative(n),
+        Nonnegative(n) => visitor.nonnegative(n),
+        Float(n) => visitor.float(n),
+        // The token does not live as long as the input, so is never lent out.
+        Str(s) => visitor.string(&s),
+        SeqStart | MapStart => Err(Error),
+    })
+}
+
+pub(crate) fn visit_key<'a, 'de>(
+    token: &[u8],
+    scratch: &mut Vec<u8>,
+    map: &'a mut dyn Map<'de>,
+) -> Result<&'a mut dyn Visitor<'de>> {
+    with_token(token, scratch, |de| match de.next() {
+        Some(b'"') => map.key(&de.parse_str(b'"')?),
+        _ => Err(Error),
+    })
+}
+
+fn with_token<R>(
+    token: &[u8],
+    scratch: &mut Vec<u8>,
+    f: impl FnOnce(&mut Deserializer) -> Result<R>,
+) -> Result<R> {
+    // Unlike from_str, the input has not already been checked to be UTF-8.
+    if str::from_utf8(token).is_err() {
+        return Err(Error);
     }
+    let mut de = Deserializer {
+        input: token,
+        pos: 0,
+        buffer: mem::take(scratch),
+        stack: Vec::new(),
+        options: Options::new(),
+        values: 0,
+    };
+    let result = match f(&mut de) {
+        Ok(_) if de.pos < token.len() => Err(Error),
+        result => result,
+    };
+    *scratch = mem::take(&mut de.buffer);
+    result
 }
 
-enum Event<'a> {
+enum Event<'a, 's> {
     Null,
     Bool(bool),
-    Str(&'a str),
+    Str(Reference<'a, 's>),
     Negative(i64),
     Nonnegative(u64),
     Float(f64),
@@ -193,6 +380,24 @@ enum Event<'a> {
     MapStart,
 }
 
+// A string either borrowed from the input or copied into the scratch space
+// because it contained escape sequences.
+enum Reference<'a, 's> {
+    Borrowed(&'a str),
+    Copied(&'s str),
+}
+
+impl<'a, 's> Deref for Reference<'a, 's> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        match *self {
+            Borrowed(s) => s,
+            Copied(s) => s,
+        }
+    }
+}
+
 macro_rules! overflow {
     ($a:ident * 10 + $b:ident, $c:expr) => {
         match $c {
@@ -232,7 +437,8 @@ impl<'a, 'b> Deserializer<'a, 'b> {
         self.pos += 1;
     }
 
-    fn parse_str(&mut self) -> Result<&str> {
+    // Parses the rest of a string which began with the given quote.
+    fn parse_str(&mut self, quote: u8) -> Result<Reference<'a, '_>> {
         fn result(bytes: &[u8]) -> &str {
             // The deserialization input came in as &str with a UTF-8 guarantee,
             // and the \u-escapes are checked along the way, so don't need to
@@ -240,37 +446,55 @@ impl<'a, 'b> Deserializer<'a, 'b> {
             unsafe { str::from_utf8_unchecked(bytes) }
         }
 
+        let escape = if self.options.relaxed {
+            &ESCAPE_RELAXED
+        } else {
+            &ESCAPE
+        };
+
         // Index of the first byte not yet copied into the scratch space.
         let mut start = self.pos;
         self.buffer.clear();
 
         loop {
-            while self.pos < self.input.len() && !ESCAPE[usize::from(self.input[self.pos])] {
+            while self.pos < self.input.len() && !escape[usize::from(self.input[self.pos])] {
                 self.pos += 1;
             }
             if self.pos == self.input.len() {
                 return Err(Error);
             }
+            // Checked before anything more is copied into the scratch space,
+            // which so never grows much past the limit.
+            if self.buffer.len() + (self.pos - start) > self.options.max_string_len {
+                return Err(Error);
+            }
             match self.input[self.pos] {
-                b'"' => {
+                byte if byte == quote => {
                     if self.buffer.is_empty() {
                         // Fast path: return a slice of the raw JSON without any
                         // copying.
                         let borrowed = &self.input[start..self.pos];
                         self.pos += 1;
-                        return Ok(result(borrowed));
+                        return Ok(Borrowed(result(borrowed)));
                     } else {
                         self.buffer.extend_from_slice(&self.input[start..self.pos]);
                         self.pos += 1;
-                        return Ok(result(&self.buffer));
+                        return Ok(Copied(result(&self.buffer)));
                     }
                 }
                 b'\\' => {
                     self.buffer.extend_from_slice(&self.input[start..self.pos]);
                     self.pos += 1;
                     self.parse_escape()?;
+                    if self.buffer.len() > self.options.max_string_len {
+                        return Err(Error);
+                    }
                     start = self.pos;
                 }
+                // The other kind of quote than the one this string began with.
+                b'"' | b'\'' => {
+                    self.pos += 1;
+                }
                 _ => {
                     return Err(Error);
                 }
@@ -289,6 +513,7 @@ impl<'a, 'b> Deserializer<'a, 'b> {
 
         match ch {
             b'"' => self.buffer.push(b'"'),
+            b'\'' if self.options.relaxed => self.buffer.push(b'\''),
             b'\\' => self.buffer.push(b'\\'),
             b'/' => self.buffer.push(b'/'),
             b'b' => self.buffer.push(b'\x08'),
@@ -373,6 +598,11 @@ impl<'a, 'b> Deserializer<'a, 'b> {
                 Some(b' ' | b'\n' | b'\t' | b'\r') => {
                     self.bump();
                 }
+                Some(b'/') if self.options.relaxed => {
+                    if !self.parse_comment() {
+                        return Some(b'/');
+                    }
+                }
                 other => {
                     return other;
                 }
@@ -380,6 +610,52 @@ impl<'a, 'b> Deserializer<'a, 'b> {
         }
     }
 
+    // Skips a line or block comment. Returns false, without consuming
+    // anything, if there is not a complete comment here.
+    fn parse_comment(&mut self) -> bool {
+        let rest = &self.input[self.pos..];
+        let len = match rest.get(1) {
+            Some(b'/') => match rest.iter().position(|&b| b == b'\n') {
+                Some(i) => i + 1,
+                None => rest.len(),
+            },
+            Some(b'*') => match rest[2..].windows(2).position(|w| w == b"*/") {
+                Some(i) => i + 4,
+                None => return false,
+            },
+            _ => return false,
+        };
+        self.pos += len;
+        true
+    }
+
+    fn parse_key(&mut self) -> Result<Reference<'a, '_>> {
+        match self.parse_whitespace() {
+            Some(b'"') => {
+                self.bump();
+                self.parse_str(b'"')
+            }
+            Some(b'\'') if self.options.relaxed => {
+                self.bump();
+                self.parse_str(b'\'')
+            }
+            Some(b'A'..=b'Z' | b'a'..=b'z' | b'_' | b'$') if self.options.relaxed => {
+                let start = self.pos;
+                while let b'0'..=b'9' | b'A'..=b'Z' | b'a'..=b'z' | b'_' | b'$' = self.peek_or_nul()
+                {
+                    self.bump();
+                }
+                let ident = &self.input[start..self.pos];
+                if ident.len() > self.options.max_string_len {
+                    return Err(Error);
+                }
+                // All ASCII.
+                Ok(Borrowed(unsafe { str::from_utf8_unchecked(ident) }))
+            }
+            _ => Err(Error),
+        }
+    }
+
     fn parse_ident(&mut self, ident: &[u8]) -> Result<()> {
         for expected in ident {
             match self.next() {
@@ -396,8 +672,20 @@ impl<'a, 'b> Deserializer<'a, 'b> {
         Ok(())
     }
 
-    fn parse_integer(&mut self, nonnegative: bool, first_digit: u8) -> Result<Event> {
+    fn parse_integer(&mut self, nonnegative: bool, first_digit: u8) -> Result<Event<'a, '_>> {
         match first_digit {
+            b'0' if self.options.relaxed && matches!(self.peek_or_nul(), b'x' | b'X') => {
+                self.bump();
+                self.parse_hex(nonnegative)
+            }
+            b'I' if self.options.relaxed => {
+                self.parse_ident(b"nfinity")?;
+                Ok(Float(if nonnegative {
+                    f64::INFINITY
+                } else {
+                    f64::NEG_INFINITY
+                }))
+            }
             b'0' => {
                 // There can be only one leading '0'.
                 match self.peek_or_nul() {
@@ -439,6 +727,29 @@ impl<'a, 'b> Deserializer<'a, 'b> {
         }
     }
 
+    fn parse_hex(&mut self, nonnegative: bool) -> Result<Event<'a, '_>> {
+        let mut res: u64 = 0;
+        let mut at_least_one_digit = false;
+        loop {
+            let digit = match self.peek_or_nul() {
+                c @ b'0'..=b'9' => c - b'0',
+                c @ b'a'..=b'f' => c - b'a' + 10,
+                c @ b'A'..=b'F' => c - b'A' + 10,
+                _ => break,
+            };
+            self.bump();
+            at_least_one_digit = true;
+            res = res
+                .checked_mul(16)
+                .and_then(|res| res.checked_add(u64::from(digit)))
+                .ok_or(Error)?;
+        }
+        if !at_least_one_digit {
+            return Err(Error);
+        }
+        Ok(integer(nonnegative, res))
+    }
+
     fn parse_long_integer(
         &mut self,
         nonnegative: bool,
@@ -466,24 +777,11 @@ impl<'a, 'b> Deserializer<'a, 'b> {
         }
     }
 
-    fn parse_number(&mut self, nonnegative: bool, significand: u64) -> Result<Event> {
+    fn parse_number(&mut self, nonnegative: bool, significand: u64) -> Result<Event<'a, '_>> {
         match self.peek_or_nul() {
             b'.' => self.parse_decimal(nonnegative, significand, 0).map(Float),
             b'e' | b'E' => self.parse_exponent(nonnegative, significand, 0).map(Float),
-            _ => {
-                Ok(if nonnegative {
-                    Nonnegative(significand)
-                } else {
-                    let neg = (significand as i64).wrapping_neg();
-
-                    // Convert into a float if we underflow.
-                    if neg > 0 {
-                        Float(-(significand as f64))
-                    } else {
-                        Negative(neg)
-                    }
-                })
-            }
+            _ => Ok(integer(nonnegative, significand)),
         }
     }
 
@@ -593,13 +891,14 @@ impl<'a, 'b> Deserializer<'a, 'b> {
         Ok(if nonnegative { 0.0 } else { -0.0 })
     }
 
-    fn event(&mut self) -> Result<Event> {
+    fn event(&mut self) -> Result<Event<'a, '_>> {
         let Some(peek) = self.parse_whitespace() else {
             return Err(Error);
         };
         self.bump();
         match peek {
-            b'"' => self.parse_str().map(Str),
+            b'"' => self.parse_str(b'"').map(Str),
+            b'\'' if self.options.relaxed => self.parse_str(b'\'').map(Str),
             digit @ b'0'..=b'9' => self.parse_integer(true, digit),
             b'-' => {
                 let first_digit = self.next_or_nul();
@@ -619,11 +918,40 @@ impl<'a, 'b> Deserializer<'a, 'b> {
                 self.parse_ident(b"alse")?;
                 Ok(Bool(false))
             }
+            b'I' if self.options.relaxed => self.parse_integer(true, peek),
+            b'N' if self.options.relaxed => {
+                self.parse_ident(b"aN")?;
+                Ok(Float(f64::NAN))
+            }
             _ => Err(Error),
         }
     }
 }
 
+fn non_finite_str(s: &str) -> Option<f64> {
+    match s {
+        "NaN" => Some(f64::NAN),
+        "Infinity" => Some(f64::INFINITY),
+        "-Infinity" => Some(f64::NEG_INFINITY),
+        _ => None,
+    }
+}
+
+fn integer<'a, 's>(nonnegative: bool, significand: u64) -> Event<'a, 's> {
+    if nonnegative {
+        Nonnegative(significand)
+    } else {
+        let neg = (significand as i64).wrapping_neg();
+
+        // Convert into a float if we underflow.
+        if neg > 0 {
+            Float(-(significand as f64))
+        } else {
+            Negative(neg)
+        }
+    }
+}
+
 fn f64_from_parts(nonnegative: bool, significand: u64, mut exponent: i32) -> Result<f64> {
     let mut f = significand as f64;
     loop {
@@ -717,3 +1045,26 @@ static ESCAPE: [bool; 256] = [
      O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O, // E
      O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O, // F
 ];
+
+// Same as ESCAPE, but also stopping at a single quote, which may begin and end
+// a string in relaxed mode.
+#[rustfmt::skip]
+static ESCAPE_RELAXED: [bool; 256] = [
+    //   1   2   3   4   5   6   7   8   9   A   B   C   D   E   F
+    CT, CT, CT, CT, CT, CT, CT, CT, CT, CT, CT, CT, CT, CT, CT, CT, // 0
+    CT, CT, CT, CT, CT, CT, CT, CT, CT, CT, CT, CT, CT, CT, CT, CT, // 1
+     O,  O, QU,  O,  O,  O,  O, QU,  O,  O,  O,  O,  O,  O,  O,  O, // 2
+     O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O, // 3
+     O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O, // 4
+     O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O, BS,  O,  O,  O, // 5
+     O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O, // 6
+     O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O, // 7
+     O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O, // 8
+     O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O, // 9
+     O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O, // A
+     O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O, // B
+     O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O, // C
+     O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O, // D
+     O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O, // E
+     O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O,  O, // F
+];
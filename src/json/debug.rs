@@ -0,0 +1,104 @@
+use crate::json::{Array, Object, Value};
+use alloc::collections::btree_map;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+use core::slice;
+
+// Matches the output of Formatter::debug_list and Formatter::debug_map,
+// including the `{:#?}` pretty format, without recursing into nested arrays
+// and objects.
+
+enum Layer<'a> {
+    Array(slice::Iter<'a, Value>),
+    Object(btree_map::Iter<'a, String, Value>),
+}
+
+pub(crate) fn debug_value(value: &Value, formatter: &mut fmt::Formatter) -> fmt::Result {
+    match value {
+        Value::Null => formatter.write_str("Null"),
+        Value::Bool(boolean) => write!(formatter, "Bool({})", boolean),
+        Value::Number(number) => write!(formatter, "Number({})", number),
+        Value::String(string) => write!(formatter, "String({:?})", string),
+        Value::Array(array) => debug_array(array, formatter),
+        Value::Object(object) => debug_object(object, formatter),
+    }
+}
+
+pub(crate) fn debug_array(array: &Array, formatter: &mut fmt::Formatter) -> fmt::Result {
+    formatter.write_str("Array [")?;
+    walk(Layer::Array(array.iter()), formatter)
+}
+
+pub(crate) fn debug_object(object: &Object, formatter: &mut fmt::Formatter) -> fmt::Result {
+    formatter.write_str("Object {")?;
+    walk(Layer::Object(object.iter()), formatter)
+}
+
+fn walk(layer: Layer, formatter: &mut fmt::Formatter) -> fmt::Result {
+    let pretty = formatter.alternate();
+    // Each layer records whether it has produced any entries yet.
+    let mut stack = Vec::new();
+    stack.push((layer, true));
+
+    loop {
+        let depth = stack.len();
+        let Some((layer, first)) = stack.last_mut() else {
+            return Ok(());
+        };
+
+        if pretty && !*first {
+            formatter.write_str(",\n")?;
+        }
+
+        let next = match layer {
+            Layer::Array(iter) => iter.next().map(|value| (None, value)),
+            Layer::Object(iter) => iter.next().map(|(key, value)| (Some(key), value)),
+        };
+
+        let Some((key, value)) = next else {
+            if pretty && !*first {
+                indent(formatter, depth - 1)?;
+            }
+            formatter.write_char(match layer {
+                Layer::Array(_) => ']',
+                Layer::Object(_) => '}',
+            })?;
+            stack.pop();
+            continue;
+        };
+
+        if pretty {
+            if *first {
+                formatter.write_char('\n')?;
+            }
+            indent(formatter, depth)?;
+        } else if !*first {
+            formatter.write_str(", ")?;
+        }
+        *first = false;
+
+        if let Some(key) = key {
+            write!(formatter, "{:?}: ", key)?;
+        }
+
+        match value {
+            Value::Array(array) => {
+                formatter.write_str("Array [")?;
+                stack.push((Layer::Array(array.iter()), true));
+            }
+            Value::Object(object) => {
+                formatter.write_str("Object {")?;
+                stack.push((Layer::Object(object.iter()), true));
+            }
+            primitive => debug_value(primitive, formatter)?,
+        }
+    }
+}
+
+fn indent(formatter: &mut fmt::Formatter, depth: usize) -> fmt::Result {
+    for _ in 0..depth {
+        formatter.write_str("    ")?;
+    }
+    Ok(())
+}
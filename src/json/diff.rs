@@ -0,0 +1,297 @@
+use crate::json::{Array, Object, Value};
+use alloc::collections::btree_map;
+use alloc::string::String;
+use alloc::vec::{self, Vec};
+use core::iter::{Enumerate, Zip};
+use core::slice;
+
+/// How [`json::diff_with`][crate::json::diff_with] matches up the elements of
+/// two arrays.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ArrayDiff {
+    /// Compare elements at the same index, then add or remove elements at the
+    /// end. This is fast but produces a long patch when an element is
+    /// inserted or removed near the front of an array.
+    #[default]
+    ByIndex,
+    /// Compute a longest common subsequence of the elements, so insertions and
+    /// removals anywhere in the array become single `add` and `remove`
+    /// operations. This takes time and memory proportional to the product of
+    /// the lengths of the differing parts of the two arrays, and elements are
+    /// compared in full at every level of nesting.
+    Lcs,
+}
+
+/// Compute a JSON Patch that turns `source` into `target`.
+///
+/// The result is an array of [RFC 6902] operations that can be passed to
+/// [`json::patch::apply`][crate::json::patch::apply]. Objects are compared key
+/// by key and arrays index by index.
+///
+/// [RFC 6902]: https://tools.ietf.org/html/rfc6902
+///
+/// ```rust
+/// use miniserde::json::{self, Value};
+///
+/// fn main() -> miniserde::Result<()> {
+///     let source: Value = json::from_str(r#" {"a": [1, 2], "b": "c"} "#)?;
+///     let target: Value = json::from_str(r#" {"a": [1, 3, 4], "d": "c"} "#)?;
+///
+///     let patch = json::diff(&source, &target);
+///     assert_eq!(
+///         json::to_string(&patch),
+///         concat!(
+///             r#"[{"op":"remove","path":"/b"},"#,
+///             r#"{"op":"add","path":"/d","value":"c"},"#,
+///             r#"{"op":"add","path":"/a/2","value":4},"#,
+///             r#"{"op":"replace","path":"/a/1","value":3}]"#,
+///         ),
+///     );
+///
+///     let mut doc = source.clone();
+///     json::patch::apply(&mut doc, &patch)?;
+///     assert_eq!(doc, target);
+///
+///     Ok(())
+/// }
+/// ```
+pub fn diff(source: &Value, target: &Value) -> Value {
+    diff_with(source, target, ArrayDiff::ByIndex)
+}
+
+/// Compute a JSON Patch that turns `source` into `target`, choosing how array
+/// elements are matched up.
+pub fn diff_with(source: &Value, target: &Value, arrays: ArrayDiff) -> Value {
+    let mut differ = Differ {
+        arrays,
+        path: String::new(),
+        ops: Array::new(),
+    };
+    let mut stack = Vec::new();
+
+    if let Some(children) = differ.compare(source, target) {
+        stack.push((0, children));
+    }
+
+    // All the operations on one level are emitted before descending into the
+    // children, whose paths are not affected by those operations.
+    while let Some((len, children)) = stack.last_mut() {
+        let len = *len;
+        let Some((token, source, target)) = children.next() else {
+            stack.pop();
+            continue;
+        };
+        differ.path.truncate(len);
+        match token {
+            Token::Key(key) => differ.push_key(key),
+            Token::Index(index) => differ.push_index(index),
+        }
+        if let Some(children) = differ.compare(source, target) {
+            stack.push((differ.path.len(), children));
+        }
+    }
+
+    Value::Array(differ.ops)
+}
+
+struct Differ {
+    arrays: ArrayDiff,
+    // Pointer to the values currently being compared.
+    path: String,
+    ops: Array,
+}
+
+enum Token<'a> {
+    Key(&'a str),
+    Index(usize),
+}
+
+// Pairs of values below the current path that still need to be compared.
+enum Children<'a> {
+    Object(btree_map::Iter<'a, String, Value>, &'a Object),
+    Array(Enumerate<Zip<slice::Iter<'a, Value>, slice::Iter<'a, Value>>>),
+    Pairs(vec::IntoIter<(usize, &'a Value, &'a Value)>),
+}
+
+impl<'a> Iterator for Children<'a> {
+    type Item = (Token<'a>, &'a Value, &'a Value);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Children::Object(iter, target) => {
+                for (key, source) in iter {
+                    if let Some(target) = target.get(key) {
+                        return Some((Token::Key(key), source, target));
+                    }
+                }
+                None
+            }
+            Children::Array(iter) => {
+                let (index, (source, target)) = iter.next()?;
+                Some((Token::Index(index), source, target))
+            }
+            Children::Pairs(iter) => {
+                let (index, source, target) = iter.next()?;
+                Some((Token::Index(index), source, target))
+            }
+        }
+    }
+}
+
+impl Differ {
+    // Emits the operations needed at the current path and returns the pairs
+    // of children that still need to be compared.
+    fn compare<'a>(&mut self, source: &'a Value, target: &'a Value) -> Option<Children<'a>> {
+        match (source, target) {
+            (Value::Object(source), Value::Object(target)) => {
+                for key in source.keys() {
+                    if !target.contains_key(key) {
+                        self.op_at_key("remove", key, None);
+                    }
+                }
+                for (key, value) in target {
+                    if !source.contains_key(key) {
+                        self.op_at_key("add", key, Some(value));
+                    }
+                }
+                Some(Children::Object(source.iter(), target))
+            }
+            (Value::Array(source), Value::Array(target)) => match self.arrays {
+                ArrayDiff::ByIndex => {
+                    let common = source.len().min(target.len());
+                    for index in (common..source.len()).rev() {
+                        self.op_at_index("remove", index, None);
+                    }
+                    for (index, value) in target.iter().enumerate().skip(common) {
+                        self.op_at_index("add", index, Some(value));
+                    }
+                    Some(Children::Array(
+                        source.iter().zip(target.iter()).enumerate(),
+                    ))
+                }
+                ArrayDiff::Lcs => Some(Children::Pairs(self.lcs(source, target).into_iter())),
+            },
+            (Value::Null, Value::Null) => None,
+            (Value::Bool(a), Value::Bool(b)) if a == b => None,
+            (Value::Number(a), Value::Number(b)) if a == b => None,
+            (Value::String(a), Value::String(b)) if a == b => None,
+            _ => {
+                let path = self.path.clone();
+                self.op(String::from("replace"), path, Some(target));
+                None
+            }
+        }
+    }
+
+    // Emits adds and removes based on a longest common subsequence, and
+    // returns the remaining pairs of elements which occupy the same position
+    // once those have been applied.
+    fn lcs<'a>(
+        &mut self,
+        source: &'a [Value],
+        target: &'a [Value],
+    ) -> Vec<(usize, &'a Value, &'a Value)> {
+        let mut start = 0;
+        while start < source.len() && start < target.len() && source[start] == target[start] {
+            start += 1;
+        }
+        let mut source_end = source.len();
+        let mut target_end = target.len();
+        while source_end > start
+            && target_end > start
+            && source[source_end - 1] == target[target_end - 1]
+        {
+            source_end -= 1;
+            target_end -= 1;
+        }
+        let source = &source[start..source_end];
+        let target = &target[start..target_end];
+
+        // lengths[i * (m + 1) + j] is the length of the longest common
+        // subsequence of source[i..] and target[j..].
+        let (n, m) = (source.len(), target.len());
+        let mut equal = Vec::with_capacity(n * m);
+        for a in source {
+            for b in target {
+                equal.push(a == b);
+            }
+        }
+        let mut lengths = alloc::vec![0usize; (n + 1) * (m + 1)];
+        for i in (0..n).rev() {
+            for j in (0..m).rev() {
+                lengths[i * (m + 1) + j] = if equal[i * m + j] {
+                    lengths[(i + 1) * (m + 1) + j + 1] + 1
+                } else {
+                    lengths[(i + 1) * (m + 1) + j].max(lengths[i * (m + 1) + j + 1])
+                };
+            }
+        }
+        let lcs = |i: usize, j: usize| lengths[i * (m + 1) + j];
+
+        let mut pairs = Vec::new();
+        let mut index = start;
+        let (mut i, mut j) = (0, 0);
+        while i < n || j < m {
+            if i < n && j < m && equal[i * m + j] {
+                index += 1;
+                i += 1;
+                j += 1;
+            } else if i < n && j < m && lcs(i + 1, j + 1) == lcs(i, j) {
+                pairs.push((index, &source[i], &target[j]));
+                index += 1;
+                i += 1;
+                j += 1;
+            } else if i < n && (j == m || lcs(i + 1, j) >= lcs(i, j + 1)) {
+                self.op_at_index("remove", index, None);
+                i += 1;
+            } else {
+                self.op_at_index("add", index, Some(&target[j]));
+                index += 1;
+                j += 1;
+            }
+        }
+        pairs
+    }
+
+    fn push_key(&mut self, key: &str) {
+        self.path.push('/');
+        for ch in key.chars() {
+            match ch {
+                '~' => self.path.push_str("~0"),
+                '/' => self.path.push_str("~1"),
+                ch => self.path.push(ch),
+            }
+        }
+    }
+
+    fn push_index(&mut self, index: usize) {
+        self.path.push('/');
+        self.path.push_str(itoa::Buffer::new().format(index));
+    }
+
+    fn op_at_key(&mut self, op: &str, key: &str, value: Option<&Value>) {
+        let len = self.path.len();
+        self.push_key(key);
+        let path = self.path.clone();
+        self.path.truncate(len);
+        self.op(String::from(op), path, value);
+    }
+
+    fn op_at_index(&mut self, op: &str, index: usize, value: Option<&Value>) {
+        let len = self.path.len();
+        self.push_index(index);
+        let path = self.path.clone();
+        self.path.truncate(len);
+        self.op(String::from(op), path, value);
+    }
+
+    fn op(&mut self, op: String, path: String, value: Option<&Value>) {
+        let mut object = Object::new();
+        object.insert(String::from("op"), Value::String(op));
+        object.insert(String::from("path"), Value::String(path));
+        if let Some(value) = value {
+            object.insert(String::from("value"), value.clone());
+        }
+        self.ops.push(Value::Object(object));
+    }
+}
@@ -0,0 +1,182 @@
+use crate::json::{Array, Number, Object, Value};
+use alloc::borrow::Cow;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::hash::BuildHasher;
+
+impl From<()> for Value {
+    fn from((): ()) -> Self {
+        Value::Null
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        Value::Bool(b)
+    }
+}
+
+macro_rules! from_unsigned {
+    ($($ty:ident)*) => {
+        $(
+            impl From<$ty> for Number {
+                fn from(n: $ty) -> Self {
+                    Number::U64(n as u64)
+                }
+            }
+
+            impl From<$ty> for Value {
+                fn from(n: $ty) -> Self {
+                    Value::Number(Number::from(n))
+                }
+            }
+        )*
+    };
+}
+from_unsigned!(u8 u16 u32 u64 usize);
+
+macro_rules! from_signed {
+    ($($ty:ident)*) => {
+        $(
+            impl From<$ty> for Number {
+                fn from(n: $ty) -> Self {
+                    if n < 0 {
+                        Number::I64(n as i64)
+                    } else {
+                        Number::U64(n as u64)
+                    }
+                }
+            }
+
+            impl From<$ty> for Value {
+                fn from(n: $ty) -> Self {
+                    Value::Number(Number::from(n))
+                }
+            }
+        )*
+    };
+}
+from_signed!(i8 i16 i32 i64 isize);
+
+macro_rules! from_float {
+    ($($ty:ident)*) => {
+        $(
+            impl From<$ty> for Number {
+                fn from(n: $ty) -> Self {
+                    Number::F64(n as f64)
+                }
+            }
+
+            impl From<$ty> for Value {
+                fn from(n: $ty) -> Self {
+                    Value::Number(Number::from(n))
+                }
+            }
+        )*
+    };
+}
+from_float!(f32 f64);
+
+impl From<Number> for Value {
+    fn from(number: Number) -> Self {
+        Value::Number(number)
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Value::String(s)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::String(String::from(s))
+    }
+}
+
+impl<'a> From<Cow<'a, str>> for Value {
+    fn from(s: Cow<'a, str>) -> Self {
+        Value::String(s.into_owned())
+    }
+}
+
+impl From<Array> for Value {
+    fn from(array: Array) -> Self {
+        Value::Array(array)
+    }
+}
+
+impl From<Object> for Value {
+    fn from(object: Object) -> Self {
+        Value::Object(object)
+    }
+}
+
+impl<T> From<Option<T>> for Value
+where
+    T: Into<Value>,
+{
+    fn from(option: Option<T>) -> Self {
+        match option {
+            Some(some) => some.into(),
+            None => Value::Null,
+        }
+    }
+}
+
+impl<T> From<Vec<T>> for Value
+where
+    T: Into<Value>,
+{
+    fn from(vec: Vec<T>) -> Self {
+        Value::Array(vec.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T> From<&[T]> for Value
+where
+    T: Clone + Into<Value>,
+{
+    fn from(slice: &[T]) -> Self {
+        Value::Array(slice.iter().cloned().map(Into::into).collect())
+    }
+}
+
+impl<K, V> From<BTreeMap<K, V>> for Value
+where
+    K: Into<String>,
+    V: Into<Value>,
+{
+    fn from(map: BTreeMap<K, V>) -> Self {
+        Value::Object(map.into_iter().map(|(k, v)| (k.into(), v.into())).collect())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, H> From<HashMap<K, V, H>> for Value
+where
+    K: Into<String>,
+    V: Into<Value>,
+    H: BuildHasher,
+{
+    fn from(map: HashMap<K, V, H>) -> Self {
+        Value::Object(map.into_iter().map(|(k, v)| (k.into(), v.into())).collect())
+    }
+}
+
+impl<T> FromIterator<T> for Value
+where
+    T: Into<Value>,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        Value::Array(iter.into_iter().map(Into::into).collect())
+    }
+}
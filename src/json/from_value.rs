@@ -0,0 +1,174 @@
+use crate::de::{Deserialize, DeserializeOwned, Map, Seq, Visitor};
+use crate::error::{Error, Result};
+use crate::json::{to_string, Number, Value};
+use crate::ptr::NonuniqueBox;
+use alloc::collections::btree_map;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ptr::NonNull;
+use core::slice;
+
+/// Deserialize a `json::Value` into any deserializable type.
+///
+/// This produces the same result as serializing the value to a JSON string and
+/// deserializing from that string, but without going through text.
+///
+/// ```rust
+/// use miniserde::json::{self, Value};
+/// use miniserde::Deserialize;
+///
+/// #[derive(Deserialize, Debug)]
+/// struct Example {
+///     code: u32,
+///     message: String,
+/// }
+///
+/// fn main() -> miniserde::Result<()> {
+///     let value: Value = json::from_str(r#" {"code": 200, "message": "ok"} "#)?;
+///
+///     let out: Example = json::from_value(value)?;
+///     println!("{:?}", out);
+///
+///     Ok(())
+/// }
+/// ```
+pub fn from_value<T>(value: Value) -> Result<T>
+where
+    T: DeserializeOwned,
+{
+    from_value_ref(&value)
+}
+
+/// Deserialize a borrowed `json::Value` into any deserializable type.
+///
+/// Strings in the output may borrow from the strings in the value.
+pub fn from_value_ref<'de, T>(value: &'de Value) -> Result<T>
+where
+    T: Deserialize<'de>,
+{
+    let mut out = None;
+    from_value_impl(value, T::begin(&mut out))?;
+    out.ok_or(Error)
+}
+
+struct Deserializer<'a, 'b> {
+    stack: Vec<(NonNull<dyn Visitor<'a>>, Layer<'a, 'b>)>,
+}
+
+enum Layer<'a, 'b> {
+    Seq(NonuniqueBox<dyn Seq<'a> + 'b>, slice::Iter<'a, Value>),
+    Map(
+        NonuniqueBox<dyn Map<'a> + 'b>,
+        btree_map::Iter<'a, String, Value>,
+    ),
+}
+
+impl<'a, 'b> Drop for Deserializer<'a, 'b> {
+    fn drop(&mut self) {
+        // Drop layers in reverse order.
+        while !self.stack.is_empty() {
+            self.stack.pop();
+        }
+    }
+}
+
+fn from_value_impl<'de>(value: &'de Value, visitor: &mut dyn Visitor<'de>) -> Result<()> {
+    let visitor = NonNull::from(visitor);
+    let mut visitor = unsafe { extend_lifetime!(visitor as NonNull<dyn Visitor<'de>>) };
+    let mut de = Deserializer { stack: Vec::new() };
+    let mut value = value;
+
+    loop {
+        let visitor_mut = unsafe { &mut *visitor.as_ptr() };
+        let layer = match value {
+            _ if visitor_mut.wants_raw_json() => {
+                visitor_mut.raw_json(&to_string(value))?;
+                None
+            }
+            Value::Null => {
+                visitor_mut.null()?;
+                None
+            }
+            Value::Bool(b) => {
+                visitor_mut.boolean(*b)?;
+                None
+            }
+            Value::Number(Number::U64(n)) => {
+                visitor_mut.nonnegative(*n)?;
+                None
+            }
+            Value::Number(Number::I64(n)) => {
+                if *n < 0 {
+                    visitor_mut.negative(*n)?;
+                } else {
+                    visitor_mut.nonnegative(*n as u64)?;
+                }
+                None
+            }
+            Value::Number(Number::F64(n)) => {
+                visitor_mut.float(*n)?;
+                None
+            }
+            Value::String(s) => {
+                visitor_mut.borrowed_string(s)?;
+                None
+            }
+            Value::Array(array) => {
+                let seq = visitor_mut.seq()?;
+                Some(Layer::Seq(NonuniqueBox::from(seq), array.iter()))
+            }
+            Value::Object(object) => {
+                let map = visitor_mut.map()?;
+                Some(Layer::Map(NonuniqueBox::from(map), object.iter()))
+            }
+        };
+
+        let mut layer = match layer {
+            Some(layer) => layer,
+            None => match de.stack.pop() {
+                Some(frame) => {
+                    visitor = frame.0;
+                    frame.1
+                }
+                None => return Ok(()),
+            },
+        };
+
+        loop {
+            let next = match &mut layer {
+                Layer::Seq(seq, iter) => match iter.next() {
+                    Some(element) => Some((seq.element()?, element)),
+                    None => {
+                        seq.finish()?;
+                        None
+                    }
+                },
+                Layer::Map(map, iter) => match iter.next() {
+                    Some((key, entry)) => Some((map.key(key)?, entry)),
+                    None => {
+                        map.finish()?;
+                        None
+                    }
+                },
+            };
+
+            match next {
+                Some((next_visitor, next_value)) => {
+                    let next = NonNull::from(next_visitor);
+                    let next = unsafe { extend_lifetime!(next as NonNull<dyn Visitor<'de>>) };
+                    de.stack.push((visitor, layer));
+                    visitor = next;
+                    value = next_value;
+                    break;
+                }
+                None => match de.stack.pop() {
+                    Some(frame) => {
+                        visitor = frame.0;
+                        layer = frame.1;
+                    }
+                    None => return Ok(()),
+                },
+            }
+        }
+    }
+}
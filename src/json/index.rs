@@ -0,0 +1,191 @@
+use crate::json::Value;
+use alloc::string::String;
+use core::ops;
+
+/// A type that can be used to index into a `json::Value`.
+///
+/// This trait is sealed and implemented for `usize` (array elements), and for
+/// `str` and `String` (object entries).
+pub trait Index: private::Sealed {
+    #[doc(hidden)]
+    fn index_into<'v>(&self, v: &'v Value) -> Option<&'v Value>;
+
+    #[doc(hidden)]
+    fn index_into_mut<'v>(&self, v: &'v mut Value) -> Option<&'v mut Value>;
+}
+
+impl Index for usize {
+    fn index_into<'v>(&self, v: &'v Value) -> Option<&'v Value> {
+        match v {
+            Value::Array(array) => array.get(*self),
+            _ => None,
+        }
+    }
+
+    fn index_into_mut<'v>(&self, v: &'v mut Value) -> Option<&'v mut Value> {
+        match v {
+            Value::Array(array) => array.get_mut(*self),
+            _ => None,
+        }
+    }
+}
+
+impl Index for str {
+    fn index_into<'v>(&self, v: &'v Value) -> Option<&'v Value> {
+        match v {
+            Value::Object(object) => object.get(self),
+            _ => None,
+        }
+    }
+
+    fn index_into_mut<'v>(&self, v: &'v mut Value) -> Option<&'v mut Value> {
+        match v {
+            Value::Object(object) => object.get_mut(self),
+            _ => None,
+        }
+    }
+}
+
+impl Index for String {
+    fn index_into<'v>(&self, v: &'v Value) -> Option<&'v Value> {
+        self[..].index_into(v)
+    }
+
+    fn index_into_mut<'v>(&self, v: &'v mut Value) -> Option<&'v mut Value> {
+        self[..].index_into_mut(v)
+    }
+}
+
+impl<T> Index for &T
+where
+    T: ?Sized + Index,
+{
+    fn index_into<'v>(&self, v: &'v Value) -> Option<&'v Value> {
+        (**self).index_into(v)
+    }
+
+    fn index_into_mut<'v>(&self, v: &'v mut Value) -> Option<&'v mut Value> {
+        (**self).index_into_mut(v)
+    }
+}
+
+mod private {
+    use alloc::string::String;
+
+    pub trait Sealed {}
+    impl Sealed for usize {}
+    impl Sealed for str {}
+    impl Sealed for String {}
+    impl<T> Sealed for &T where T: ?Sized + Sealed {}
+}
+
+impl<I> ops::Index<I> for Value
+where
+    I: Index,
+{
+    type Output = Value;
+
+    /// Index into an array by position or into an object by key.
+    ///
+    /// Returns `Value::Null` if the type of `self` does not match the type of
+    /// the index, or if the array is too short or the object has no such key.
+    fn index(&self, index: I) -> &Value {
+        static NULL: Value = Value::Null;
+        index.index_into(self).unwrap_or(&NULL)
+    }
+}
+
+// Parses one reference token of a JSON Pointer as an array index. RFC 6901
+// forbids leading zeros and the "-" past-the-end marker never refers to an
+// existing element.
+pub(crate) fn parse_array_index(token: &str) -> Option<usize> {
+    if token.is_empty() || token.len() > 1 && token.starts_with('0') {
+        return None;
+    }
+    if !token.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    token.parse().ok()
+}
+
+// Undoes the "~1" and "~0" escapes of a JSON Pointer reference token.
+pub(crate) fn unescape_token(token: &str) -> Option<String> {
+    let mut unescaped = String::with_capacity(token.len());
+    let mut chars = token.chars();
+    while let Some(ch) = chars.next() {
+        if ch == '~' {
+            match chars.next() {
+                Some('0') => unescaped.push('~'),
+                Some('1') => unescaped.push('/'),
+                _ => return None,
+            }
+        } else {
+            unescaped.push(ch);
+        }
+    }
+    Some(unescaped)
+}
+
+impl Value {
+    /// Look up a value by a JSON Pointer as defined in [RFC 6901].
+    ///
+    /// The empty string refers to the whole value. Otherwise the pointer must
+    /// begin with `/` and consists of reference tokens separated by `/`, in
+    /// which `~1` stands for `/` and `~0` stands for `~`.
+    ///
+    /// [RFC 6901]: https://tools.ietf.org/html/rfc6901
+    ///
+    /// ```rust
+    /// use miniserde::json::{self, Value};
+    ///
+    /// fn main() -> miniserde::Result<()> {
+    ///     let j = r#" {"data": [{"id": 7, "a/b": true}]} "#;
+    ///     let value: Value = json::from_str(j)?;
+    ///
+    ///     assert_eq!(value.pointer("/data/0/id").and_then(Value::as_u64), Some(7));
+    ///     assert_eq!(value.pointer("/data/0/a~1b").and_then(Value::as_bool), Some(true));
+    ///     assert!(value.pointer("/data/1").is_none());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn pointer(&self, pointer: &str) -> Option<&Value> {
+        if pointer.is_empty() {
+            return Some(self);
+        }
+        if !pointer.starts_with('/') {
+            return None;
+        }
+        let mut target = self;
+        for token in pointer[1..].split('/') {
+            let token = unescape_token(token)?;
+            target = match target {
+                Value::Object(object) => object.get(&token)?,
+                Value::Array(array) => array.get(parse_array_index(&token)?)?,
+                _ => return None,
+            };
+        }
+        Some(target)
+    }
+
+    /// Look up a value by a JSON Pointer and return a mutable reference to it.
+    ///
+    /// See [`Value::pointer`] for the syntax of the pointer.
+    pub fn pointer_mut(&mut self, pointer: &str) -> Option<&mut Value> {
+        if pointer.is_empty() {
+            return Some(self);
+        }
+        if !pointer.starts_with('/') {
+            return None;
+        }
+        let mut target = self;
+        for token in pointer[1..].split('/') {
+            let token = unescape_token(token)?;
+            target = match target {
+                Value::Object(object) => object.get_mut(&token)?,
+                Value::Array(array) => array.get_mut(parse_array_index(&token)?)?,
+                _ => return None,
+            };
+        }
+        Some(target)
+    }
+}
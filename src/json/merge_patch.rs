@@ -0,0 +1,64 @@
+use crate::json::{Object, Value};
+use alloc::vec::Vec;
+
+/// Apply a JSON Merge Patch as defined in [RFC 7396].
+///
+/// [RFC 7396]: https://tools.ietf.org/html/rfc7396
+///
+/// If the patch is an object, each of its entries is merged into the
+/// corresponding entry of `target`, with `null` meaning that the entry is
+/// removed. Any other patch replaces `target` entirely.
+///
+/// ```rust
+/// use miniserde::json::{self, Value};
+///
+/// fn main() -> miniserde::Result<()> {
+///     let mut doc: Value = json::from_str(r#" {"a": "b", "c": {"d": "e", "f": "g"}} "#)?;
+///     let patch: Value = json::from_str(r#" {"a": "z", "c": {"f": null}} "#)?;
+///
+///     json::merge_patch(&mut doc, &patch);
+///     assert_eq!(json::to_string(&doc), r#"{"a":"z","c":{"d":"e"}}"#);
+///
+///     Ok(())
+/// }
+/// ```
+pub fn merge_patch(target: &mut Value, patch: &Value) {
+    let mut stack = Vec::new();
+    stack.push((target, patch));
+
+    while let Some((target, patch)) = stack.pop() {
+        let Value::Object(patch) = patch else {
+            target.clone_from(patch);
+            continue;
+        };
+
+        if !target.is_object() {
+            *target = Value::Object(Object::new());
+        }
+        let Value::Object(object) = target else {
+            unreachable!();
+        };
+
+        for (key, value) in patch {
+            match value {
+                Value::Null => {
+                    object.remove(key);
+                }
+                Value::Object(_) => {
+                    object.entry(key.clone()).or_insert(Value::Null);
+                }
+                _ => {
+                    object.insert(key.clone(), value.clone());
+                }
+            }
+        }
+
+        // Nested objects in the patch are merged into the corresponding
+        // entries after this level is done, instead of recursively.
+        for (key, entry) in object.iter_mut() {
+            if let Some(value @ Value::Object(_)) = patch.get(key) {
+                stack.push((entry, value));
+            }
+        }
+    }
+}
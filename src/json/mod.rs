@@ -4,14 +4,43 @@
 //! serializing and deserializing JSON.
 
 mod ser;
-pub use self::ser::to_string;
+pub use self::ser::{to_string, to_string_with, try_to_string};
 
 mod de;
-pub use self::de::from_str;
+pub use self::de::{from_str, from_str_with};
+
+mod canonical;
+pub use self::canonical::to_canonical_string;
+
+mod options;
+pub use self::options::{BytesFormat, DuplicateKeys, NonFinite, Options, SerializeOptions};
+
+mod stream;
+pub use self::stream::StreamDeserializer;
+
+mod parser;
+pub use self::parser::{Parser, Status};
+
+mod to_value;
+pub use self::to_value::to_value;
+
+mod from_value;
+pub use self::from_value::{from_value, from_value_ref};
+
+pub mod patch;
+
+mod merge_patch;
+pub use self::merge_patch::merge_patch;
+
+mod diff;
+pub use self::diff::{diff, diff_with, ArrayDiff};
 
 mod value;
 pub use self::value::Value;
 
+mod raw;
+pub use self::raw::RawValue;
+
 mod number;
 pub use self::number::Number;
 
@@ -21,4 +50,15 @@ pub use self::array::Array;
 mod object;
 pub use self::object::Object;
 
+mod clone;
+
+mod cmp;
+
+mod debug;
+
+mod from;
+
+mod index;
+pub use self::index::Index;
+
 mod drop;
@@ -1,9 +1,17 @@
 use crate::de::{Deserialize, Visitor};
 use crate::error::Result;
+use crate::json::cmp;
 use crate::ser::{Fragment, Serialize};
+use core::cmp::Ordering;
 use core::fmt::{self, Display};
+use core::hash::{Hash, Hasher};
 
 /// A JSON number represented by some Rust primitive.
+///
+/// Numbers compare by their mathematical value regardless of representation,
+/// so `Number::U64(1)`, `Number::I64(1)` and `Number::F64(1.0)` are all equal.
+/// To give a total order, NaN is equal to itself and greater than every other
+/// number, and negative zero is equal to zero.
 #[derive(Clone, Debug)]
 pub enum Number {
     U64(u64),
@@ -11,6 +19,75 @@ pub enum Number {
     F64(f64),
 }
 
+impl Number {
+    /// Returns the number as a `u64` if it is an integer representable as one.
+    pub fn as_u64(&self) -> Option<u64> {
+        match *self {
+            Number::U64(n) => Some(n),
+            Number::I64(n) => u64::try_from(n).ok(),
+            Number::F64(_) => None,
+        }
+    }
+
+    /// Returns the number as an `i64` if it is an integer representable as
+    /// one.
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            Number::U64(n) => i64::try_from(n).ok(),
+            Number::I64(n) => Some(n),
+            Number::F64(_) => None,
+        }
+    }
+
+    /// Returns the number converted to `f64`, which may lose precision for
+    /// large integers.
+    pub fn as_f64(&self) -> f64 {
+        match *self {
+            Number::U64(n) => n as f64,
+            Number::I64(n) => n as f64,
+            Number::F64(n) => n,
+        }
+    }
+
+    pub fn is_u64(&self) -> bool {
+        self.as_u64().is_some()
+    }
+
+    pub fn is_i64(&self) -> bool {
+        self.as_i64().is_some()
+    }
+
+    pub fn is_f64(&self) -> bool {
+        matches!(self, Number::F64(_))
+    }
+}
+
+impl PartialEq for Number {
+    fn eq(&self, other: &Self) -> bool {
+        cmp::cmp_numbers(self, other) == Ordering::Equal
+    }
+}
+
+impl Eq for Number {}
+
+impl PartialOrd for Number {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Number {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp::cmp_numbers(self, other)
+    }
+}
+
+impl Hash for Number {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        cmp::hash_number(self, state);
+    }
+}
+
 impl Display for Number {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -31,11 +108,11 @@ impl Serialize for Number {
     }
 }
 
-impl Deserialize for Number {
-    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor {
+impl<'de> Deserialize<'de> for Number {
+    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor<'de> {
         make_place!(Place);
 
-        impl Visitor for Place<Number> {
+        impl<'de> Visitor<'de> for Place<Number> {
             fn negative(&mut self, n: i64) -> Result<()> {
                 self.out = Some(Number::I64(n));
                 Ok(())
@@ -1,12 +1,14 @@
 use crate::de::{Deserialize, Map, Visitor};
 use crate::error::Result;
-use crate::json::{drop, Value};
+use crate::json::{cmp, debug, drop, Value};
 use crate::ser::{self, Fragment, Serialize};
 use alloc::borrow::{Cow, ToOwned};
 use alloc::boxed::Box;
 use alloc::collections::{btree_map, BTreeMap};
 use alloc::string::String;
+use core::cmp::Ordering;
 use core::fmt::{self, Debug};
+use core::hash::{Hash, Hasher};
 use core::mem::{self, ManuallyDrop};
 use core::ops::{Deref, DerefMut};
 use core::ptr;
@@ -91,10 +93,41 @@ impl FromIterator<(String, Value)> for Object {
     }
 }
 
+impl From<BTreeMap<String, Value>> for Object {
+    fn from(inner: BTreeMap<String, Value>) -> Self {
+        Object { inner }
+    }
+}
+
+impl PartialEq for Object {
+    fn eq(&self, other: &Self) -> bool {
+        cmp::cmp_objects(self, other) == Ordering::Equal
+    }
+}
+
+impl Eq for Object {}
+
+impl PartialOrd for Object {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Object {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp::cmp_objects(self, other)
+    }
+}
+
+impl Hash for Object {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        cmp::hash_object(self, state);
+    }
+}
+
 impl Debug for Object {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        formatter.write_str("Object ")?;
-        formatter.debug_map().entries(self).finish()
+        debug::debug_object(self, formatter)
     }
 }
 
@@ -107,18 +140,22 @@ impl Serialize for Object {
                 let (k, v) = self.0.next()?;
                 Some((Cow::Borrowed(k), v as &dyn Serialize))
             }
+
+            fn size_hint(&self) -> Option<usize> {
+                Some(self.0.len())
+            }
         }
 
         Fragment::Map(Box::new(ObjectIter(self.iter())))
     }
 }
 
-impl Deserialize for Object {
-    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor {
+impl<'de> Deserialize<'de> for Object {
+    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor<'de> {
         make_place!(Place);
 
-        impl Visitor for Place<Object> {
-            fn map(&mut self) -> Result<Box<dyn Map + '_>> {
+        impl<'de> Visitor<'de> for Place<Object> {
+            fn map(&mut self) -> Result<Box<dyn Map<'de> + '_>> {
                 Ok(Box::new(ObjectBuilder {
                     out: &mut self.out,
                     object: Object::new(),
@@ -143,8 +180,8 @@ impl Deserialize for Object {
             }
         }
 
-        impl<'a> Map for ObjectBuilder<'a> {
-            fn key(&mut self, k: &str) -> Result<&mut dyn Visitor> {
+        impl<'a, 'de> Map<'de> for ObjectBuilder<'a> {
+            fn key(&mut self, k: &str) -> Result<&mut dyn Visitor<'de>> {
                 self.shift();
                 self.key = Some(k.to_owned());
                 Ok(Deserialize::begin(&mut self.value))
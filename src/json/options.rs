@@ -0,0 +1,284 @@
+/// Settings for deserializing JSON with [`from_str_with`][crate::json::from_str_with].
+///
+/// The defaults are the same as [`from_str`][crate::json::from_str]: there are
+/// no limits. When deserializing untrusted input, the limits bound how much
+/// memory an attacker can make the deserializer use, and the input is
+/// rejected as soon as it exceeds one of them.
+///
+/// ```rust
+/// use miniserde::json::{self, Options, Value};
+///
+/// let options = Options::new().max_depth(2).max_elements(3);
+///
+/// let value: Value = json::from_str_with("[[1, 2, 3]]", &options).unwrap();
+/// assert_eq!(value[0][2].as_u64(), Some(3));
+///
+/// assert!(json::from_str_with::<Value>("[[[]]]", &options).is_err());
+/// assert!(json::from_str_with::<Value>("[1, 2, 3, 4]", &options).is_err());
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Options {
+    pub(crate) duplicate_keys: DuplicateKeys,
+    pub(crate) max_depth: usize,
+    pub(crate) max_input_len: usize,
+    pub(crate) max_string_len: usize,
+    pub(crate) max_elements: usize,
+    pub(crate) max_values: usize,
+    pub(crate) relaxed: bool,
+}
+
+impl Options {
+    /// The default options, which have no limits.
+    pub const fn new() -> Self {
+        Options {
+            duplicate_keys: DuplicateKeys::Last,
+            max_depth: usize::MAX,
+            max_input_len: usize::MAX,
+            max_string_len: usize::MAX,
+            max_elements: usize::MAX,
+            max_values: usize::MAX,
+            relaxed: false,
+        }
+    }
+
+    /// What to do when a key appears more than once in the same object. The
+    /// default is [`DuplicateKeys::Last`].
+    pub const fn duplicate_keys(mut self, policy: DuplicateKeys) -> Self {
+        self.duplicate_keys = policy;
+        self
+    }
+
+    /// Maximum number of arrays and objects nested inside one another. A
+    /// depth of 0 allows only scalar values.
+    pub const fn max_depth(mut self, max: usize) -> Self {
+        self.max_depth = max;
+        self
+    }
+
+    /// Maximum length of the input in bytes.
+    pub const fn max_input_len(mut self, max: usize) -> Self {
+        self.max_input_len = max;
+        self
+    }
+
+    /// Maximum length in bytes of any string or object key, after escape
+    /// sequences have been decoded.
+    pub const fn max_string_len(mut self, max: usize) -> Self {
+        self.max_string_len = max;
+        self
+    }
+
+    /// Maximum number of elements in any one array, or entries in any one
+    /// object.
+    pub const fn max_elements(mut self, max: usize) -> Self {
+        self.max_elements = max;
+        self
+    }
+
+    /// Maximum total number of values in the input, counting every array,
+    /// object and scalar at any depth.
+    pub const fn max_values(mut self, max: usize) -> Self {
+        self.max_values = max;
+        self
+    }
+
+    /// Accept some common extensions to JSON, in the style of JSON5:
+    ///
+    /// - `// line` and `/* block */` comments wherever whitespace is allowed,
+    /// - a trailing comma after the last element of an array or object,
+    /// - strings in single quotes, in which `\'` is an escape sequence,
+    /// - object keys which are identifiers, like `{key: 1}`,
+    /// - hexadecimal integers, like `0x1F` and `-0x1f`,
+    /// - the number literals `NaN`, `Infinity` and `-Infinity`, and the same
+    ///   in strings like `"NaN"` wherever a string is not expected.
+    ///
+    /// The default is strict [RFC 8259] JSON.
+    ///
+    /// [RFC 8259]: https://www.rfc-editor.org/rfc/rfc8259
+    ///
+    /// ```rust
+    /// use miniserde::json::{self, Options, Value};
+    ///
+    /// let j = "{
+    ///     // Comment.
+    ///     name: 'config',
+    ///     mask: 0xFF,
+    ///     list: [1, 2, 3,],
+    /// }";
+    ///
+    /// let options = Options::new().relaxed(true);
+    /// let value: Value = json::from_str_with(j, &options).unwrap();
+    /// assert_eq!(value["mask"].as_u64(), Some(255));
+    ///
+    /// assert!(json::from_str::<Value>(j).is_err());
+    /// ```
+    pub const fn relaxed(mut self, relaxed: bool) -> Self {
+        self.relaxed = relaxed;
+        self
+    }
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options::new()
+    }
+}
+
+/// Policy for an object in which the same key appears more than once.
+///
+/// This is applied by [`from_str_with`][crate::json::from_str_with], the
+/// same way to every type that deserializes from a JSON object, including
+/// [`Object`][crate::json::Object], maps from the standard library and structs
+/// with a derived `Deserialize` impl. Everything else that deserializes maps,
+/// such as [`Parser`][crate::json::Parser], [`from_value`][crate::json::from_value]
+/// and the other formats in this crate, keeps the last value as
+/// [`DuplicateKeys::Last`] does.
+///
+/// ```rust
+/// use miniserde::json::{self, DuplicateKeys, Options, Value};
+///
+/// let j = r#"{"role": "user", "role": "admin"}"#;
+///
+/// let options = Options::new().duplicate_keys(DuplicateKeys::First);
+/// let value: Value = json::from_str_with(j, &options).unwrap();
+/// assert_eq!(value["role"].as_str(), Some("user"));
+///
+/// let options = Options::new().duplicate_keys(DuplicateKeys::Error);
+/// assert!(json::from_str_with::<Value>(j, &options).is_err());
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DuplicateKeys {
+    /// Fail to deserialize.
+    Error,
+    /// Keep the value of the first occurrence and ignore the rest.
+    First,
+    /// Keep the value of the last occurrence, overwriting the earlier ones.
+    Last,
+}
+
+/// Settings for serializing JSON with [`to_string_with`][crate::json::to_string_with].
+///
+/// The defaults are the same as [`to_string`][crate::json::to_string].
+#[derive(Copy, Clone, Debug)]
+pub struct SerializeOptions {
+    pub(crate) non_finite: NonFinite,
+    pub(crate) bytes: BytesFormat,
+    pub(crate) ascii_only: bool,
+    pub(crate) html_safe: bool,
+}
+
+impl SerializeOptions {
+    /// The default options, which produce the same output as `to_string`.
+    pub const fn new() -> Self {
+        SerializeOptions {
+            non_finite: NonFinite::Null,
+            bytes: BytesFormat::Base64,
+            ascii_only: false,
+            html_safe: false,
+        }
+    }
+
+    /// How to write floats which are `NaN` or infinite, which JSON has no way
+    /// to represent. The default is [`NonFinite::Null`].
+    pub const fn non_finite(mut self, policy: NonFinite) -> Self {
+        self.non_finite = policy;
+        self
+    }
+
+    /// How to write byte strings, such as [`ByteBuf`][crate::ByteBuf], which
+    /// JSON has no type for. The default is [`BytesFormat::Base64`].
+    pub const fn bytes(mut self, format: BytesFormat) -> Self {
+        self.bytes = format;
+        self
+    }
+
+    /// Escape every non-ASCII character in strings and object keys as
+    /// `\uXXXX`, using a surrogate pair for characters outside the Basic
+    /// Multilingual Plane, so that the output is pure ASCII.
+    ///
+    /// ```rust
+    /// use miniserde::json::{self, SerializeOptions};
+    ///
+    /// let options = SerializeOptions::new().ascii_only(true);
+    /// let j = json::to_string_with("é😀", &options).unwrap();
+    /// assert_eq!(j, r#""\u00e9\ud83d\ude00""#);
+    /// ```
+    pub const fn ascii_only(mut self, ascii_only: bool) -> Self {
+        self.ascii_only = ascii_only;
+        self
+    }
+
+    /// Escape `<`, `>` and `&`, and the line terminators U+2028 and U+2029, in
+    /// strings and object keys. The output can then be embedded in HTML, such
+    /// as inside a `<script>` tag, and is also valid JavaScript.
+    ///
+    /// ```rust
+    /// use miniserde::json::{self, SerializeOptions};
+    ///
+    /// let options = SerializeOptions::new().html_safe(true);
+    /// let j = json::to_string_with("</script>", &options).unwrap();
+    /// assert_eq!(j, r#""\u003c/script\u003e""#);
+    /// ```
+    pub const fn html_safe(mut self, html_safe: bool) -> Self {
+        self.html_safe = html_safe;
+        self
+    }
+}
+
+impl Default for SerializeOptions {
+    fn default() -> Self {
+        SerializeOptions::new()
+    }
+}
+
+/// Policy for serializing a float which is `NaN`, `Infinity` or `-Infinity`.
+///
+/// ```rust
+/// use miniserde::json::{self, NonFinite, SerializeOptions};
+///
+/// let values = [f64::NAN, f64::NEG_INFINITY];
+///
+/// let options = SerializeOptions::new().non_finite(NonFinite::String);
+/// let j = json::to_string_with(&values, &options).unwrap();
+/// assert_eq!(j, r#"["NaN","-Infinity"]"#);
+///
+/// let options = SerializeOptions::new().non_finite(NonFinite::Error);
+/// assert!(json::to_string_with(&values, &options).is_err());
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum NonFinite {
+    /// Write `null`, losing the value.
+    Null,
+    /// Fail to serialize.
+    Error,
+    /// Write the JSON5 literals `NaN`, `Infinity` and `-Infinity`. These are
+    /// not valid JSON, but are accepted by
+    /// [`Options::relaxed`][Options::relaxed].
+    Literal,
+    /// Write the strings `"NaN"`, `"Infinity"` and `"-Infinity"`. These are
+    /// valid JSON, and are accepted as floats by
+    /// [`Options::relaxed`][Options::relaxed].
+    String,
+}
+
+/// Representation of a byte string in JSON.
+///
+/// Either one can be deserialized into a [`ByteBuf`][crate::ByteBuf].
+///
+/// ```rust
+/// use miniserde::json::{self, BytesFormat, SerializeOptions};
+/// use miniserde::Bytes;
+///
+/// let data = Bytes::new(b"hi!");
+/// assert_eq!(json::to_string(data), r#""aGkh""#);
+///
+/// let options = SerializeOptions::new().bytes(BytesFormat::Array);
+/// assert_eq!(json::to_string_with(data, &options).unwrap(), "[104,105,33]");
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BytesFormat {
+    /// A string of standard base64 with padding, as in RFC 4648.
+    Base64,
+    /// An array of numbers from 0 to 255.
+    Array,
+}
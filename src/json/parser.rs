@@ -0,0 +1,346 @@
+use crate::de::{Map, Seq, Visitor};
+use crate::error::{Error, Result};
+use crate::json::de::{visit_key, visit_token};
+use crate::ptr::NonuniqueBox;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use core::mem;
+use core::ptr::NonNull;
+
+/// Push parser that deserializes a JSON value from input arriving in chunks.
+///
+/// Each chunk is parsed as soon as it is passed to [`feed`][Parser::feed], so
+/// the whole document never needs to be buffered; only a token which is split
+/// across chunks is held on to until the rest of it arrives. Chunks may be
+/// split anywhere, including in the middle of a string, a number or a
+/// multi-byte character.
+///
+/// The parser takes no [`Options`][crate::json::Options]. It accepts only
+/// strict JSON, places no limits on the input, and gives a key which appears
+/// more than once in an object to the map again each time, which the maps and
+/// structs of this crate treat like [`DuplicateKeys::Last`]. Input which is
+/// not trusted needs to be bounded by the caller, for example by counting the
+/// bytes fed in.
+///
+/// [`DuplicateKeys::Last`]: crate::json::DuplicateKeys::Last
+///
+/// ```rust
+/// use miniserde::de::Deserialize;
+/// use miniserde::json::{Parser, Status};
+///
+/// #[derive(miniserde::Deserialize, Debug)]
+/// struct Example {
+///     code: u32,
+///     message: String,
+/// }
+///
+/// fn main() -> miniserde::Result<()> {
+///     let mut out = None;
+///     let mut parser = Parser::new(Example::begin(&mut out));
+///
+///     assert_eq!(parser.feed(b"{\"code\": 2")?, Status::Incomplete);
+///     assert_eq!(parser.feed(b"00, \"message\": \"remini")?, Status::Incomplete);
+///     assert_eq!(parser.feed(b"scent of Serde\"}")?, Status::Complete);
+///     parser.finish()?;
+///
+///     let example = out.unwrap();
+///     assert_eq!(example.code, 200);
+///     assert_eq!(example.message, "reminiscent of Serde");
+///
+///     Ok(())
+/// }
+/// ```
+pub struct Parser<'a, 'de> {
+    // Receives the next value when the state is Value. Input is never lent
+    // out to the visitors through `borrowed_string`, as it does not outlive
+    // the chunk it arrived in.
+    visitor: NonNull<dyn Visitor<'de>>,
+    stack: Vec<Layer<'a, 'de>>,
+    state: State,
+    // Beginning of a token whose end has not arrived yet.
+    partial: Option<Partial>,
+    token: Vec<u8>,
+    scratch: Vec<u8>,
+    marker: PhantomData<&'a mut ()>,
+}
+
+/// Progress of a [`Parser`] after consuming a chunk of input.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// More input is needed to complete the value.
+    Incomplete,
+    /// A complete value has been parsed. Only whitespace may follow.
+    Complete,
+}
+
+enum Layer<'a, 'de> {
+    Seq(NonuniqueBox<dyn Seq<'de> + 'a>),
+    Map(NonuniqueBox<dyn Map<'de> + 'a>),
+}
+
+#[derive(Copy, Clone, PartialEq)]
+enum State {
+    // Expecting a value.
+    Value,
+    // After '[', expecting a value or ']'.
+    SeqFirst,
+    // After '{', expecting a key or '}'.
+    MapFirst,
+    // After ',' in an object, expecting a key.
+    Key,
+    // After a key, expecting ':'.
+    Colon,
+    // After a value inside an array or object, expecting ',' or a close.
+    AfterValue,
+    // The top-level value is complete.
+    Done,
+    // A previous error left the visitors in an unknown state.
+    Failed,
+}
+
+#[derive(Copy, Clone)]
+enum Partial {
+    String { escape: bool },
+    Scalar,
+}
+
+impl<'a, 'de> Drop for Parser<'a, 'de> {
+    fn drop(&mut self) {
+        // Drop layers in reverse order.
+        while !self.stack.is_empty() {
+            self.stack.pop();
+        }
+    }
+}
+
+impl<'a, 'de: 'a> Parser<'a, 'de> {
+    /// Begin parsing a value into the given visitor, which is typically
+    /// obtained from [`Deserialize::begin`][crate::de::Deserialize::begin].
+    pub fn new(visitor: &'a mut dyn Visitor<'de>) -> Self {
+        let visitor = NonNull::from(visitor);
+        // invariant: `visitor` outlives the parser
+        let visitor = unsafe { extend_lifetime!(visitor as NonNull<dyn Visitor<'de>>) };
+        Parser {
+            visitor,
+            stack: Vec::new(),
+            state: State::Value,
+            partial: None,
+            token: Vec::new(),
+            scratch: Vec::new(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Parse the next chunk of input.
+    ///
+    /// Once an error has been returned, every later call fails too.
+    pub fn feed(&mut self, chunk: &[u8]) -> Result<Status> {
+        if self.state == State::Failed {
+            return Err(Error);
+        }
+        let result = self.feed_impl(chunk);
+        if result.is_err() {
+            self.state = State::Failed;
+        }
+        result
+    }
+
+    /// Signal the end of the input.
+    ///
+    /// This fails if the value is not complete. It is necessary to call this
+    /// after a top-level number, because until the end of the input it is not
+    /// known whether more digits are coming.
+    pub fn finish(mut self) -> Result<()> {
+        if let Some(Partial::Scalar) = self.partial {
+            self.partial = None;
+            let token = mem::take(&mut self.token);
+            if self.state == State::Value && self.stack.is_empty() {
+                self.token_done(&token)?;
+            }
+        }
+        match self.state {
+            State::Done => Ok(()),
+            _ => Err(Error),
+        }
+    }
+
+    fn feed_impl(&mut self, chunk: &[u8]) -> Result<Status> {
+        let mut pos = 0;
+
+        if let Some(partial) = self.partial {
+            match token_end(partial, chunk, 0) {
+                Ok(end) => {
+                    self.partial = None;
+                    let mut token = mem::take(&mut self.token);
+                    token.extend_from_slice(&chunk[..end]);
+                    let result = self.token_done(&token);
+                    token.clear();
+                    self.token = token;
+                    result?;
+                    pos = end;
+                }
+                Err(partial) => {
+                    self.partial = Some(partial);
+                    self.token.extend_from_slice(chunk);
+                    return Ok(Status::Incomplete);
+                }
+            }
+        }
+
+        while let Some(&byte) = chunk.get(pos) {
+            if let b' ' | b'\n' | b'\t' | b'\r' = byte {
+                pos += 1;
+                continue;
+            }
+
+            match (self.state, byte) {
+                (State::Value | State::SeqFirst, b'[') => {
+                    self.enter_value()?;
+                    let seq = unsafe { &mut *self.visitor.as_ptr() }.seq()?;
+                    self.stack.push(Layer::Seq(NonuniqueBox::from(seq)));
+                    self.state = State::SeqFirst;
+                    pos += 1;
+                }
+                (State::Value | State::SeqFirst, b'{') => {
+                    self.enter_value()?;
+                    let map = unsafe { &mut *self.visitor.as_ptr() }.map()?;
+                    self.stack.push(Layer::Map(NonuniqueBox::from(map)));
+                    self.state = State::MapFirst;
+                    pos += 1;
+                }
+                (State::SeqFirst | State::AfterValue, b']')
+                | (State::MapFirst | State::AfterValue, b'}') => {
+                    match self.stack.last_mut() {
+                        Some(Layer::Seq(seq)) if byte == b']' => seq.finish()?,
+                        Some(Layer::Map(map)) if byte == b'}' => map.finish()?,
+                        _ => return Err(Error),
+                    }
+                    self.stack.pop();
+                    self.value_done();
+                    pos += 1;
+                }
+                (State::AfterValue, b',') => {
+                    match self.stack.last_mut() {
+                        Some(Layer::Seq(seq)) => {
+                            let element = NonNull::from(seq.element()?);
+                            self.visitor =
+                                unsafe { extend_lifetime!(element as NonNull<dyn Visitor<'de>>) };
+                            self.state = State::Value;
+                        }
+                        Some(Layer::Map(_)) => self.state = State::Key,
+                        None => return Err(Error),
+                    }
+                    pos += 1;
+                }
+                (State::Colon, b':') => {
+                    self.state = State::Value;
+                    pos += 1;
+                }
+                (State::Value | State::SeqFirst | State::MapFirst | State::Key, _) => {
+                    if matches!(self.state, State::MapFirst | State::Key) && byte != b'"' {
+                        return Err(Error);
+                    }
+                    self.enter_value()?;
+                    let partial = if byte == b'"' {
+                        Partial::String { escape: false }
+                    } else {
+                        Partial::Scalar
+                    };
+                    match token_end(partial, chunk, pos + 1) {
+                        Ok(end) => {
+                            self.token_done(&chunk[pos..end])?;
+                            pos = end;
+                        }
+                        Err(partial) => {
+                            self.partial = Some(partial);
+                            self.token.extend_from_slice(&chunk[pos..]);
+                            return Ok(Status::Incomplete);
+                        }
+                    }
+                }
+                _ => return Err(Error),
+            }
+        }
+
+        Ok(match self.state {
+            State::Done => Status::Complete,
+            _ => Status::Incomplete,
+        })
+    }
+
+    // Obtains the visitor for the first element of an array, which is not
+    // requested until it is known that the array is not empty.
+    fn enter_value(&mut self) -> Result<()> {
+        if self.state == State::SeqFirst {
+            let Some(Layer::Seq(seq)) = self.stack.last_mut() else {
+                return Err(Error);
+            };
+            let element = NonNull::from(seq.element()?);
+            self.visitor = unsafe { extend_lifetime!(element as NonNull<dyn Visitor<'de>>) };
+            self.state = State::Value;
+        }
+        // The text of a value is not held on to across chunks, so there is
+        // none to give to a RawValue.
+        if self.state == State::Value && unsafe { &mut *self.visitor.as_ptr() }.wants_raw_json() {
+            return Err(Error);
+        }
+        Ok(())
+    }
+
+    fn token_done(&mut self, token: &[u8]) -> Result<()> {
+        match self.state {
+            State::MapFirst | State::Key => {
+                let Some(Layer::Map(map)) = self.stack.last_mut() else {
+                    return Err(Error);
+                };
+                let entry = NonNull::from(visit_key(token, &mut self.scratch, &mut **map)?);
+                self.visitor = unsafe { extend_lifetime!(entry as NonNull<dyn Visitor<'de>>) };
+                self.state = State::Colon;
+            }
+            _ => {
+                let visitor = unsafe { &mut *self.visitor.as_ptr() };
+                visit_token(token, &mut self.scratch, visitor)?;
+                self.value_done();
+            }
+        }
+        Ok(())
+    }
+
+    fn value_done(&mut self) {
+        self.state = if self.stack.is_empty() {
+            State::Done
+        } else {
+            State::AfterValue
+        };
+    }
+}
+
+// Finds the end of a token which began before `start`. On reaching the end of
+// the chunk first, returns the state to continue from in the next chunk.
+fn token_end(partial: Partial, chunk: &[u8], start: usize) -> core::result::Result<usize, Partial> {
+    match partial {
+        Partial::String { mut escape } => {
+            for (i, &byte) in chunk.iter().enumerate().skip(start) {
+                if escape {
+                    escape = false;
+                } else if byte == b'\\' {
+                    escape = true;
+                } else if byte == b'"' {
+                    return Ok(i + 1);
+                }
+            }
+            Err(Partial::String { escape })
+        }
+        Partial::Scalar => {
+            let rest = &chunk[start.min(chunk.len())..];
+            match rest.iter().position(|byte| !is_scalar_byte(*byte)) {
+                Some(i) => Ok(start + i),
+                None => Err(Partial::Scalar),
+            }
+        }
+    }
+}
+
+fn is_scalar_byte(byte: u8) -> bool {
+    matches!(byte, b'0'..=b'9' | b'a'..=b'z' | b'A'..=b'Z' | b'+' | b'-' | b'.')
+}
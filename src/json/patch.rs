@@ -0,0 +1,257 @@
+//! JSON Patch as defined in [RFC 6902].
+//!
+//! [RFC 6902]: https://tools.ietf.org/html/rfc6902
+
+use crate::error::{Error, Result};
+use crate::json::index::{parse_array_index, unescape_token};
+use crate::json::Value;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::mem;
+
+/// Apply a JSON Patch document to a value.
+///
+/// The patch must be an array of operation objects, each of which is one of
+/// `add`, `remove`, `replace`, `move`, `copy` or `test`. Operations are
+/// applied in order. If any operation fails, the ones already applied are
+/// rolled back so that `doc` is left exactly as it was, and an error is
+/// returned.
+///
+/// ```rust
+/// use miniserde::json::{self, Value};
+///
+/// fn main() -> miniserde::Result<()> {
+///     let mut doc: Value = json::from_str(r#" {"a": [1, 2], "b": "c"} "#)?;
+///     let patch: Value = json::from_str(r#" [
+///         {"op": "add", "path": "/a/-", "value": 3},
+///         {"op": "move", "from": "/b", "path": "/d"},
+///         {"op": "test", "path": "/d", "value": "c"}
+///     ] "#)?;
+///
+///     json::patch::apply(&mut doc, &patch)?;
+///     assert_eq!(json::to_string(&doc), r#"{"a":[1,2,3],"d":"c"}"#);
+///
+///     // The failing test operation undoes the removal before it.
+///     let patch: Value = json::from_str(r#" [
+///         {"op": "remove", "path": "/a"},
+///         {"op": "test", "path": "/d", "value": "x"}
+///     ] "#)?;
+///     json::patch::apply(&mut doc, &patch).unwrap_err();
+///     assert_eq!(json::to_string(&doc), r#"{"a":[1,2,3],"d":"c"}"#);
+///
+///     Ok(())
+/// }
+/// ```
+pub fn apply(doc: &mut Value, patch: &Value) -> Result<()> {
+    let Value::Array(operations) = patch else {
+        return Err(Error);
+    };
+
+    let mut undo = Vec::new();
+    for operation in operations {
+        if let Err(error) = apply_operation(doc, operation, &mut undo) {
+            rollback(doc, undo);
+            return Err(error);
+        }
+    }
+    Ok(())
+}
+
+// How to revert one primitive change to the document.
+enum Undo {
+    // Put back the previous whole document.
+    Root(Value),
+    // Remove the value that was added at this location.
+    Remove(Vec<String>),
+    // Put back the previous value of an object entry that was overwritten.
+    Swap(Vec<String>, Value),
+    // Put back a value that was removed from this location.
+    Insert(Vec<String>, Value),
+    // Put back the value that was removed by the previously reverted change,
+    // which is how a move is undone without cloning the moved value.
+    Restore(Vec<String>),
+}
+
+fn apply_operation(doc: &mut Value, operation: &Value, undo: &mut Vec<Undo>) -> Result<()> {
+    let member = |name| operation.get(name).ok_or(Error);
+    let op = member("op")?.as_str().ok_or(Error)?;
+    let path = parse_pointer(member("path")?.as_str().ok_or(Error)?)?;
+
+    match op {
+        "add" => {
+            let value = member("value")?.clone();
+            undo.push(add(doc, &path, value).map_err(|_| Error)?);
+        }
+        "remove" => {
+            let (removed, at) = remove(doc, &path)?;
+            undo.push(Undo::Insert(at, removed));
+        }
+        "replace" => {
+            let value = member("value")?.clone();
+            if path.is_empty() {
+                undo.push(Undo::Root(mem::replace(doc, value)));
+            } else {
+                let (removed, at) = remove(doc, &path)?;
+                undo.push(Undo::Insert(at, removed));
+                undo.push(add(doc, &path, value).map_err(|_| Error)?);
+            }
+        }
+        "move" => {
+            let from = parse_pointer(member("from")?.as_str().ok_or(Error)?)?;
+            if from == path {
+                lookup(doc, &from)?;
+                return Ok(());
+            }
+            // A location cannot be moved into one of its own children.
+            if path.starts_with(&from) {
+                return Err(Error);
+            }
+            let (value, at) = remove(doc, &from)?;
+            match add(doc, &path, value) {
+                Ok(added) => {
+                    undo.push(Undo::Restore(at));
+                    undo.push(added);
+                }
+                Err(value) => {
+                    undo.push(Undo::Insert(at, value));
+                    return Err(Error);
+                }
+            }
+        }
+        "copy" => {
+            let from = parse_pointer(member("from")?.as_str().ok_or(Error)?)?;
+            let value = lookup(doc, &from)?.clone();
+            undo.push(add(doc, &path, value).map_err(|_| Error)?);
+        }
+        "test" => {
+            if *lookup(doc, &path)? != *member("value")? {
+                return Err(Error);
+            }
+        }
+        _ => return Err(Error),
+    }
+
+    Ok(())
+}
+
+fn rollback(doc: &mut Value, undo: Vec<Undo>) {
+    // None of these can fail, because each one exactly reverses a change that
+    // was made to the document in the state it is being restored to.
+    let mut carry = None;
+    for change in undo.into_iter().rev() {
+        carry = match change {
+            Undo::Root(previous) => Some(mem::replace(doc, previous)),
+            Undo::Remove(path) => remove(doc, &path).ok().map(|(removed, _)| removed),
+            Undo::Swap(path, previous) => match add(doc, &path, previous) {
+                Ok(Undo::Swap(_, current)) => Some(current),
+                _ => None,
+            },
+            Undo::Insert(path, removed) => {
+                let _ = add(doc, &path, removed);
+                None
+            }
+            Undo::Restore(path) => {
+                if let Some(moved) = carry {
+                    let _ = add(doc, &path, moved);
+                }
+                None
+            }
+        };
+    }
+}
+
+pub(crate) fn parse_pointer(pointer: &str) -> Result<Vec<String>> {
+    if pointer.is_empty() {
+        return Ok(Vec::new());
+    }
+    if !pointer.starts_with('/') {
+        return Err(Error);
+    }
+    pointer[1..]
+        .split('/')
+        .map(|token| unescape_token(token).ok_or(Error))
+        .collect()
+}
+
+fn lookup<'a>(doc: &'a Value, path: &[String]) -> Result<&'a Value> {
+    let mut target = doc;
+    for token in path {
+        target = match target {
+            Value::Object(object) => object.get(token),
+            Value::Array(array) => parse_array_index(token).and_then(|index| array.get(index)),
+            _ => None,
+        }
+        .ok_or(Error)?;
+    }
+    Ok(target)
+}
+
+fn lookup_mut<'a>(doc: &'a mut Value, path: &[String]) -> Result<&'a mut Value> {
+    let mut target = doc;
+    for token in path {
+        target = match target {
+            Value::Object(object) => object.get_mut(token),
+            Value::Array(array) => {
+                parse_array_index(token).and_then(move |index| array.get_mut(index))
+            }
+            _ => None,
+        }
+        .ok_or(Error)?;
+    }
+    Ok(target)
+}
+
+// Adds a value at the given location, returning how to undo it. On failure the
+// value is handed back to the caller.
+fn add(doc: &mut Value, path: &[String], value: Value) -> core::result::Result<Undo, Value> {
+    let Some((last, parent)) = path.split_last() else {
+        return Ok(Undo::Root(mem::replace(doc, value)));
+    };
+    let Ok(parent_value) = lookup_mut(doc, parent) else {
+        return Err(value);
+    };
+
+    match parent_value {
+        Value::Object(object) => Ok(match object.insert(last.clone(), value) {
+            Some(previous) => Undo::Swap(path.to_vec(), previous),
+            None => Undo::Remove(path.to_vec()),
+        }),
+        Value::Array(array) => {
+            let index = if last == "-" {
+                array.len()
+            } else {
+                match parse_array_index(last) {
+                    Some(index) if index <= array.len() => index,
+                    _ => return Err(value),
+                }
+            };
+            array.insert(index, value);
+            let mut at = parent.to_vec();
+            at.push(index.to_string());
+            Ok(Undo::Remove(at))
+        }
+        _ => Err(value),
+    }
+}
+
+// Removes the value at the given location, returning it together with the
+// location in a form that `add` will put it back into.
+fn remove(doc: &mut Value, path: &[String]) -> Result<(Value, Vec<String>)> {
+    let Some((last, parent)) = path.split_last() else {
+        return Err(Error);
+    };
+
+    let removed = match lookup_mut(doc, parent)? {
+        Value::Object(object) => object.remove(last),
+        Value::Array(array) => match parse_array_index(last) {
+            Some(index) if index < array.len() => Some(array.remove(index)),
+            _ => None,
+        },
+        _ => None,
+    };
+
+    match removed {
+        Some(removed) => Ok((removed, path.to_vec())),
+        None => Err(Error),
+    }
+}
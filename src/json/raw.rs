@@ -0,0 +1,125 @@
+use crate::de::{Deserialize, Visitor};
+use crate::error::Result;
+use crate::json::de::validate;
+use crate::ser::{Fragment, Serialize};
+use alloc::borrow::ToOwned;
+use alloc::boxed::Box;
+use alloc::string::String;
+use core::fmt::{self, Debug, Display};
+
+/// A JSON value kept as unparsed text.
+///
+/// Deserializing a `Box<RawValue>` captures the exact text of one JSON value
+/// from the input, without any surrounding whitespace. Its syntax is checked
+/// but nothing is decoded. Serializing it writes the same text back out
+/// unchanged. This is useful for passing through parts of a document that
+/// only need to be forwarded.
+///
+/// ```rust
+/// use miniserde::json::{self, RawValue};
+/// use miniserde::{Deserialize, Serialize};
+///
+/// #[derive(Deserialize, Serialize)]
+/// struct Envelope {
+///     to: String,
+///     payload: Box<RawValue>,
+/// }
+///
+/// fn main() -> miniserde::Result<()> {
+///     let j = r#" {"to": "backend", "payload": {"b": 1.50, "a": "é"}} "#;
+///
+///     let envelope: Envelope = json::from_str(j)?;
+///     assert_eq!(envelope.payload.get(), r#"{"b": 1.50, "a": "é"}"#);
+///
+///     let out = json::to_string(&envelope);
+///     assert_eq!(out, r#"{"to":"backend","payload":{"b": 1.50, "a": "é"}}"#);
+///
+///     Ok(())
+/// }
+/// ```
+///
+/// The push [`Parser`][crate::json::Parser] does not keep the text of a value
+/// around, so it fails on input containing a `RawValue`.
+#[repr(transparent)]
+pub struct RawValue {
+    json: str,
+}
+
+impl RawValue {
+    /// Check that a string is a single valid JSON value and convert it into a
+    /// `RawValue`. Whitespace around the value is kept.
+    pub fn from_string(json: String) -> Result<Box<Self>> {
+        validate(&json)?;
+        Ok(RawValue::from_owned(json.into_boxed_str()))
+    }
+
+    /// The JSON text of the value.
+    pub fn get(&self) -> &str {
+        &self.json
+    }
+
+    fn from_owned(json: Box<str>) -> Box<Self> {
+        unsafe { Box::from_raw(Box::into_raw(json) as *mut RawValue) }
+    }
+
+    fn from_borrowed(json: &str) -> &Self {
+        unsafe { &*(json as *const str as *const RawValue) }
+    }
+}
+
+impl Clone for Box<RawValue> {
+    fn clone(&self) -> Self {
+        (**self).to_owned()
+    }
+}
+
+impl ToOwned for RawValue {
+    type Owned = Box<RawValue>;
+
+    fn to_owned(&self) -> Self::Owned {
+        RawValue::from_owned(Box::from(&self.json))
+    }
+}
+
+impl Debug for RawValue {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter
+            .debug_tuple("RawValue")
+            .field(&format_args!("{}", &self.json))
+            .finish()
+    }
+}
+
+impl Display for RawValue {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(&self.json)
+    }
+}
+
+impl Serialize for RawValue {
+    fn begin(&self) -> Fragment {
+        Fragment::RawJson(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Box<RawValue> {
+    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor<'de> {
+        make_place!(Place);
+
+        impl<'de> Visitor<'de> for Place<Box<RawValue>> {
+            fn wants_raw_json(&mut self) -> bool {
+                true
+            }
+
+            fn raw_json(&mut self, j: &str) -> Result<()> {
+                // Not checked again: this is only called from within the
+                // crate, with text the JSON deserializer has already parsed
+                // as one value or that was produced by serializing a Value.
+                self.out = Some(RawValue::from_borrowed(j).to_owned());
+                Ok(())
+            }
+        }
+
+        Place::new(out)
+    }
+}
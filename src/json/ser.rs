@@ -1,3 +1,6 @@
+use crate::base64;
+use crate::error::{Error, Result};
+use crate::json::{BytesFormat, NonFinite, SerializeOptions};
 use crate::ser::{Fragment, Map, Seq, Serialize};
 use alloc::borrow::Cow;
 use alloc::boxed::Box;
@@ -25,11 +28,51 @@ use alloc::vec::Vec;
 ///     println!("{}", j);
 /// }
 /// ```
+///
+/// Floats which are `NaN` or infinite are written as `null`. Use
+/// [`try_to_string`] to fail on them instead.
 pub fn to_string<T>(value: &T) -> String
 where
     T: ?Sized + Serialize,
 {
-    to_string_impl(&value)
+    match to_string_impl(&value, &SerializeOptions::new()) {
+        Ok(out) => out,
+        // Writing null for non-finite floats cannot fail.
+        Err(Error) => unreachable!(),
+    }
+}
+
+/// Serialize any serializable type into a JSON string, failing if it contains
+/// a float which is `NaN` or infinite.
+///
+/// ```rust
+/// use miniserde::json;
+///
+/// assert_eq!(json::try_to_string(&[1.5]).unwrap(), "[1.5]");
+/// assert!(json::try_to_string(&[f64::NAN]).is_err());
+/// ```
+pub fn try_to_string<T>(value: &T) -> Result<String>
+where
+    T: ?Sized + Serialize,
+{
+    let options = SerializeOptions::new().non_finite(NonFinite::Error);
+    to_string_impl(&value, &options)
+}
+
+/// Serialize any serializable type into a JSON string with the given options.
+///
+/// ```rust
+/// use miniserde::json::{self, NonFinite, SerializeOptions};
+///
+/// let options = SerializeOptions::new().non_finite(NonFinite::Literal);
+/// let j = json::to_string_with(&[1.0, f64::INFINITY], &options).unwrap();
+/// assert_eq!(j, "[1.0,Infinity]");
+/// ```
+pub fn to_string_with<T>(value: &T, options: &SerializeOptions) -> Result<String>
+where
+    T: ?Sized + Serialize,
+{
+    to_string_impl(&value, options)
 }
 
 struct Serializer<'a> {
@@ -50,27 +93,60 @@ impl<'a> Drop for Serializer<'a> {
     }
 }
 
-fn to_string_impl(value: &dyn Serialize) -> String {
+fn to_string_impl(value: &dyn Serialize, options: &SerializeOptions) -> Result<String> {
     let mut out = String::new();
     let mut serializer = Serializer { stack: Vec::new() };
     let mut fragment = value.begin();
+    let escape = match (options.ascii_only, options.html_safe) {
+        (false, false) => &ESCAPE,
+        (true, false) => &ESCAPE_ASCII,
+        (false, true) => &ESCAPE_HTML,
+        (true, true) => &ESCAPE_ASCII_HTML,
+    };
 
     loop {
         match fragment {
             Fragment::Null => out.push_str("null"),
             Fragment::Bool(b) => out.push_str(if b { "true" } else { "false" }),
-            Fragment::Str(s) => escape_str(&s, &mut out),
+            Fragment::Str(s) => escape_str(&s, &mut out, escape),
             Fragment::U64(n) => out.push_str(itoa::Buffer::new().format(n)),
             Fragment::I64(n) => out.push_str(itoa::Buffer::new().format(n)),
             Fragment::F64(n) => {
                 if n.is_finite() {
                     out.push_str(zmij::Buffer::new().format_finite(n));
                 } else {
-                    out.push_str("null");
+                    let literal = if n.is_nan() {
+                        "NaN"
+                    } else if n > 0.0 {
+                        "Infinity"
+                    } else {
+                        "-Infinity"
+                    };
+                    match options.non_finite {
+                        NonFinite::Null => out.push_str("null"),
+                        NonFinite::Error => return Err(Error),
+                        NonFinite::Literal => out.push_str(literal),
+                        NonFinite::String => escape_str(literal, &mut out, escape),
+                    }
+                }
+            }
+            Fragment::Bytes(b) => match options.bytes {
+                BytesFormat::Base64 => write_base64(&b, &mut out),
+                BytesFormat::Array => write_bytes(&b, &mut out),
+            },
+            Fragment::RawJson(raw) => {
+                if options.ascii_only || options.html_safe {
+                    escape_raw(raw.get(), &mut out, escape);
+                } else {
+                    out.push_str(raw.get());
                 }
             }
             Fragment::Seq(mut seq) => {
                 out.push('[');
+                if let Some(len) = seq.size_hint() {
+                    // At least one digit and a comma for each element.
+                    let _ = out.try_reserve(len.saturating_mul(2));
+                }
                 // invariant: `seq` must outlive `first`
                 match unsafe { extend_lifetime!(seq.next() as Option<&dyn Serialize>) } {
                     Some(first) => {
@@ -83,11 +159,15 @@ fn to_string_impl(value: &dyn Serialize) -> String {
             }
             Fragment::Map(mut map) => {
                 out.push('{');
+                if let Some(len) = map.size_hint() {
+                    // At least `"":0,` for each entry.
+                    let _ = out.try_reserve(len.saturating_mul(5));
+                }
                 // invariant: `map` must outlive `first`
                 match unsafe { extend_lifetime!(map.next() as Option<(Cow<str>, &dyn Serialize)>) }
                 {
                     Some((key, first)) => {
-                        escape_str(&key, &mut out);
+                        escape_str(&key, &mut out, escape);
                         out.push(':');
                         serializer.stack.push(Layer::Map(map));
                         fragment = first.begin();
@@ -118,7 +198,7 @@ fn to_string_impl(value: &dyn Serialize) -> String {
                     } {
                         Some((key, next)) => {
                             out.push(',');
-                            escape_str(&key, &mut out);
+                            escape_str(&key, &mut out, escape);
                             out.push(':');
                             fragment = next.begin();
                             break;
@@ -126,24 +206,83 @@ fn to_string_impl(value: &dyn Serialize) -> String {
                         None => out.push('}'),
                     }
                 }
-                None => return out,
+                None => return Ok(out),
             }
             serializer.stack.pop();
         }
     }
 }
 
-// Clippy false positive: https://github.com/rust-lang/rust-clippy/issues/5169
-#[allow(clippy::zero_prefixed_literal)]
-fn escape_str(value: &str, out: &mut String) {
+// JSON has no byte strings, so these become a base64 string, which needs no
+// escaping, or else an array of numbers.
+pub(crate) fn write_base64(bytes: &[u8], out: &mut String) {
+    out.push('"');
+    base64::encode(bytes, out);
     out.push('"');
+}
 
+fn write_bytes(bytes: &[u8], out: &mut String) {
+    out.push('[');
+    for (i, &byte) in bytes.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        out.push_str(itoa::Buffer::new().format(byte));
+    }
+    out.push(']');
+}
+
+pub(crate) fn escape_str(value: &str, out: &mut String, table: &[u8; 256]) {
+    out.push('"');
+    escape_contents(value, out, table);
+    out.push('"');
+}
+
+// Copies valid JSON text, escaping the characters inside its strings which
+// `table` escapes beyond what JSON requires. Those that JSON requires are
+// escaped already, and outside of strings there is only ASCII.
+fn escape_raw(json: &str, out: &mut String, table: &[u8; 256]) {
+    let bytes = json.as_bytes();
+    let mut i = 0;
+    while let Some(quote) = bytes[i..].iter().position(|&b| b == b'"') {
+        let open = i + quote + 1;
+        out.push_str(&json[i..open]);
+        i = open;
+        loop {
+            let len = bytes[i..]
+                .iter()
+                .position(|&b| b == b'"' || b == b'\\')
+                .unwrap();
+            escape_contents(&json[i..i + len], out, table);
+            i += len;
+            if bytes[i] == b'"' {
+                out.push('"');
+                i += 1;
+                break;
+            }
+            // The backslash and the character after it. The hex digits of a
+            // \u escape are left to the next run, in which they stay as they
+            // are.
+            out.push_str(&json[i..i + 2]);
+            i += 2;
+        }
+    }
+    out.push_str(&json[i..]);
+}
+
+// Clippy false positive: https://github.com/rust-lang/rust-clippy/issues/5169
+#[allow(clippy::zero_prefixed_literal)]
+fn escape_contents(value: &str, out: &mut String, table: &[u8; 256]) {
     let bytes = value.as_bytes();
     let mut start = 0;
 
     for (i, &byte) in bytes.iter().enumerate() {
-        let escape = ESCAPE[byte as usize];
-        if escape == 0 {
+        let escape = table[byte as usize];
+        // Skip the rest of a character which has already been escaped.
+        if escape == 0 || i < start {
+            continue;
+        }
+        if escape == LS && !matches!(bytes[i + 1..], [0x80, 0xA8 | 0xA9, ..]) {
             continue;
         }
 
@@ -151,6 +290,7 @@ fn escape_str(value: &str, out: &mut String) {
             out.push_str(&value[start..i]);
         }
 
+        let mut len = 1;
         match escape {
             self::BB => out.push_str("\\b"),
             self::TT => out.push_str("\\t"),
@@ -159,23 +299,35 @@ fn escape_str(value: &str, out: &mut String) {
             self::RR => out.push_str("\\r"),
             self::QU => out.push_str("\\\""),
             self::BS => out.push_str("\\\\"),
-            self::U => {
-                static HEX_DIGITS: [u8; 16] = *b"0123456789abcdef";
-                out.push_str("\\u00");
-                out.push(HEX_DIGITS[(byte >> 4) as usize] as char);
-                out.push(HEX_DIGITS[(byte & 0xF) as usize] as char);
+            self::U => push_unicode_escape(u16::from(byte), out),
+            self::NA => {
+                let ch = value[i..].chars().next().unwrap();
+                for &unit in ch.encode_utf16(&mut [0; 2]).iter() {
+                    push_unicode_escape(unit, out);
+                }
+                len = ch.len_utf8();
+            }
+            self::LS => {
+                push_unicode_escape(if bytes[i + 2] == 0xA8 { 0x2028 } else { 0x2029 }, out);
+                len = 3;
             }
             _ => unreachable!(),
         }
 
-        start = i + 1;
+        start = i + len;
     }
 
-    if start != bytes.len() {
+    if start < bytes.len() {
         out.push_str(&value[start..]);
     }
+}
 
-    out.push('"');
+fn push_unicode_escape(unit: u16, out: &mut String) {
+    static HEX_DIGITS: [u8; 16] = *b"0123456789abcdef";
+    out.push_str("\\u");
+    for shift in [12, 8, 4, 0] {
+        out.push(HEX_DIGITS[usize::from(unit >> shift & 0xF)] as char);
+    }
 }
 
 const BB: u8 = b'b'; // \x08
@@ -186,11 +338,13 @@ const RR: u8 = b'r'; // \x0D
 const QU: u8 = b'"'; // \x22
 const BS: u8 = b'\\'; // \x5C
 const U: u8 = b'u'; // \x00...\x1F except the ones above
+const NA: u8 = b'a'; // \x80...\xFF, escaped a whole character at a time
+const LS: u8 = b'l'; // \xE2, which begins U+2028 and U+2029
 
 // Lookup table of escape sequences. A value of b'x' at index i means that byte
 // i is escaped as "\x" in JSON. A value of 0 means that byte i is not escaped.
 #[rustfmt::skip]
-static ESCAPE: [u8; 256] = [
+pub(crate) static ESCAPE: [u8; 256] = [
     //  1   2   3   4   5   6   7   8   9   A   B   C   D   E   F
     U,  U,  U,  U,  U,  U,  U,  U, BB, TT, NN,  U, FF, RR,  U,  U, // 0
     U,  U,  U,  U,  U,  U,  U,  U,  U,  U,  U,  U,  U,  U,  U,  U, // 1
@@ -209,3 +363,70 @@ static ESCAPE: [u8; 256] = [
     0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0, // E
     0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0, // F
 ];
+
+// Same as ESCAPE, but also escaping every byte of non-ASCII characters.
+#[rustfmt::skip]
+static ESCAPE_ASCII: [u8; 256] = [
+    //  1   2   3   4   5   6   7   8   9   A   B   C   D   E   F
+    U,  U,  U,  U,  U,  U,  U,  U, BB, TT, NN,  U, FF, RR,  U,  U, // 0
+    U,  U,  U,  U,  U,  U,  U,  U,  U,  U,  U,  U,  U,  U,  U,  U, // 1
+    0,  0, QU,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0, // 2
+    0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0, // 3
+    0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0, // 4
+    0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0, BS,  0,  0,  0, // 5
+    0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0, // 6
+    0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0, // 7
+   NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, // 8
+   NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, // 9
+   NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, // A
+   NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, // B
+   NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, // C
+   NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, // D
+   NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, // E
+   NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, // F
+];
+
+// Same as ESCAPE, but also escaping `<`, `>` and `&`, and looking at each byte
+// which may begin U+2028 or U+2029.
+#[rustfmt::skip]
+static ESCAPE_HTML: [u8; 256] = [
+    //  1   2   3   4   5   6   7   8   9   A   B   C   D   E   F
+    U,  U,  U,  U,  U,  U,  U,  U, BB, TT, NN,  U, FF, RR,  U,  U, // 0
+    U,  U,  U,  U,  U,  U,  U,  U,  U,  U,  U,  U,  U,  U,  U,  U, // 1
+    0,  0, QU,  0,  0,  0,  U,  0,  0,  0,  0,  0,  0,  0,  0,  0, // 2
+    0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  U,  0,  U,  0, // 3
+    0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0, // 4
+    0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0, BS,  0,  0,  0, // 5
+    0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0, // 6
+    0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0, // 7
+    0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0, // 8
+    0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0, // 9
+    0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0, // A
+    0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0, // B
+    0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0, // C
+    0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0, // D
+    0,  0, LS,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0, // E
+    0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0, // F
+];
+
+// Both of the above. U+2028 and U+2029 are non-ASCII so need no special case.
+#[rustfmt::skip]
+static ESCAPE_ASCII_HTML: [u8; 256] = [
+    //  1   2   3   4   5   6   7   8   9   A   B   C   D   E   F
+    U,  U,  U,  U,  U,  U,  U,  U, BB, TT, NN,  U, FF, RR,  U,  U, // 0
+    U,  U,  U,  U,  U,  U,  U,  U,  U,  U,  U,  U,  U,  U,  U,  U, // 1
+    0,  0, QU,  0,  0,  0,  U,  0,  0,  0,  0,  0,  0,  0,  0,  0, // 2
+    0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  U,  0,  U,  0, // 3
+    0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0, // 4
+    0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0, BS,  0,  0,  0, // 5
+    0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0, // 6
+    0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0,  0, // 7
+   NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, // 8
+   NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, // 9
+   NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, // A
+   NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, // B
+   NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, // C
+   NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, // D
+   NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, // E
+   NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, // F
+];
@@ -0,0 +1,314 @@
+use crate::de::DeserializeOwned;
+use crate::error::{Error, Result};
+use crate::json::from_str;
+#[cfg(feature = "std")]
+use alloc::boxed::Box;
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use core::str;
+#[cfg(feature = "std")]
+use std::io::{self, Read};
+
+// Record separator that begins each JSON text in an RFC 7464 sequence.
+const RS: u8 = b'\x1E';
+
+/// Iterator that deserializes a stream of JSON values.
+///
+/// The values may be separated by any amount of whitespace, which covers both
+/// concatenated JSON and newline-delimited JSON Lines, or each may be preceded
+/// by an ASCII record separator as in [RFC 7464] JSON text sequences. A number,
+/// `true`, `false` or `null` at the top level must be followed by whitespace
+/// or the end of the input so that a truncated value is not mistaken for a
+/// complete one.
+///
+/// Each call to `next` yields one value. After an error the iterator ends,
+/// unless the input is a JSON text sequence, in which case the rest of the
+/// record is skipped and deserialization resumes at the next one.
+///
+/// Values are parsed with the default settings: none of the limits in
+/// [`Options`](super::Options) apply.
+///
+/// [RFC 7464]: https://tools.ietf.org/html/rfc7464
+///
+/// ```rust
+/// use miniserde::{json, Deserialize};
+///
+/// #[derive(Deserialize, Debug)]
+/// struct Event {
+///     code: u32,
+/// }
+///
+/// fn main() -> miniserde::Result<()> {
+///     let j = "{\"code\": 200}\n{\"code\": 404}\n";
+///
+///     let mut stream = json::StreamDeserializer::<Event>::new(j);
+///     assert_eq!(stream.next().unwrap()?.code, 200);
+///     assert_eq!(stream.byte_offset(), 13);
+///     assert_eq!(stream.next().unwrap()?.code, 404);
+///     assert!(stream.next().is_none());
+///
+///     Ok(())
+/// }
+/// ```
+pub struct StreamDeserializer<'a, T> {
+    input: Input<'a>,
+    // Position of the next unread byte within the buffered input.
+    pos: usize,
+    // Number of bytes that have been discarded from the front of the buffer.
+    discarded: usize,
+    // Whether a record separator has been seen, making this a JSON text
+    // sequence in which errors are recoverable.
+    sequence: bool,
+    done: bool,
+    marker: PhantomData<fn() -> T>,
+}
+
+enum Input<'a> {
+    Slice(&'a [u8]),
+    #[cfg(feature = "std")]
+    Reader {
+        reader: Box<dyn Read + 'a>,
+        buffer: Vec<u8>,
+        eof: bool,
+    },
+}
+
+impl<'a, T> StreamDeserializer<'a, T> {
+    /// Deserialize a stream of values from a string.
+    pub fn new(j: &'a str) -> Self {
+        Self::from_slice(j.as_bytes())
+    }
+
+    /// Deserialize a stream of values from bytes. Each value must be valid
+    /// UTF-8.
+    pub fn from_slice(j: &'a [u8]) -> Self {
+        StreamDeserializer::with_input(Input::Slice(j))
+    }
+
+    /// Deserialize a stream of values from a reader.
+    ///
+    /// The reader is read in chunks of up to 8 KiB, so it may be read past the
+    /// end of the value being returned; the rest is kept for the following
+    /// calls. A read is only made when the buffered input does not yet contain
+    /// the end of the next value, so this works on streams such as sockets and
+    /// pipes where more values keep arriving. Wrapping the reader in a
+    /// `BufReader` is unnecessary.
+    #[cfg(feature = "std")]
+    pub fn from_reader<R>(reader: R) -> Self
+    where
+        R: Read + 'a,
+    {
+        StreamDeserializer::with_input(Input::Reader {
+            reader: Box::new(reader),
+            buffer: Vec::new(),
+            eof: false,
+        })
+    }
+
+    fn with_input(input: Input<'a>) -> Self {
+        StreamDeserializer {
+            input,
+            pos: 0,
+            discarded: 0,
+            sequence: false,
+            done: false,
+            marker: PhantomData,
+        }
+    }
+
+    /// Number of bytes of the input consumed so far.
+    ///
+    /// After a value has been deserialized, this is the offset immediately
+    /// after the end of that value.
+    pub fn byte_offset(&self) -> usize {
+        self.discarded + self.pos
+    }
+
+    fn bytes(&self) -> &[u8] {
+        match &self.input {
+            Input::Slice(bytes) => bytes,
+            #[cfg(feature = "std")]
+            Input::Reader { buffer, .. } => buffer,
+        }
+    }
+
+    // Makes more input available after the end of `bytes()`. Returns false at
+    // the end of the input. Bytes before `pos` may be discarded, so any
+    // position held across a call must be relative to `pos`.
+    fn fill(&mut self) -> Result<bool> {
+        match &mut self.input {
+            Input::Slice(_) => Ok(false),
+            #[cfg(feature = "std")]
+            Input::Reader {
+                reader,
+                buffer,
+                eof,
+            } => {
+                if *eof {
+                    return Ok(false);
+                }
+                buffer.drain(..self.pos);
+                self.discarded += self.pos;
+                self.pos = 0;
+
+                let len = buffer.len();
+                buffer.resize(len + 8192, 0);
+                loop {
+                    match reader.read(&mut buffer[len..]) {
+                        Ok(n) => {
+                            buffer.truncate(len + n);
+                            *eof = n == 0;
+                            return Ok(n > 0);
+                        }
+                        Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+                        Err(_) => {
+                            buffer.truncate(len);
+                            *eof = true;
+                            return Err(Error);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // Skips to the start of the next value and finds where it ends. Returns
+    // its length, and whether it was cut short by the start of another record.
+    fn next_value(&mut self) -> Result<Option<(usize, bool)>> {
+        loop {
+            match self.bytes().get(self.pos) {
+                Some(b' ' | b'\n' | b'\t' | b'\r') => self.pos += 1,
+                Some(&RS) => {
+                    self.sequence = true;
+                    self.pos += 1;
+                }
+                Some(_) => break,
+                None => {
+                    if !self.fill()? {
+                        return Ok(None);
+                    }
+                }
+            }
+        }
+
+        let mut scanner = Scanner::default();
+        loop {
+            if let Some(len) = scanner.scan(&self.bytes()[self.pos..]) {
+                return Ok(Some((len, scanner.truncated)));
+            }
+            if !self.fill()? {
+                return Ok(Some((self.bytes().len() - self.pos, false)));
+            }
+        }
+    }
+
+    // Skips the rest of a record that failed to deserialize.
+    fn recover(&mut self) {
+        if !self.sequence {
+            self.done = true;
+            return;
+        }
+        loop {
+            let rest = &self.bytes()[self.pos..];
+            if let Some(i) = rest.iter().position(|&b| b == RS) {
+                self.pos += i;
+                return;
+            }
+            self.pos += rest.len();
+            if !matches!(self.fill(), Ok(true)) {
+                return;
+            }
+        }
+    }
+}
+
+impl<'a, T> Iterator for StreamDeserializer<'a, T>
+where
+    T: DeserializeOwned,
+{
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let (len, truncated) = match self.next_value() {
+            Ok(Some(value)) => value,
+            Ok(None) => {
+                self.done = true;
+                return None;
+            }
+            Err(error) => {
+                self.done = true;
+                return Some(Err(error));
+            }
+        };
+
+        let start = self.pos;
+        self.pos += len;
+        let result = if truncated {
+            Err(Error)
+        } else {
+            let j = str::from_utf8(&self.bytes()[start..self.pos]).map_err(|_| Error);
+            j.and_then(from_str)
+        };
+        if result.is_err() {
+            self.recover();
+        }
+        Some(result)
+    }
+}
+
+// Finds the end of a JSON value without interpreting it, so that it can be
+// handed to the deserializer in one piece. Anything malformed is left for the
+// deserializer to reject.
+#[derive(Default)]
+struct Scanner {
+    len: usize,
+    depth: usize,
+    string: bool,
+    escape: bool,
+    truncated: bool,
+}
+
+impl Scanner {
+    // Continues scanning where the previous call left off, and returns the
+    // length of the value once its end has been seen.
+    fn scan(&mut self, bytes: &[u8]) -> Option<usize> {
+        while let Some(&byte) = bytes.get(self.len) {
+            self.len += 1;
+            if byte == RS {
+                // Cannot appear in a JSON text, even inside a string. A value
+                // that is not complete by now never will be.
+                self.truncated = true;
+                return Some(self.len - 1);
+            }
+            if self.string {
+                if self.escape {
+                    self.escape = false;
+                } else if byte == b'\\' {
+                    self.escape = true;
+                } else if byte == b'"' {
+                    self.string = false;
+                    if self.depth == 0 {
+                        return Some(self.len);
+                    }
+                }
+                continue;
+            }
+            match byte {
+                b'"' => self.string = true,
+                b'[' | b'{' => self.depth += 1,
+                b']' | b'}' => match self.depth.checked_sub(1) {
+                    Some(0) | None => return Some(self.len),
+                    Some(depth) => self.depth = depth,
+                },
+                b' ' | b'\n' | b'\t' | b'\r' if self.depth == 0 => return Some(self.len - 1),
+                _ => {}
+            }
+        }
+        None
+    }
+}
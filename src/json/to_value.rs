@@ -0,0 +1,148 @@
+use crate::base64;
+use crate::json::{from_str, Array, Number, Object, Value};
+use crate::ser::{Fragment, Map, Seq, Serialize};
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::mem;
+
+/// Convert any serializable type into a `json::Value`.
+///
+/// This produces the same value as serializing to a JSON string and parsing it
+/// back into a `Value`, but without going through text.
+///
+/// ```rust
+/// use miniserde::{json, Serialize};
+///
+/// #[derive(Serialize, Debug)]
+/// struct Example {
+///     code: u32,
+///     message: String,
+/// }
+///
+/// fn main() {
+///     let example = Example {
+///         code: 200,
+///         message: "reminiscent of Serde".to_owned(),
+///     };
+///
+///     let value = json::to_value(&example);
+///     assert_eq!(value["code"].as_u64(), Some(200));
+/// }
+/// ```
+pub fn to_value<T>(value: &T) -> Value
+where
+    T: ?Sized + Serialize,
+{
+    to_value_impl(&value)
+}
+
+struct Serializer<'a> {
+    stack: Vec<Layer<'a>>,
+}
+
+enum Layer<'a> {
+    Seq(Box<dyn Seq + 'a>, Array),
+    Map(Box<dyn Map + 'a>, Object, String),
+}
+
+impl<'a> Drop for Serializer<'a> {
+    fn drop(&mut self) {
+        // Drop layers in reverse order.
+        while !self.stack.is_empty() {
+            self.stack.pop();
+        }
+    }
+}
+
+fn to_value_impl(value: &dyn Serialize) -> Value {
+    let mut serializer = Serializer { stack: Vec::new() };
+    let mut fragment = value.begin();
+
+    loop {
+        let mut value = match fragment {
+            Fragment::Null => Value::Null,
+            Fragment::Bool(b) => Value::Bool(b),
+            Fragment::Str(s) => Value::String(s.into_owned()),
+            Fragment::U64(n) => Value::Number(Number::U64(n)),
+            Fragment::I64(n) => Value::Number(if n < 0 {
+                Number::I64(n)
+            } else {
+                Number::U64(n as u64)
+            }),
+            Fragment::F64(n) => {
+                if n.is_finite() {
+                    Value::Number(Number::F64(n))
+                } else {
+                    Value::Null
+                }
+            }
+            Fragment::Bytes(b) => {
+                let mut string = String::new();
+                base64::encode(&b, &mut string);
+                Value::String(string)
+            }
+            // Always valid JSON when it comes from a RawValue. Anything else
+            // that fails to parse has no sensible Value to become.
+            // The text of a RawValue is always valid JSON.
+            Fragment::RawJson(raw) => from_str(raw.get()).unwrap(),
+            Fragment::Seq(mut seq) => {
+                // invariant: `seq` must outlive `first`
+                match unsafe { extend_lifetime!(seq.next() as Option<&dyn Serialize>) } {
+                    Some(first) => {
+                        serializer.stack.push(Layer::Seq(seq, Array::new()));
+                        fragment = first.begin();
+                        continue;
+                    }
+                    None => Value::Array(Array::new()),
+                }
+            }
+            Fragment::Map(mut map) => {
+                // invariant: `map` must outlive `first`
+                match unsafe { extend_lifetime!(map.next() as Option<(Cow<str>, &dyn Serialize)>) }
+                {
+                    Some((key, first)) => {
+                        let key = key.into_owned();
+                        serializer.stack.push(Layer::Map(map, Object::new(), key));
+                        fragment = first.begin();
+                        continue;
+                    }
+                    None => Value::Object(Object::new()),
+                }
+            }
+        };
+
+        loop {
+            match serializer.stack.last_mut() {
+                Some(Layer::Seq(seq, array)) => {
+                    array.push(value);
+                    // invariant: `seq` must outlive `next`
+                    if let Some(next) =
+                        unsafe { extend_lifetime!(seq.next() as Option<&dyn Serialize>) }
+                    {
+                        fragment = next.begin();
+                        break;
+                    }
+                }
+                Some(Layer::Map(map, object, key)) => {
+                    object.insert(mem::take(key), value);
+                    // invariant: `map` must outlive `next`
+                    if let Some((k, next)) = unsafe {
+                        extend_lifetime!(map.next() as Option<(Cow<str>, &dyn Serialize)>)
+                    } {
+                        *key = k.into_owned();
+                        fragment = next.begin();
+                        break;
+                    }
+                }
+                None => return value,
+            }
+            value = match serializer.stack.pop() {
+                Some(Layer::Seq(_, array)) => Value::Array(array),
+                Some(Layer::Map(_, object, _)) => Value::Object(object),
+                None => unreachable!(),
+            };
+        }
+    }
+}
@@ -1,11 +1,14 @@
+use crate::base64;
 use crate::de::{Deserialize, Map, Seq, Visitor};
 use crate::error::Result;
-use crate::json::{Array, Number, Object};
+use crate::json::{clone, cmp, debug, Array, Index, Number, Object};
 use crate::ser::{Fragment, Serialize};
 use alloc::borrow::{Cow, ToOwned};
 use alloc::boxed::Box;
 use alloc::string::String;
+use core::cmp::Ordering;
 use core::fmt::{self, Debug};
+use core::hash::{Hash, Hasher};
 use core::mem;
 use core::str;
 
@@ -26,7 +29,9 @@ use core::str;
 /// }
 /// // no stack overflow when `value` goes out of scope
 /// ```
-#[derive(Clone)]
+///
+/// The same goes for its `Clone`, `Debug`, `PartialEq`, `Ord` and `Hash`
+/// impls, none of which recurse.
 pub enum Value {
     Null,
     Bool(bool),
@@ -36,6 +41,179 @@ pub enum Value {
     Object(Object),
 }
 
+impl Value {
+    /// Index into an array by position or into an object by key.
+    ///
+    /// Returns `None` if the type of `self` does not match the type of the
+    /// index, or if the array is too short or the object has no such key.
+    ///
+    /// ```rust
+    /// use miniserde::json::{self, Value};
+    ///
+    /// fn main() -> miniserde::Result<()> {
+    ///     let value: Value = json::from_str(r#" {"data": [{"id": 7}]} "#)?;
+    ///
+    ///     assert_eq!(value["data"][0]["id"].as_u64(), Some(7));
+    ///     assert!(value.get("data").and_then(|data| data.get(1)).is_none());
+    ///     assert!(value["missing"][0].is_null());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn get<I>(&self, index: I) -> Option<&Value>
+    where
+        I: Index,
+    {
+        index.index_into(self)
+    }
+
+    /// Mutably index into an array by position or into an object by key.
+    pub fn get_mut<I>(&mut self, index: I) -> Option<&mut Value>
+    where
+        I: Index,
+    {
+        index.index_into_mut(self)
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+
+    pub fn is_bool(&self) -> bool {
+        matches!(self, Value::Bool(_))
+    }
+
+    pub fn is_number(&self) -> bool {
+        matches!(self, Value::Number(_))
+    }
+
+    /// True if the value is a number representable as `u64`.
+    pub fn is_u64(&self) -> bool {
+        self.as_u64().is_some()
+    }
+
+    /// True if the value is a number representable as `i64`.
+    pub fn is_i64(&self) -> bool {
+        self.as_i64().is_some()
+    }
+
+    /// True if the value is a number stored as `f64`.
+    pub fn is_f64(&self) -> bool {
+        matches!(self, Value::Number(Number::F64(_)))
+    }
+
+    pub fn is_string(&self) -> bool {
+        matches!(self, Value::String(_))
+    }
+
+    pub fn is_array(&self) -> bool {
+        matches!(self, Value::Array(_))
+    }
+
+    pub fn is_object(&self) -> bool {
+        matches!(self, Value::Object(_))
+    }
+
+    pub fn as_null(&self) -> Option<()> {
+        match self {
+            Value::Null => Some(()),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn as_number(&self) -> Option<&Number> {
+        match self {
+            Value::Number(number) => Some(number),
+            _ => None,
+        }
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        self.as_number()?.as_u64()
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        self.as_number()?.as_i64()
+    }
+
+    /// Returns any number converted to `f64`.
+    pub fn as_f64(&self) -> Option<f64> {
+        Some(self.as_number()?.as_f64())
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&Array> {
+        match self {
+            Value::Array(array) => Some(array),
+            _ => None,
+        }
+    }
+
+    pub fn as_array_mut(&mut self) -> Option<&mut Array> {
+        match self {
+            Value::Array(array) => Some(array),
+            _ => None,
+        }
+    }
+
+    pub fn as_object(&self) -> Option<&Object> {
+        match self {
+            Value::Object(object) => Some(object),
+            _ => None,
+        }
+    }
+
+    pub fn as_object_mut(&mut self) -> Option<&mut Object> {
+        match self {
+            Value::Object(object) => Some(object),
+            _ => None,
+        }
+    }
+
+    /// Takes the value out, leaving `Value::Null` in its place.
+    pub fn take(&mut self) -> Value {
+        mem::take(self)
+    }
+}
+
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        cmp::cmp_values(self, other) == Ordering::Equal
+    }
+}
+
+impl Eq for Value {}
+
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Value {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp::cmp_values(self, other)
+    }
+}
+
+impl Hash for Value {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        cmp::hash_value(self, state);
+    }
+}
+
 impl Default for Value {
     /// The default value is null.
     fn default() -> Self {
@@ -43,16 +221,22 @@ impl Default for Value {
     }
 }
 
+impl Clone for Value {
+    fn clone(&self) -> Self {
+        clone::clone_value(self)
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        match (self, source) {
+            (Value::String(string), Value::String(source)) => string.clone_from(source),
+            (this, source) => *this = clone::clone_value(source),
+        }
+    }
+}
+
 impl Debug for Value {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            Value::Null => formatter.write_str("Null"),
-            Value::Bool(boolean) => write!(formatter, "Bool({})", boolean),
-            Value::Number(number) => write!(formatter, "Number({})", number),
-            Value::String(string) => write!(formatter, "String({:?})", string),
-            Value::Array(array) => Debug::fmt(array, formatter),
-            Value::Object(object) => Debug::fmt(object, formatter),
-        }
+        debug::debug_value(self, formatter)
     }
 }
 
@@ -69,11 +253,11 @@ impl Serialize for Value {
     }
 }
 
-impl Deserialize for Value {
-    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor {
+impl<'de> Deserialize<'de> for Value {
+    fn begin(out: &mut Option<Self>) -> &mut dyn Visitor<'de> {
         make_place!(Place);
 
-        impl Visitor for Place<Value> {
+        impl<'de> Visitor<'de> for Place<Value> {
             fn null(&mut self) -> Result<()> {
                 self.out = Some(Value::Null);
                 Ok(())
@@ -104,7 +288,15 @@ impl Deserialize for Value {
                 Ok(())
             }
 
-            fn seq(&mut self) -> Result<Box<dyn Seq + '_>> {
+            fn bytes(&mut self, b: &[u8]) -> Result<()> {
+                // The same as JSON would represent them.
+                let mut string = String::new();
+                base64::encode(b, &mut string);
+                self.out = Some(Value::String(string));
+                Ok(())
+            }
+
+            fn seq(&mut self) -> Result<Box<dyn Seq<'de> + '_>> {
                 Ok(Box::new(ArrayBuilder {
                     out: &mut self.out,
                     array: Array::new(),
@@ -112,7 +304,7 @@ impl Deserialize for Value {
                 }))
             }
 
-            fn map(&mut self) -> Result<Box<dyn Map + '_>> {
+            fn map(&mut self) -> Result<Box<dyn Map<'de> + '_>> {
                 Ok(Box::new(ObjectBuilder {
                     out: &mut self.out,
                     object: Object::new(),
@@ -136,8 +328,8 @@ impl Deserialize for Value {
             }
         }
 
-        impl<'a> Seq for ArrayBuilder<'a> {
-            fn element(&mut self) -> Result<&mut dyn Visitor> {
+        impl<'a, 'de> Seq<'de> for ArrayBuilder<'a> {
+            fn element(&mut self) -> Result<&mut dyn Visitor<'de>> {
                 self.shift();
                 Ok(Deserialize::begin(&mut self.element))
             }
@@ -164,8 +356,8 @@ impl Deserialize for Value {
             }
         }
 
-        impl<'a> Map for ObjectBuilder<'a> {
-            fn key(&mut self, k: &str) -> Result<&mut dyn Visitor> {
+        impl<'a, 'de> Map<'de> for ObjectBuilder<'a> {
+            fn key(&mut self, k: &str) -> Result<&mut dyn Visitor<'de>> {
                 self.shift();
                 self.key = Some(k.to_owned());
                 Ok(Deserialize::begin(&mut self.value))
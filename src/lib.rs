@@ -189,14 +189,29 @@ mod place {
     include!(concat!(env!("OUT_DIR"), "/place.rs"));
 }
 
+#[macro_use]
+mod macros;
+
+mod base64;
+mod bytes;
 mod error;
 mod ignore;
 mod ptr;
+mod text;
 
+pub mod cbor;
+#[cfg(feature = "serde")]
+pub mod compat;
+pub mod csv;
 pub mod de;
+#[cfg(feature = "std")]
+pub mod env;
 pub mod json;
+pub mod msgpack;
 pub mod ser;
+pub mod urlencoded;
 
+pub use crate::bytes::{ByteBuf, Bytes};
 #[doc(inline)]
 pub use crate::de::Deserialize;
 pub use crate::error::{Error, Result};
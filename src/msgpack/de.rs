@@ -0,0 +1,283 @@
+use self::Event::*;
+use crate::de::{Deserialize, Map, Seq, Visitor};
+use crate::error::{Error, Result};
+use crate::ptr::NonuniqueBox;
+use alloc::vec::Vec;
+use core::ptr::NonNull;
+use core::str;
+
+/// Deserialize MessagePack bytes into any deserializable type.
+///
+/// Map keys must be strings. Extension types are not supported.
+///
+/// ```rust
+/// use miniserde::msgpack;
+///
+/// fn main() -> miniserde::Result<()> {
+///     // {"a": [1, -2]}
+///     let bytes = [0x81, 0xA1, b'a', 0x92, 0x01, 0xFE];
+///
+///     let out: std::collections::BTreeMap<String, Vec<i32>> = msgpack::from_slice(&bytes)?;
+///     assert_eq!(out["a"], [1, -2]);
+///
+///     Ok(())
+/// }
+/// ```
+pub fn from_slice<'de, T>(bytes: &'de [u8]) -> Result<T>
+where
+    T: Deserialize<'de>,
+{
+    let mut out = None;
+    from_slice_impl(bytes, T::begin(&mut out))?;
+    out.ok_or(Error)
+}
+
+struct Deserializer<'a, 'b> {
+    input: &'a [u8],
+    pos: usize,
+    // Each open array or map, with how many elements or entries are left in
+    // it.
+    stack: Vec<(NonNull<dyn Visitor<'a>>, Layer<'a, 'b>, u32)>,
+}
+
+enum Layer<'de, 'a> {
+    Seq(NonuniqueBox<dyn Seq<'de> + 'a>),
+    Map(NonuniqueBox<dyn Map<'de> + 'a>),
+}
+
+impl<'a, 'b> Drop for Deserializer<'a, 'b> {
+    fn drop(&mut self) {
+        // Drop layers in reverse order.
+        while !self.stack.is_empty() {
+            self.stack.pop();
+        }
+    }
+}
+
+fn from_slice_impl<'de>(bytes: &'de [u8], visitor: &mut dyn Visitor<'de>) -> Result<()> {
+    let mut de = Deserializer {
+        input: bytes,
+        pos: 0,
+        stack: Vec::new(),
+    };
+    de.visit(visitor)?;
+
+    if de.pos == de.input.len() {
+        Ok(())
+    } else {
+        Err(Error)
+    }
+}
+
+impl<'a, 'b> Deserializer<'a, 'b> {
+    fn visit(&mut self, visitor: &mut dyn Visitor<'a>) -> Result<()>
+    where
+        'a: 'b,
+    {
+        let visitor = NonNull::from(visitor);
+        let mut visitor = unsafe { extend_lifetime!(visitor as NonNull<dyn Visitor<'a>>) };
+
+        loop {
+            let visitor_mut = unsafe { &mut *visitor.as_ptr() };
+            let layer = match self.event()? {
+                Null => {
+                    visitor_mut.null()?;
+                    None
+                }
+                Bool(b) => {
+                    visitor_mut.boolean(b)?;
+                    None
+                }
+                Negative(n) => {
+                    visitor_mut.negative(n)?;
+                    None
+                }
+                Nonnegative(n) => {
+                    visitor_mut.nonnegative(n)?;
+                    None
+                }
+                Float(n) => {
+                    visitor_mut.float(n)?;
+                    None
+                }
+                Bytes(b) => {
+                    visitor_mut.bytes(b)?;
+                    None
+                }
+                Str(s) => {
+                    visitor_mut.borrowed_string(s)?;
+                    None
+                }
+                SeqStart(len) => {
+                    let seq = visitor_mut.seq()?;
+                    Some((Layer::Seq(NonuniqueBox::from(seq)), len))
+                }
+                MapStart(len) => {
+                    let map = visitor_mut.map()?;
+                    Some((Layer::Map(NonuniqueBox::from(map)), len))
+                }
+            };
+
+            let (mut layer, mut remaining) = match layer {
+                Some(layer) => layer,
+                None => match self.stack.pop() {
+                    Some(frame) => {
+                        visitor = frame.0;
+                        (frame.1, frame.2)
+                    }
+                    None => return Ok(()),
+                },
+            };
+
+            while remaining == 0 {
+                match &mut layer {
+                    Layer::Seq(seq) => seq.finish()?,
+                    Layer::Map(map) => map.finish()?,
+                }
+                let Some(frame) = self.stack.pop() else {
+                    return Ok(());
+                };
+                visitor = frame.0;
+                layer = frame.1;
+                remaining = frame.2;
+            }
+
+            let outer = visitor;
+            match layer {
+                Layer::Seq(mut seq) => {
+                    let element = seq.element()?;
+                    let next = NonNull::from(element);
+                    visitor = unsafe { extend_lifetime!(next as NonNull<dyn Visitor<'a>>) };
+                    self.stack.push((outer, Layer::Seq(seq), remaining - 1));
+                }
+                Layer::Map(mut map) => {
+                    let key = self.parse_key()?;
+                    let entry = map.key(key)?;
+                    let next = NonNull::from(entry);
+                    visitor = unsafe { extend_lifetime!(next as NonNull<dyn Visitor<'a>>) };
+                    self.stack.push((outer, Layer::Map(map), remaining - 1));
+                }
+            }
+        }
+    }
+
+    fn next_byte(&mut self) -> Result<u8> {
+        let byte = *self.input.get(self.pos).ok_or(Error)?;
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let rest = &self.input[self.pos..];
+        if len > rest.len() {
+            return Err(Error);
+        }
+        self.pos += len;
+        Ok(&rest[..len])
+    }
+
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    // Reads a big-endian length of the given number of bytes.
+    fn parse_len(&mut self, size: usize) -> Result<u32> {
+        Ok(match size {
+            1 => u32::from(self.next_byte()?),
+            2 => u32::from(u16::from_be_bytes(self.take_array()?)),
+            _ => u32::from_be_bytes(self.take_array()?),
+        })
+    }
+
+    fn parse_str(&mut self, len: u32) -> Result<&'a str> {
+        let bytes = self.take(len as usize)?;
+        str::from_utf8(bytes).map_err(|_| Error)
+    }
+
+    fn parse_key(&mut self) -> Result<&'a str> {
+        let len = match self.next_byte()? {
+            marker @ 0xA0..=0xBF => u32::from(marker & 0x1F),
+            0xD9 => self.parse_len(1)?,
+            0xDA => self.parse_len(2)?,
+            0xDB => self.parse_len(4)?,
+            _ => return Err(Error),
+        };
+        self.parse_str(len)
+    }
+
+    fn event(&mut self) -> Result<Event<'a>> {
+        Ok(match self.next_byte()? {
+            marker @ 0x00..=0x7F => Nonnegative(u64::from(marker)),
+            marker @ 0x80..=0x8F => MapStart(u32::from(marker & 0x0F)),
+            marker @ 0x90..=0x9F => SeqStart(u32::from(marker & 0x0F)),
+            marker @ 0xA0..=0xBF => Str(self.parse_str(u32::from(marker & 0x1F))?),
+            0xC0 => Null,
+            0xC2 => Bool(false),
+            0xC3 => Bool(true),
+            0xC4 => {
+                let len = self.parse_len(1)?;
+                Bytes(self.take(len as usize)?)
+            }
+            0xC5 => {
+                let len = self.parse_len(2)?;
+                Bytes(self.take(len as usize)?)
+            }
+            0xC6 => {
+                let len = self.parse_len(4)?;
+                Bytes(self.take(len as usize)?)
+            }
+            0xCA => Float(f64::from(f32::from_be_bytes(self.take_array()?))),
+            0xCB => Float(f64::from_be_bytes(self.take_array()?)),
+            0xCC => Nonnegative(u64::from(self.next_byte()?)),
+            0xCD => Nonnegative(u64::from(u16::from_be_bytes(self.take_array()?))),
+            0xCE => Nonnegative(u64::from(u32::from_be_bytes(self.take_array()?))),
+            0xCF => Nonnegative(u64::from_be_bytes(self.take_array()?)),
+            0xD0 => integer(i64::from(i8::from_be_bytes(self.take_array()?))),
+            0xD1 => integer(i64::from(i16::from_be_bytes(self.take_array()?))),
+            0xD2 => integer(i64::from(i32::from_be_bytes(self.take_array()?))),
+            0xD3 => integer(i64::from_be_bytes(self.take_array()?)),
+            0xD9 => {
+                let len = self.parse_len(1)?;
+                Str(self.parse_str(len)?)
+            }
+            0xDA => {
+                let len = self.parse_len(2)?;
+                Str(self.parse_str(len)?)
+            }
+            0xDB => {
+                let len = self.parse_len(4)?;
+                Str(self.parse_str(len)?)
+            }
+            0xDC => SeqStart(self.parse_len(2)?),
+            0xDD => SeqStart(self.parse_len(4)?),
+            0xDE => MapStart(self.parse_len(2)?),
+            0xDF => MapStart(self.parse_len(4)?),
+            marker @ 0xE0..=0xFF => Negative(i64::from(marker as i8)),
+            // Never used, and extension types.
+            0xC1 | 0xC7..=0xC9 | 0xD4..=0xD8 => return Err(Error),
+        })
+    }
+}
+
+// Signed encodings may hold nonnegative values too.
+fn integer(n: i64) -> Event<'static> {
+    if n < 0 {
+        Negative(n)
+    } else {
+        Nonnegative(n as u64)
+    }
+}
+
+enum Event<'a> {
+    Null,
+    Bool(bool),
+    Negative(i64),
+    Nonnegative(u64),
+    Float(f64),
+    Bytes(&'a [u8]),
+    Str(&'a str),
+    SeqStart(u32),
+    MapStart(u32),
+}
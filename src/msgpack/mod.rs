@@ -0,0 +1,39 @@
+//! MessagePack data format.
+//!
+//! This is a compact binary encoding of the same data model as JSON, plus
+//! byte strings, as described by the [MessagePack specification]. Any type
+//! that implements `Serialize` and `Deserialize` for JSON works with
+//! MessagePack too.
+//!
+//! [MessagePack specification]: https://github.com/msgpack/msgpack/blob/master/spec.md
+//!
+//! ```rust
+//! use miniserde::{msgpack, Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize, Debug, PartialEq)]
+//! struct Reading {
+//!     sensor: String,
+//!     celsius: f64,
+//! }
+//!
+//! fn main() -> miniserde::Result<()> {
+//!     let reading = Reading {
+//!         sensor: "t1".to_owned(),
+//!         celsius: 21.5,
+//!     };
+//!
+//!     let bytes = msgpack::to_vec(&reading);
+//!     assert_eq!(bytes.len(), 24);
+//!
+//!     let out: Reading = msgpack::from_slice(&bytes)?;
+//!     assert_eq!(out, reading);
+//!
+//!     Ok(())
+//! }
+//! ```
+
+mod ser;
+pub use self::ser::to_vec;
+
+mod de;
+pub use self::de::from_slice;
@@ -0,0 +1,293 @@
+use crate::json::{self, Value};
+use crate::ser::{Fragment, Map, Seq, Serialize};
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+/// Serialize any serializable type into MessagePack bytes.
+///
+/// Every value takes the fewest bytes which represent it exactly, so a float
+/// may be written in single precision.
+///
+/// Arrays and maps begin with their number of elements. This is taken from
+/// the [`size_hint`][crate::ser::Seq::size_hint] of `ser::Seq` and `ser::Map`
+/// if they provide one, and otherwise the header is filled in once the
+/// elements have been written, which means moving them.
+///
+/// # Panics
+///
+/// If a string, array or map has more than `u32::MAX` elements, which
+/// MessagePack cannot represent.
+///
+/// ```rust
+/// use miniserde::msgpack;
+///
+/// assert_eq!(msgpack::to_vec(&[1.5]), [0x91, 0xCA, 0x3F, 0xC0, 0x00, 0x00]);
+/// ```
+pub fn to_vec<T>(value: &T) -> Vec<u8>
+where
+    T: ?Sized + Serialize,
+{
+    to_vec_impl(&value)
+}
+
+struct Serializer<'a> {
+    stack: Vec<(Layer<'a>, Header)>,
+}
+
+enum Layer<'a> {
+    Seq(Box<dyn Seq + 'a>),
+    Map(Box<dyn Map + 'a>),
+}
+
+// Where the header of an open array or map was written, and how many
+// elements it claims compared to how many there have been.
+struct Header {
+    pos: usize,
+    claimed: usize,
+    count: usize,
+}
+
+impl<'a> Drop for Serializer<'a> {
+    fn drop(&mut self) {
+        // Drop layers in reverse order.
+        while !self.stack.is_empty() {
+            self.stack.pop();
+        }
+    }
+}
+
+const NIL: u8 = 0xC0;
+const FALSE: u8 = 0xC2;
+const TRUE: u8 = 0xC3;
+const FLOAT32: u8 = 0xCA;
+const FLOAT64: u8 = 0xCB;
+
+fn to_vec_impl(value: &dyn Serialize) -> Vec<u8> {
+    let mut out = Vec::new();
+    let mut serializer = Serializer { stack: Vec::new() };
+    let mut fragment = value.begin();
+
+    loop {
+        match fragment {
+            Fragment::Null => out.push(NIL),
+            Fragment::Bool(b) => out.push(if b { TRUE } else { FALSE }),
+            Fragment::Str(s) => write_str(&mut out, &s),
+            Fragment::U64(n) => write_u64(&mut out, n),
+            Fragment::I64(n) => write_i64(&mut out, n),
+            Fragment::F64(n) => {
+                let single = n as f32;
+                if f64::from(single) == n || n.is_nan() {
+                    out.push(FLOAT32);
+                    out.extend_from_slice(&single.to_bits().to_be_bytes());
+                } else {
+                    out.push(FLOAT64);
+                    out.extend_from_slice(&n.to_bits().to_be_bytes());
+                }
+            }
+            Fragment::Bytes(b) => {
+                write_len(&mut out, &BIN, b.len());
+                out.extend_from_slice(&b);
+            }
+            Fragment::RawJson(raw) => {
+                // A Value never contains raw JSON, so this goes only one level
+                // deep. The text of a RawValue is always valid JSON.
+                let value = json::from_str::<Value>(raw.get()).unwrap();
+                out.extend_from_slice(&to_vec_impl(&value));
+            }
+            Fragment::Seq(mut seq) => {
+                let header = write_header(&mut out, &ARRAY, seq.size_hint());
+                // invariant: `seq` must outlive `first`
+                match unsafe { extend_lifetime!(seq.next() as Option<&dyn Serialize>) } {
+                    Some(first) => {
+                        serializer.stack.push((Layer::Seq(seq), header.counted()));
+                        fragment = first.begin();
+                        continue;
+                    }
+                    None => header.finish(&mut out, &ARRAY),
+                }
+            }
+            Fragment::Map(mut map) => {
+                let header = write_header(&mut out, &MAP, map.size_hint());
+                // invariant: `map` must outlive `first`
+                match unsafe { extend_lifetime!(map.next() as Option<(Cow<str>, &dyn Serialize)>) }
+                {
+                    Some((key, first)) => {
+                        write_str(&mut out, &key);
+                        serializer.stack.push((Layer::Map(map), header.counted()));
+                        fragment = first.begin();
+                        continue;
+                    }
+                    None => header.finish(&mut out, &MAP),
+                }
+            }
+        }
+
+        loop {
+            match serializer.stack.last_mut() {
+                Some((Layer::Seq(seq), header)) => {
+                    // invariant: `seq` must outlive `next`
+                    match unsafe { extend_lifetime!(seq.next() as Option<&dyn Serialize>) } {
+                        Some(next) => {
+                            header.count += 1;
+                            fragment = next.begin();
+                            break;
+                        }
+                        None => header.finish(&mut out, &ARRAY),
+                    }
+                }
+                Some((Layer::Map(map), header)) => {
+                    // invariant: `map` must outlive `next`
+                    match unsafe {
+                        extend_lifetime!(map.next() as Option<(Cow<str>, &dyn Serialize)>)
+                    } {
+                        Some((key, next)) => {
+                            header.count += 1;
+                            write_str(&mut out, &key);
+                            fragment = next.begin();
+                            break;
+                        }
+                        None => header.finish(&mut out, &MAP),
+                    }
+                }
+                None => return out,
+            }
+            serializer.stack.pop();
+        }
+    }
+}
+
+// The markers for one kind of length-prefixed item, starting with the fixed
+// size form if there is one, and how many elements that form can hold.
+struct Markers {
+    fixed: Option<(u8, usize)>,
+    len8: Option<u8>,
+    len16: u8,
+    len32: u8,
+}
+
+const STR: Markers = Markers {
+    fixed: Some((0xA0, 32)),
+    len8: Some(0xD9),
+    len16: 0xDA,
+    len32: 0xDB,
+};
+
+const BIN: Markers = Markers {
+    fixed: None,
+    len8: Some(0xC4),
+    len16: 0xC5,
+    len32: 0xC6,
+};
+
+const ARRAY: Markers = Markers {
+    fixed: Some((0x90, 16)),
+    len8: None,
+    len16: 0xDC,
+    len32: 0xDD,
+};
+
+const MAP: Markers = Markers {
+    fixed: Some((0x80, 16)),
+    len8: None,
+    len16: 0xDE,
+    len32: 0xDF,
+};
+
+fn write_len(out: &mut Vec<u8>, markers: &Markers, len: usize) {
+    if let Some((marker, max)) = markers.fixed {
+        if len < max {
+            out.push(marker | len as u8);
+            return;
+        }
+    }
+    if let (Some(marker), Ok(len)) = (markers.len8, u8::try_from(len)) {
+        out.push(marker);
+        out.push(len);
+        return;
+    }
+    if let Ok(len) = u16::try_from(len) {
+        out.push(markers.len16);
+        out.extend_from_slice(&len.to_be_bytes());
+    } else if let Ok(len) = u32::try_from(len) {
+        out.push(markers.len32);
+        out.extend_from_slice(&len.to_be_bytes());
+    } else {
+        panic!("length {} is too large for MessagePack", len);
+    }
+}
+
+fn write_header(out: &mut Vec<u8>, markers: &Markers, hint: Option<usize>) -> Header {
+    let claimed = hint.unwrap_or(0);
+    let pos = out.len();
+    write_len(out, markers, claimed);
+    Header {
+        pos,
+        claimed,
+        count: 0,
+    }
+}
+
+impl Header {
+    // After the first element has been handed out.
+    fn counted(mut self) -> Self {
+        self.count = 1;
+        self
+    }
+
+    // Rewrites the header if the size hint was missing or wrong.
+    fn finish(&self, out: &mut Vec<u8>, markers: &Markers) {
+        if self.count == self.claimed {
+            return;
+        }
+        let mut old = Vec::new();
+        write_len(&mut old, markers, self.claimed);
+        let mut new = Vec::new();
+        write_len(&mut new, markers, self.count);
+        out.splice(self.pos..self.pos + old.len(), new);
+    }
+}
+
+fn write_str(out: &mut Vec<u8>, s: &str) {
+    write_len(out, &STR, s.len());
+    out.extend_from_slice(s.as_bytes());
+}
+
+fn write_u64(out: &mut Vec<u8>, n: u64) {
+    if n < 0x80 {
+        out.push(n as u8);
+    } else if let Ok(n) = u8::try_from(n) {
+        out.push(0xCC);
+        out.push(n);
+    } else if let Ok(n) = u16::try_from(n) {
+        out.push(0xCD);
+        out.extend_from_slice(&n.to_be_bytes());
+    } else if let Ok(n) = u32::try_from(n) {
+        out.push(0xCE);
+        out.extend_from_slice(&n.to_be_bytes());
+    } else {
+        out.push(0xCF);
+        out.extend_from_slice(&n.to_be_bytes());
+    }
+}
+
+fn write_i64(out: &mut Vec<u8>, n: i64) {
+    if n >= 0 {
+        write_u64(out, n as u64);
+    } else if n >= -32 {
+        // Negative fixint.
+        out.push(n as u8);
+    } else if let Ok(n) = i8::try_from(n) {
+        out.push(0xD0);
+        out.extend_from_slice(&n.to_be_bytes());
+    } else if let Ok(n) = i16::try_from(n) {
+        out.push(0xD1);
+        out.extend_from_slice(&n.to_be_bytes());
+    } else if let Ok(n) = i32::try_from(n) {
+        out.push(0xD2);
+        out.extend_from_slice(&n.to_be_bytes());
+    } else {
+        out.push(0xD3);
+        out.extend_from_slice(&n.to_be_bytes());
+    }
+}
@@ -139,6 +139,10 @@ where
                     _ => None,
                 }
             }
+
+            fn size_hint(&self) -> Option<usize> {
+                Some(2usize.saturating_sub(self.state))
+            }
         }
 
         Fragment::Seq(Box::new(TupleStream {
@@ -195,6 +199,10 @@ where
                 let (k, v) = self.0.next()?;
                 Some((Cow::Owned(k.to_string()), v as &dyn Serialize))
             }
+
+            fn size_hint(&self) -> Option<usize> {
+                Some(self.0.len())
+            }
         }
 
         Fragment::Map(Box::new(HashMapStream(self.iter())))
@@ -226,6 +234,10 @@ impl private {
                 let element = self.0.next()?;
                 Some(element)
             }
+
+            fn size_hint(&self) -> Option<usize> {
+                Some(self.0.len())
+            }
         }
 
         Fragment::Seq(Box::new(SliceStream(slice.iter())))
@@ -250,6 +262,10 @@ impl private {
                 let (k, v) = self.0.next()?;
                 Some((Cow::Owned(k.to_string()), v as &dyn Serialize))
             }
+
+            fn size_hint(&self) -> Option<usize> {
+                Some(self.0.len())
+            }
         }
 
         Fragment::Map(Box::new(BTreeMapStream(map.iter())))
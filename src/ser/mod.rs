@@ -94,6 +94,7 @@
 
 mod impls;
 
+use crate::json::RawValue;
 use alloc::borrow::Cow;
 use alloc::boxed::Box;
 
@@ -109,6 +110,14 @@ pub enum Fragment<'a> {
     F64(f64),
     Seq(Box<dyn Seq + 'a>),
     Map(Box<dyn Map + 'a>),
+    /// A string of arbitrary bytes. Binary formats write it as such, while
+    /// JSON writes it as a base64 string, or optionally as an array of
+    /// numbers.
+    Bytes(Cow<'a, [u8]>),
+    /// JSON text to be written to the output verbatim. Other formats write the
+    /// value it holds instead, which is always valid, as a `RawValue` is only
+    /// ever made from checked text.
+    RawJson(&'a RawValue),
 }
 
 /// Trait for data structures that can be serialized to a JSON string.
@@ -123,6 +132,15 @@ pub trait Serialize {
 /// [Refer to the module documentation for examples.][crate::ser]
 pub trait Seq {
     fn next(&mut self) -> Option<&dyn Serialize>;
+
+    /// The number of elements remaining to be returned by `next`, if known.
+    ///
+    /// Formats which write the length ahead of the elements call this before
+    /// the first call to `next`, and use it to avoid rewriting their output.
+    /// Others may use it to reserve space.
+    fn size_hint(&self) -> Option<usize> {
+        None
+    }
 }
 
 /// Trait that can iterate key-value entries of a map or struct.
@@ -130,4 +148,13 @@ pub trait Seq {
 /// [Refer to the module documentation for examples.][crate::ser]
 pub trait Map {
     fn next(&mut self) -> Option<(Cow<str>, &dyn Serialize)>;
+
+    /// The number of entries remaining to be returned by `next`, if known.
+    ///
+    /// Formats which write the length ahead of the entries call this before
+    /// the first call to `next`, and use it to avoid rewriting their output.
+    /// Others may use it to reserve space.
+    fn size_hint(&self) -> Option<usize> {
+        None
+    }
 }
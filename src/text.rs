@@ -0,0 +1,29 @@
+use crate::de::Visitor;
+use crate::error::{Error, Result};
+
+// Offers text which the visitor did not accept as a string to it as whatever
+// the text spells instead: null if it is empty, otherwise a bool or a number.
+// Formats whose values are all text use this to fill typed fields.
+pub(crate) fn visit_scalar(visitor: &mut dyn Visitor, text: &str) -> Result<()> {
+    match text {
+        "" => visitor.null(),
+        "true" => visitor.boolean(true),
+        "false" => visitor.boolean(false),
+        _ => {
+            if let Ok(n) = text.parse::<u64>() {
+                visitor.nonnegative(n)
+            } else if let Ok(n) = text.parse::<i64>() {
+                // Only "-0" and the like get here without being negative.
+                if n < 0 {
+                    visitor.negative(n)
+                } else {
+                    visitor.nonnegative(0)
+                }
+            } else if let Ok(n) = text.parse::<f64>() {
+                visitor.float(n)
+            } else {
+                Err(Error)
+            }
+        }
+    }
+}
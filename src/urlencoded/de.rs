@@ -0,0 +1,155 @@
+use crate::de::{Deserialize, Visitor};
+use crate::error::{Error, Result};
+use crate::text;
+use alloc::borrow::Cow;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::str;
+
+/// Deserialize `application/x-www-form-urlencoded` text into a struct or map.
+///
+/// Keys and values are percent-decoded, with `+` standing for a space. A key
+/// which appears more than once deserializes into a sequence such as a
+/// `Vec<T>`, as does a key appearing once whose field does not accept a
+/// single value.
+///
+/// Since everything in the input is text, each value is first offered to the
+/// field as a string. If the field does not accept a string, the value is
+/// parsed as a bool, an integer or a float instead, and an empty value is
+/// offered as null.
+///
+/// ```rust
+/// use miniserde::{urlencoded, Deserialize};
+///
+/// #[derive(Deserialize)]
+/// struct Login {
+///     user: String,
+///     remember: bool,
+///     attempts: Option<u8>,
+/// }
+///
+/// fn main() -> miniserde::Result<()> {
+///     let login: Login = urlencoded::from_str("user=jo&remember=true&attempts=")?;
+///     assert_eq!(login.user, "jo");
+///     assert!(login.remember);
+///     assert_eq!(login.attempts, None);
+///
+///     Ok(())
+/// }
+/// ```
+pub fn from_str<'de, T>(s: &'de str) -> Result<T>
+where
+    T: Deserialize<'de>,
+{
+    let mut out = None;
+    from_str_impl(s, T::begin(&mut out))?;
+    out.ok_or(Error)
+}
+
+// A distinct key, in order of first appearance, with all of its values.
+struct Entry<'de> {
+    key: Cow<'de, str>,
+    values: Vec<Cow<'de, [u8]>>,
+}
+
+fn from_str_impl<'de>(s: &'de str, visitor: &mut dyn Visitor<'de>) -> Result<()> {
+    let mut entries: Vec<Entry> = Vec::new();
+    let mut index: BTreeMap<Cow<str>, usize> = BTreeMap::new();
+    for pair in s.split('&') {
+        if pair.is_empty() {
+            continue;
+        }
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        let key = match decode(key)? {
+            Cow::Borrowed(key) => Cow::Borrowed(str::from_utf8(key).map_err(|_| Error)?),
+            Cow::Owned(key) => Cow::Owned(String::from_utf8(key).map_err(|_| Error)?),
+        };
+        let value = decode(value)?;
+        match index.get(&key) {
+            Some(&i) => entries[i].values.push(value),
+            None => {
+                index.insert(key.clone(), entries.len());
+                entries.push(Entry {
+                    key,
+                    values: alloc::vec![value],
+                });
+            }
+        }
+    }
+
+    let mut map = visitor.map()?;
+    for Entry { key, values } in &entries {
+        let visitor = map.key(key)?;
+        if values.len() == 1 && visit_text(visitor, &values[0]).is_ok() {
+            continue;
+        }
+        let mut seq = visitor.seq()?;
+        for value in values {
+            visit_text(seq.element()?, value)?;
+        }
+        seq.finish()?;
+    }
+    map.finish()
+}
+
+// Takes the Cow itself because only borrowed text can be handed out as a
+// borrowed string.
+#[allow(clippy::ptr_arg)]
+fn visit_text<'de>(visitor: &mut dyn Visitor<'de>, value: &Cow<'de, [u8]>) -> Result<()> {
+    let result = match value {
+        Cow::Borrowed(bytes) => match str::from_utf8(bytes) {
+            Ok(text) => visitor.borrowed_string(text),
+            Err(_) => return visitor.bytes(bytes),
+        },
+        Cow::Owned(bytes) => match str::from_utf8(bytes) {
+            Ok(text) => visitor.string(text),
+            Err(_) => return visitor.bytes(bytes),
+        },
+    };
+    if result.is_ok() {
+        return result;
+    }
+
+    // Checked above.
+    let text = unsafe { str::from_utf8_unchecked(value) };
+    text::visit_scalar(visitor, text)
+}
+
+// Undoes percent-encoding, borrowing from the input if there is nothing to
+// decode.
+fn decode(s: &str) -> Result<Cow<[u8]>> {
+    let bytes = s.as_bytes();
+    if !bytes.iter().any(|&b| b == b'%' || b == b'+') {
+        return Ok(Cow::Borrowed(bytes));
+    }
+
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => out.push(b' '),
+            b'%' => {
+                let hi = bytes.get(i + 1).and_then(|&b| hex_digit(b));
+                let lo = bytes.get(i + 2).and_then(|&b| hex_digit(b));
+                let (Some(hi), Some(lo)) = (hi, lo) else {
+                    return Err(Error);
+                };
+                out.push(hi << 4 | lo);
+                i += 2;
+            }
+            b => out.push(b),
+        }
+        i += 1;
+    }
+    Ok(Cow::Owned(out))
+}
+
+fn hex_digit(b: u8) -> Option<u8> {
+    match b {
+        b'0'..=b'9' => Some(b - b'0'),
+        b'A'..=b'F' => Some(b - b'A' + 10),
+        b'a'..=b'f' => Some(b - b'a' + 10),
+        _ => None,
+    }
+}
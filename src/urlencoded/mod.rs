@@ -0,0 +1,39 @@
+//! The `application/x-www-form-urlencoded` format of HTML forms and URL
+//! query strings.
+//!
+//! The data is a flat list of `key=value` pairs, so only structs and maps
+//! whose values are scalars, options or sequences of scalars can be
+//! represented. A sequence is written as the same key repeated for each
+//! element.
+//!
+//! ```rust
+//! use miniserde::{urlencoded, Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize, Debug, PartialEq)]
+//! struct Search {
+//!     q: String,
+//!     page: u32,
+//!     tag: Vec<String>,
+//!     exact: Option<bool>,
+//! }
+//!
+//! fn main() -> miniserde::Result<()> {
+//!     let query = "q=caf%C3%A9+au+lait&page=2&tag=drinks&tag=hot";
+//!
+//!     let search: Search = urlencoded::from_str(query)?;
+//!     assert_eq!(search.q, "café au lait");
+//!     assert_eq!(search.page, 2);
+//!     assert_eq!(search.tag, ["drinks", "hot"]);
+//!     assert_eq!(search.exact, None);
+//!
+//!     assert_eq!(urlencoded::to_string(&search)?, query);
+//!
+//!     Ok(())
+//! }
+//! ```
+
+mod ser;
+pub use self::ser::to_string;
+
+mod de;
+pub use self::de::from_str;
@@ -0,0 +1,96 @@
+use crate::error::{Error, Result};
+use crate::ser::{Fragment, Serialize};
+use alloc::string::String;
+
+/// Serialize a struct or map into `application/x-www-form-urlencoded` text.
+///
+/// Entries whose value is null, such as an `Option` which is `None`, are
+/// left out. Each element of a sequence is written as a separate pair with
+/// the same key. This fails on any other nesting, and on floats which are
+/// `NaN` or infinite.
+///
+/// ```rust
+/// use miniserde::urlencoded;
+/// use std::collections::BTreeMap;
+///
+/// let mut map = BTreeMap::new();
+/// map.insert("name", "Jo & Al");
+/// map.insert("path", "/a/b");
+///
+/// let query = urlencoded::to_string(&map).unwrap();
+/// assert_eq!(query, "name=Jo+%26+Al&path=%2Fa%2Fb");
+/// ```
+pub fn to_string<T>(value: &T) -> Result<String>
+where
+    T: ?Sized + Serialize,
+{
+    to_string_impl(&value)
+}
+
+fn to_string_impl(value: &dyn Serialize) -> Result<String> {
+    let mut out = String::new();
+    let Fragment::Map(mut map) = value.begin() else {
+        return Err(Error);
+    };
+
+    while let Some((key, value)) = map.next() {
+        match value.begin() {
+            Fragment::Seq(mut seq) => {
+                while let Some(element) = seq.next() {
+                    write_pair(&key, element.begin(), &mut out)?;
+                }
+            }
+            fragment => write_pair(&key, fragment, &mut out)?,
+        }
+    }
+
+    Ok(out)
+}
+
+fn write_pair(key: &str, fragment: Fragment, out: &mut String) -> Result<()> {
+    if let Fragment::Null = fragment {
+        return Ok(());
+    }
+
+    if !out.is_empty() {
+        out.push('&');
+    }
+    encode(key.as_bytes(), out);
+    out.push('=');
+
+    match fragment {
+        Fragment::Null => {}
+        Fragment::Bool(b) => out.push_str(if b { "true" } else { "false" }),
+        Fragment::Str(s) => encode(s.as_bytes(), out),
+        Fragment::Bytes(b) => encode(&b, out),
+        Fragment::U64(n) => out.push_str(itoa::Buffer::new().format(n)),
+        Fragment::I64(n) => out.push_str(itoa::Buffer::new().format(n)),
+        Fragment::F64(n) => {
+            if !n.is_finite() {
+                return Err(Error);
+            }
+            out.push_str(zmij::Buffer::new().format_finite(n));
+        }
+        Fragment::Seq(_) | Fragment::Map(_) | Fragment::RawJson(_) => return Err(Error),
+    }
+    Ok(())
+}
+
+// Percent-encodes everything except the characters which the form encoding
+// algorithm of the URL Standard leaves alone, and writes spaces as `+`.
+fn encode(bytes: &[u8], out: &mut String) {
+    static HEX_DIGITS: [u8; 16] = *b"0123456789ABCDEF";
+    for &byte in bytes {
+        match byte {
+            b'0'..=b'9' | b'A'..=b'Z' | b'a'..=b'z' | b'*' | b'-' | b'.' | b'_' => {
+                out.push(byte as char);
+            }
+            b' ' => out.push('+'),
+            _ => {
+                out.push('%');
+                out.push(HEX_DIGITS[(byte >> 4) as usize] as char);
+                out.push(HEX_DIGITS[(byte & 0xF) as usize] as char);
+            }
+        }
+    }
+}
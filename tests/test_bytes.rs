@@ -0,0 +1,103 @@
+use miniserde::json::{self, BytesFormat, SerializeOptions, Value};
+use miniserde::{cbor, msgpack, ByteBuf, Bytes, Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Packet {
+    id: u8,
+    payload: ByteBuf,
+}
+
+fn packet(payload: &[u8]) -> Packet {
+    Packet {
+        id: 7,
+        payload: ByteBuf::from(payload.to_vec()),
+    }
+}
+
+#[test]
+fn test_base64() {
+    // Test vectors from RFC 4648.
+    let cases = [
+        ("", ""),
+        ("f", "Zg=="),
+        ("fo", "Zm8="),
+        ("foo", "Zm9v"),
+        ("foob", "Zm9vYg=="),
+        ("fooba", "Zm9vYmE="),
+        ("foobar", "Zm9vYmFy"),
+    ];
+    for (raw, encoded) in cases {
+        let j = format!("\"{}\"", encoded);
+        assert_eq!(json::to_string(Bytes::new(raw.as_bytes())), j);
+        let buf: ByteBuf = json::from_str(&j).unwrap();
+        assert_eq!(*buf, raw.as_bytes());
+    }
+
+    let all: Vec<u8> = (0..=255).collect();
+    let j = json::to_string(Bytes::new(&all));
+    assert!(j.contains("+/"));
+    assert_eq!(json::from_str::<ByteBuf>(&j).unwrap().into_vec(), all);
+
+    for invalid in ["Zg=", "Zg", "Z===", "Zg==Zg==", "Zm9v!A==", "Zm=v"] {
+        let j = format!("\"{}\"", invalid);
+        json::from_str::<ByteBuf>(&j).unwrap_err();
+    }
+}
+
+#[test]
+fn test_json_formats() {
+    let packet = packet(&[0, 128, 255]);
+    assert_eq!(json::to_string(&packet), r#"{"id":7,"payload":"AID/"}"#);
+
+    let options = SerializeOptions::new().bytes(BytesFormat::Array);
+    let j = json::to_string_with(&packet, &options).unwrap();
+    assert_eq!(j, r#"{"id":7,"payload":[0,128,255]}"#);
+
+    // Both are accepted back.
+    assert_eq!(json::from_str::<Packet>(&j).unwrap(), packet);
+    let j = json::to_string(&packet);
+    assert_eq!(json::from_str::<Packet>(&j).unwrap(), packet);
+
+    json::from_str::<ByteBuf>("[256]").unwrap_err();
+    json::from_str::<ByteBuf>("[-1]").unwrap_err();
+
+    // A plain Vec<u8> is still an array of numbers.
+    assert_eq!(json::to_string(&vec![1u8, 2]), "[1,2]");
+}
+
+#[test]
+fn test_value() {
+    let packet = packet(b"hi!");
+    let value = json::to_value(&packet);
+    assert_eq!(value["payload"], Value::from("aGkh"));
+    assert_eq!(json::from_value::<Packet>(value).unwrap(), packet);
+
+    assert_eq!(
+        json::to_canonical_string(&packet).unwrap(),
+        r#"{"id":7,"payload":"aGkh"}"#,
+    );
+}
+
+#[test]
+fn test_binary_formats() {
+    let packet = packet(&[1, 2, 3]);
+
+    let bytes = cbor::to_vec(&packet);
+    assert!(bytes.windows(4).any(|w| w == [0x43, 1, 2, 3]));
+    assert_eq!(cbor::from_slice::<Packet>(&bytes).unwrap(), packet);
+
+    let bytes = msgpack::to_vec(&packet);
+    assert!(bytes.windows(5).any(|w| w == [0xc4, 3, 1, 2, 3]));
+    assert_eq!(msgpack::from_slice::<Packet>(&bytes).unwrap(), packet);
+
+    // Into a Value, bytes look the same as in JSON.
+    let value: Value = msgpack::from_slice(&bytes).unwrap();
+    assert_eq!(value, json::to_value(&packet));
+}
+
+#[test]
+fn test_option_and_box() {
+    let bytes = cbor::to_vec(Bytes::new(&[9, 9]));
+    let out: Option<Box<ByteBuf>> = cbor::from_slice(&bytes).unwrap();
+    assert_eq!(**out.unwrap(), [9, 9]);
+}
@@ -0,0 +1,133 @@
+use miniserde::json::{self, RawValue, Value};
+use miniserde::Serialize;
+use std::collections::HashMap;
+
+fn canonical(j: &str) -> String {
+    let value: Value = json::from_str(j).unwrap();
+    json::to_canonical_string(&value).unwrap()
+}
+
+#[test]
+fn test_rfc8785_example() {
+    // Section 3.2.2 of RFC 8785, except for its first number which needs
+    // correctly rounded parsing; see test_numbers.
+    let j = r#"{
+        "numbers": [1E30, 4.50, 2e-3, 0.000000000000000000000000001],
+        "string": "\u20ac$\u000F\u000aA'\u0042\u0022\u005c\\\"\/",
+        "literals": [null, true, false]
+    }"#;
+    let expected = concat!(
+        r#"{"literals":[null,true,false],"#,
+        r#""numbers":[1e+30,4.5,0.002,1e-27],"#,
+        "\"string\":\"\u{20ac}$\\u000f\\nA'B\\\"\\\\\\\\\\\"/\"}",
+    );
+    assert_eq!(canonical(j), expected);
+}
+
+#[test]
+fn test_sort_utf16() {
+    // Sorted by UTF-16 code units, which puts U+1F600 (a surrogate pair)
+    // before U+FB33 even though it is greater as a char.
+    let j = "{\"\u{fb33}\": 1, \"\u{1f600}\": 2, \"\u{80}\": 3, \"1\": 4, \"\\r\": 5}";
+    let expected = "{\"\\r\":5,\"1\":4,\"\u{80}\":3,\"\u{1f600}\":2,\"\u{fb33}\":1}";
+    assert_eq!(canonical(j), expected);
+}
+
+#[test]
+fn test_nested() {
+    let j = r#"{"b": [{"z": 1, "y": {"d": [], "c": {}}}], "a": {"x": null, "w": [1, {"v": 2, "u": 3}]}}"#;
+    let expected = r#"{"a":{"w":[1,{"u":3,"v":2}],"x":null},"b":[{"y":{"c":{},"d":[]},"z":1}]}"#;
+    assert_eq!(canonical(j), expected);
+}
+
+#[test]
+fn test_deeply_nested() {
+    let depth = if cfg!(miri) { 40 } else { 100_000 };
+    let j = r#"{"b":1,"a":"#.repeat(depth) + "null" + &"}".repeat(depth);
+    let expected = r#"{"a":"#.repeat(depth) + "null" + &r#","b":1}"#.repeat(depth);
+    assert_eq!(canonical(&j), expected);
+}
+
+#[test]
+fn test_hashmap_and_derive() {
+    #[derive(Serialize)]
+    struct Signed {
+        version: u8,
+        body: HashMap<String, i64>,
+        algorithm: &'static str,
+    }
+
+    let mut body = HashMap::new();
+    for (i, key) in ["q", "w", "e", "r", "t", "y", "u", "i", "o", "p"]
+        .iter()
+        .enumerate()
+    {
+        body.insert((*key).to_owned(), -(i as i64));
+    }
+    let signed = Signed {
+        version: 1,
+        body,
+        algorithm: "ed25519",
+    };
+    let expected = concat!(
+        r#"{"algorithm":"ed25519","body":{"e":-2,"i":-7,"o":-8,"p":-9,"q":0,"#,
+        r#""r":-3,"t":-4,"u":-6,"w":-1,"y":-5},"version":1}"#,
+    );
+    assert_eq!(json::to_canonical_string(&signed).unwrap(), expected);
+}
+
+#[test]
+fn test_numbers() {
+    let cases = [
+        (0.0, "0"),
+        (-0.0, "0"),
+        (1.0, "1"),
+        (-1.5, "-1.5"),
+        (100.0, "100"),
+        (123456789012345680000.0, "123456789012345680000"),
+        (1e21, "1e+21"),
+        (1.5e300, "1.5e+300"),
+        (0.000001, "0.000001"),
+        (0.0000001, "1e-7"),
+        (1.25e-7, "1.25e-7"),
+        (f64::MAX, "1.7976931348623157e+308"),
+        (f64::MIN_POSITIVE, "2.2250738585072014e-308"),
+        (5e-324, "5e-324"),
+        (0.1 + 0.2, "0.30000000000000004"),
+        (333_333_333.333_333_3, "333333333.3333333"),
+        (9007199254740992.0, "9007199254740992"),
+    ];
+    for (n, expected) in cases {
+        assert_eq!(json::to_canonical_string(&n).unwrap(), expected);
+    }
+
+    // Integers which are exactly an ECMAScript number, written the way it
+    // would be.
+    let cases = [
+        (9007199254740992u64, "9007199254740992"),
+        (1 << 60, "1152921504606847000"),
+    ];
+    for (n, expected) in cases {
+        assert_eq!(json::to_canonical_string(&n).unwrap(), expected);
+    }
+    assert_eq!(
+        json::to_canonical_string(&i64::MIN).unwrap(),
+        "-9223372036854776000"
+    );
+
+    // And ones which are not, rather than writing a different number.
+    json::to_canonical_string(&9007199254740993u64).unwrap_err();
+    json::to_canonical_string(&-9007199254740993i64).unwrap_err();
+    json::to_canonical_string(&u64::MAX).unwrap_err();
+    json::to_canonical_string(&i64::MAX).unwrap_err();
+
+    json::to_canonical_string(&f64::NAN).unwrap_err();
+    json::to_canonical_string(&[f64::INFINITY]).unwrap_err();
+}
+
+#[test]
+fn test_raw_value() {
+    let raw = RawValue::from_string(r#" {"b": 1.50, "a": "\u0041"} "#.to_owned()).unwrap();
+    let j = json::to_canonical_string(&[raw]).unwrap();
+    assert_eq!(j, r#"[{"a":"A","b":1.5}]"#);
+}
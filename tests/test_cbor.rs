@@ -0,0 +1,226 @@
+use miniserde::json::{self, Value};
+use miniserde::{cbor, Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+fn hex(s: &str) -> Vec<u8> {
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+fn decode(s: &str) -> Value {
+    cbor::from_slice(&hex(s)).unwrap()
+}
+
+#[test]
+fn test_encode_integers() {
+    // Appendix A of RFC 8949.
+    let cases: &[(i64, &str)] = &[
+        (0, "00"),
+        (23, "17"),
+        (24, "1818"),
+        (100, "1864"),
+        (1000, "1903e8"),
+        (1000000, "1a000f4240"),
+        (1000000000000, "1b000000e8d4a51000"),
+        (-1, "20"),
+        (-10, "29"),
+        (-100, "3863"),
+        (-1000, "3903e7"),
+        (i64::MIN, "3b7fffffffffffffff"),
+    ];
+    for &(n, expected) in cases {
+        assert_eq!(cbor::to_vec(&n), hex(expected), "{}", n);
+        assert_eq!(cbor::from_slice::<i64>(&hex(expected)).unwrap(), n);
+    }
+    assert_eq!(cbor::to_vec(&u64::MAX), hex("1bffffffffffffffff"));
+
+    // Below i64::MIN, so only a float can hold it.
+    assert_eq!(
+        decode("3bffffffffffffffff").as_f64(),
+        Some(-18446744073709551616.0)
+    );
+}
+
+#[test]
+fn test_encode_floats() {
+    let cases: &[(f64, &str)] = &[
+        (0.0, "f90000"),
+        (-0.0, "f98000"),
+        (1.0, "f93c00"),
+        (1.1, "fb3ff199999999999a"),
+        (1.5, "f93e00"),
+        (65504.0, "f97bff"),
+        (100000.0, "fa47c35000"),
+        (3.4028234663852886e+38, "fa7f7fffff"),
+        (1.0e+300, "fb7e37e43c8800759c"),
+        (5.960464477539063e-8, "f90001"),
+        (0.00006103515625, "f90400"),
+        (-4.0, "f9c400"),
+        (-4.1, "fbc010666666666666"),
+        (f64::INFINITY, "f97c00"),
+        (f64::NEG_INFINITY, "f9fc00"),
+        (f64::NAN, "f97e00"),
+    ];
+    for &(n, expected) in cases {
+        assert_eq!(cbor::to_vec(&n), hex(expected), "{}", n);
+        let out: f64 = cbor::from_slice(&hex(expected)).unwrap();
+        assert!(out == n || out.is_nan() && n.is_nan(), "{}", n);
+        assert_eq!(out.is_sign_negative(), n.is_sign_negative());
+    }
+
+    // Wider than needed is accepted too.
+    assert_eq!(decode("fa3fc00000").as_f64(), Some(1.5));
+    assert_eq!(decode("fb3ff8000000000000").as_f64(), Some(1.5));
+}
+
+#[test]
+fn test_simple() {
+    assert_eq!(cbor::to_vec(&false), [0xf4]);
+    assert_eq!(cbor::to_vec(&true), [0xf5]);
+    assert_eq!(cbor::to_vec(&()), [0xf6]);
+    assert_eq!(cbor::to_vec(&None::<u8>), [0xf6]);
+    assert_eq!(decode("f7"), Value::Null);
+    cbor::from_slice::<Value>(&hex("f0")).unwrap_err();
+}
+
+#[test]
+fn test_strings() {
+    assert_eq!(cbor::to_vec(""), [0x60]);
+    assert_eq!(cbor::to_vec("IETF"), hex("6449455446"));
+    assert_eq!(cbor::to_vec("\u{fc}"), hex("62c3bc"));
+
+    // Definite length strings are borrowed from the input.
+    let bytes = hex("6449455446");
+    let s: &str = cbor::from_slice(&bytes).unwrap();
+    assert_eq!(s, "IETF");
+
+    // Indefinite length, in chunks.
+    assert_eq!(
+        decode("7f657374726561646d696e67ff"),
+        Value::from("streaming")
+    );
+    cbor::from_slice::<&str>(&hex("7f657374726561646d696e67ff")).unwrap_err();
+    // Chunks of the wrong type.
+    cbor::from_slice::<String>(&hex("7f4161ff")).unwrap_err();
+    // Invalid UTF-8.
+    cbor::from_slice::<String>(&hex("62c328")).unwrap_err();
+    // Truncated.
+    cbor::from_slice::<String>(&hex("64494554")).unwrap_err();
+}
+
+#[test]
+fn test_bytes() {
+    struct Blob(Vec<u8>);
+
+    impl Serialize for Blob {
+        fn begin(&self) -> miniserde::ser::Fragment<'_> {
+            miniserde::ser::Fragment::Bytes(self.0.as_slice().into())
+        }
+    }
+
+    let blob = Blob(vec![1, 2, 3, 4]);
+    assert_eq!(cbor::to_vec(&blob), hex("4401020304"));
+    assert_eq!(json::to_string(&blob), r#""AQIDBA==""#);
+
+    assert_eq!(decode("4401020304"), Value::from("AQIDBA=="));
+    assert_eq!(decode("5f42010243030405ff"), Value::from("AQIDBAU="));
+    cbor::from_slice::<Vec<u8>>(&hex("4401020304")).unwrap_err();
+}
+
+#[test]
+fn test_arrays_and_maps() {
+    let cases = [
+        ("[]", "80", "9fff"),
+        ("[1, 2, 3]", "83010203", "9f010203ff"),
+        (
+            "[1, [2, 3], [4, 5]]",
+            "8301820203820405",
+            "9f01820203820405ff",
+        ),
+        ("{}", "a0", "bfff"),
+        (
+            r#"{"a": 1, "b": [2, 3]}"#,
+            "a26161016162820203",
+            "bf61610161629f0203ffff",
+        ),
+        (
+            r#"["a", {"b": "c"}]"#,
+            "826161a161626163",
+            "826161bf61626163ff",
+        ),
+    ];
+    for (j, definite, indefinite) in cases {
+        let value: Value = json::from_str(j).unwrap();
+        assert_eq!(decode(definite), value);
+        assert_eq!(decode(indefinite), value);
+    }
+
+    let j = r#"{"a": 1, "b": [2, 3]}"#;
+    let value: Value = json::from_str(j).unwrap();
+    assert_eq!(
+        cbor::to_vec(&value),
+        hex("bf6161016162 9f0203ff ff".replace(' ', "").as_str())
+    );
+
+    // Wrong length, a break where an item is expected, and non-text keys.
+    cbor::from_slice::<Value>(&hex("830102")).unwrap_err();
+    cbor::from_slice::<Value>(&hex("8301020304")).unwrap_err();
+    cbor::from_slice::<Value>(&hex("9f01")).unwrap_err();
+    cbor::from_slice::<Value>(&hex("a1ff")).unwrap_err();
+    cbor::from_slice::<Value>(&hex("a10102")).unwrap_err();
+}
+
+#[test]
+fn test_tags() {
+    assert_eq!(
+        decode("c074323031332d30332d32315432303a30343a30305a"),
+        Value::from("2013-03-21T20:04:00Z")
+    );
+    assert_eq!(decode("c11a514b67b0").as_u64(), Some(1363896240));
+    assert_eq!(decode("c1fb41d452d9ec200000").as_f64(), Some(1363896240.5));
+    // Other tags are ignored.
+    assert_eq!(
+        decode("d82076687474703a2f2f7777772e6578616d706c652e636f6d"),
+        Value::from("http://www.example.com")
+    );
+
+    cbor::from_slice::<Value>(&hex("c001")).unwrap_err();
+    cbor::from_slice::<Value>(&hex("c16161")).unwrap_err();
+}
+
+#[test]
+fn test_derive() {
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Device {
+        id: u32,
+        name: String,
+        readings: Vec<f64>,
+        location: Option<(f32, f32)>,
+        tags: BTreeMap<String, i8>,
+    }
+
+    let mut tags = BTreeMap::new();
+    tags.insert("floor".to_owned(), -1);
+    let device = Device {
+        id: 70000,
+        name: "probe".to_owned(),
+        readings: vec![20.25, 1e-10, -3.0],
+        location: None,
+        tags,
+    };
+
+    let bytes = cbor::to_vec(&device);
+    let out: Device = cbor::from_slice(&bytes).unwrap();
+    assert_eq!(out, device);
+
+    // Same as going through JSON.
+    let value: Value = cbor::from_slice(&bytes).unwrap();
+    assert_eq!(value, json::to_value(&device));
+
+    // Trailing bytes.
+    let mut bytes = bytes;
+    bytes.push(0);
+    cbor::from_slice::<Device>(&bytes).unwrap_err();
+}
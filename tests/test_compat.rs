@@ -0,0 +1,181 @@
+#![cfg(feature = "serde")]
+#![allow(clippy::derive_partial_eq_without_eq)]
+
+use miniserde::compat::{self, MiniSer, Serde};
+use miniserde::{json, Bytes, Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+enum Kind {
+    Small,
+    #[serde(rename = "big")]
+    Large,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Mini {
+    name: String,
+    count: u32,
+    offset: i64,
+    ratio: f64,
+    kind: Kind,
+    tags: Vec<String>,
+    parent: Option<Box<Mini>>,
+}
+
+fn mini() -> Mini {
+    Mini {
+        name: "child".to_owned(),
+        count: 2,
+        offset: -7,
+        ratio: 0.5,
+        kind: Kind::Large,
+        tags: vec!["a".to_owned(), "b".to_owned()],
+        parent: Some(Box::new(Mini {
+            name: "root".to_owned(),
+            count: 0,
+            offset: 0,
+            ratio: 1.0,
+            kind: Kind::Small,
+            tags: Vec::new(),
+            parent: None,
+        })),
+    }
+}
+
+#[test]
+fn test_serde_round_trip() {
+    let value = mini();
+    let j = serde_json::to_string(&Serde(&value)).unwrap();
+    assert_eq!(j, json::to_string(&value));
+
+    let Serde(back): Serde<Mini> = serde_json::from_str(&j).unwrap();
+    assert_eq!(back, value);
+}
+
+#[test]
+fn test_serde_borrowed() {
+    #[derive(Deserialize)]
+    struct Borrowed<'a> {
+        s: &'a str,
+    }
+
+    let j = String::from(r#"{"s":"in place"}"#);
+    let Serde(borrowed): Serde<Borrowed> = serde_json::from_str(&j).unwrap();
+    assert_eq!(borrowed.s, "in place");
+}
+
+#[test]
+fn test_serde_bytes() {
+    let j = serde_json::to_string(&Serde(Bytes::new(b"\x01\x02"))).unwrap();
+    assert_eq!(j, "[1,2]");
+}
+
+#[test]
+fn test_serde_errors() {
+    assert!(serde_json::from_str::<Serde<Mini>>(r#"{"name":"x"}"#).is_err());
+    assert!(serde_json::from_str::<Serde<Kind>>(r#""Medium""#).is_err());
+    assert!(serde_json::from_str::<Serde<u8>>("256").is_err());
+    assert!(serde_json::from_str::<Serde<Vec<u8>>>("[1,true]").is_err());
+}
+
+#[test]
+fn test_deserialize_visitor() {
+    let mut out = None;
+    let mut deserializer = serde_json::Deserializer::from_str(r#"{"1":[true,null],"2":[]}"#);
+    let visitor = <BTreeMap<u8, Vec<Option<bool>>> as Deserialize>::begin(&mut out);
+    compat::deserialize(&mut deserializer, visitor).unwrap();
+    let map = out.unwrap();
+    assert_eq!(map[&1], [Some(true), None]);
+    assert!(map[&2].is_empty());
+}
+
+#[derive(serde_derive::Serialize)]
+struct Foreign {
+    zeta: u8,
+    alpha: Variant,
+    tuple: (i8, char),
+    keys: BTreeMap<u16, &'static str>,
+    unit: (),
+    variants: Vec<Variant>,
+}
+
+#[derive(serde_derive::Serialize)]
+enum Variant {
+    Unit,
+    Newtype(u8),
+    Tuple(u8, u8),
+    Struct { x: u8 },
+}
+
+#[test]
+fn test_miniser() {
+    let mut keys = BTreeMap::new();
+    keys.insert(10, "ten");
+    let foreign = Foreign {
+        zeta: 1,
+        alpha: Variant::Unit,
+        tuple: (-1, 'c'),
+        keys,
+        unit: (),
+        variants: vec![
+            Variant::Newtype(1),
+            Variant::Tuple(2, 3),
+            Variant::Struct { x: 4 },
+        ],
+    };
+    let expected = serde_json::to_string(&foreign).unwrap();
+    assert_eq!(json::to_string(&MiniSer(&foreign)), expected);
+}
+
+#[test]
+#[should_panic = "failed to serialize through Serde"]
+fn test_miniser_failure() {
+    let mut map = BTreeMap::new();
+    map.insert((1, 2), 3);
+    json::to_string(&MiniSer(map));
+}
+
+#[test]
+fn test_miniser_try_new() {
+    let mut map = BTreeMap::new();
+    map.insert((1, 2), 3);
+    assert!(MiniSer::try_new(&map).is_err());
+
+    let mut map = BTreeMap::new();
+    map.insert('k', [1, 2]);
+    let converted = MiniSer::try_new(&map).unwrap();
+    drop(map);
+    assert_eq!(json::to_string(&converted), r#"{"k":[1,2]}"#);
+}
+
+#[test]
+fn test_bincode() {
+    // Written the same as the equivalent Serde types would be.
+    #[derive(Serialize)]
+    struct Point {
+        x: i64,
+        y: i64,
+    }
+
+    let mut map = BTreeMap::new();
+    map.insert("x", 1i64);
+    map.insert("y", -2);
+    let bytes = bincode::serialize(&Serde(Point { x: 1, y: -2 })).unwrap();
+    assert_eq!(bytes, bincode::serialize(&map).unwrap());
+
+    let vec = vec![1u64, 2, 3];
+    let bytes = bincode::serialize(&Serde(&vec)).unwrap();
+    assert_eq!(bytes, bincode::serialize(&vec).unwrap());
+
+    // Null is a unit, which bincode writes as nothing, so an absent value
+    // leaves no trace and a present one cannot be told from a plain value.
+    assert!(bincode::serialize(&Serde(None::<u64>)).unwrap().is_empty());
+    assert_eq!(
+        bincode::serialize(&Serde(Some(7u64))).unwrap(),
+        bincode::serialize(&7u64).unwrap()
+    );
+
+    // Not self-describing, so reading back is not supported.
+    assert!(bincode::deserialize::<Serde<Vec<u64>>>(&bytes).is_err());
+}
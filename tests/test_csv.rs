@@ -0,0 +1,171 @@
+use miniserde::json::Value;
+use miniserde::{csv, Deserialize, Result, Serialize};
+use std::collections::BTreeMap;
+use std::io::{self, Read};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Row {
+    id: u64,
+    delta: i32,
+    score: f64,
+    active: bool,
+    name: String,
+    comment: Option<String>,
+}
+
+fn row(id: u64, name: &str, comment: Option<&str>) -> Row {
+    Row {
+        id,
+        delta: -(id as i32),
+        score: id as f64 / 4.0,
+        active: id % 2 == 0,
+        name: name.to_owned(),
+        comment: comment.map(str::to_owned),
+    }
+}
+
+fn to_string<T: Serialize>(records: &[T]) -> String {
+    let mut out = Vec::new();
+    csv::to_writer(&mut out, records).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn test_round_trip() {
+    let rows = vec![
+        row(1, "plain", None),
+        row(2, "comma, inside", Some("")),
+        row(3, "\"quoted\"", Some("line\r\nbreak")),
+    ];
+    let text = to_string(&rows);
+    let expected = "id,delta,score,active,name,comment\r\n\
+                    1,-1,0.25,false,plain,\r\n\
+                    2,-2,0.5,true,\"comma, inside\",\"\"\r\n\
+                    3,-3,0.75,false,\"\"\"quoted\"\"\",\"line\r\nbreak\"\r\n";
+    assert_eq!(text, expected);
+
+    let parsed: Vec<Row> = csv::from_str(&text).collect::<Result<_>>().unwrap();
+    assert_eq!(parsed, rows);
+}
+
+#[test]
+fn test_empty() {
+    assert_eq!(to_string::<Row>(&[]), "");
+    assert_eq!(csv::from_str::<Row>("").count(), 0);
+    assert_eq!(csv::from_str::<Row>("id,name\n").count(), 0);
+}
+
+#[test]
+fn test_lenient_input() {
+    #[derive(Deserialize)]
+    struct Pair {
+        a: String,
+        b: u8,
+    }
+
+    // BOM, LF line endings, blank lines, no final line break.
+    let text = "\u{feff}a,b\n\nx,1\n\n\"y\",\"2\"";
+    let mut rows = csv::from_str::<Pair>(text);
+    let first = rows.next().unwrap().unwrap();
+    assert_eq!(rows.header().unwrap(), ["a", "b"]);
+    assert_eq!((first.a.as_str(), first.b), ("x", 1));
+    let second = rows.next().unwrap().unwrap();
+    assert_eq!((second.a.as_str(), second.b), ("y", 2));
+    assert!(rows.next().is_none());
+}
+
+#[test]
+fn test_single_column() {
+    let rows: Vec<Option<String>> =
+        csv::from_str::<BTreeMap<String, Option<String>>>("a\nx\n\n\"\"\n")
+            .map(|row| row.unwrap().remove("a").unwrap())
+            .collect();
+    assert_eq!(rows, [Some("x".to_owned()), None, Some(String::new())]);
+}
+
+#[test]
+fn test_untyped() {
+    let rows: Vec<Value> = csv::from_str("n,s\n1,x\n").collect::<Result<_>>().unwrap();
+    assert_eq!(rows[0]["n"].as_str(), Some("1"));
+    assert_eq!(rows[0]["s"].as_str(), Some("x"));
+}
+
+#[test]
+fn test_row_errors() {
+    // Bad rows are reported without ending the iteration.
+    let text = "id,delta,score,active,name,comment\n\
+                1,0,0,true,a,\n\
+                2,0,0,true\n\
+                x,0,0,true,b,\n\
+                4,0,0,true,c,\n";
+    let ids: Vec<Option<u64>> = csv::from_str::<Row>(text)
+        .map(|row| row.ok().map(|row| row.id))
+        .collect();
+    assert_eq!(ids, [Some(1), None, None, Some(4)]);
+}
+
+#[test]
+fn test_malformed() {
+    for text in ["a\n\"x", "a\nx\"y\n", "a\n\"x\"y\n", "a\nx\ry\n"] {
+        let mut rows = csv::from_str::<Value>(text);
+        assert!(rows.next().unwrap().is_err(), "{:?}", text);
+        assert!(rows.next().is_none());
+    }
+}
+
+#[test]
+fn test_write_errors() {
+    let mut out = Vec::new();
+    assert!(csv::to_writer(&mut out, [1]).is_err());
+
+    let mut first = BTreeMap::new();
+    first.insert("a", 1);
+    let mut second = BTreeMap::new();
+    second.insert("b", 1);
+    assert!(csv::to_writer(&mut out, [&first, &second]).is_err());
+
+    let mut nested = BTreeMap::new();
+    nested.insert("a", vec![1]);
+    assert!(csv::to_writer(&mut out, [nested]).is_err());
+}
+
+// Hands out the input one byte at a time.
+struct Trickle<'a>(&'a [u8]);
+
+impl Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let Some((&first, rest)) = self.0.split_first() else {
+            return Ok(0);
+        };
+        buf[0] = first;
+        self.0 = rest;
+        Ok(1)
+    }
+}
+
+#[test]
+fn test_from_reader() {
+    let rows = vec![
+        row(1, "é", Some("a\r\nb")),
+        row(2, "\"", None),
+        row(3, "", Some(",")),
+    ];
+    let text = to_string(&rows);
+    let parsed: Vec<Row> = csv::from_reader(Trickle(text.as_bytes()))
+        .collect::<Result<_>>()
+        .unwrap();
+    assert_eq!(parsed, rows);
+}
+
+#[test]
+fn test_long_cell_from_reader() {
+    let len = if cfg!(miri) { 20_000 } else { 4_000_000 };
+    let cell = "\"x,\"".repeat(len / 4);
+    let text = format!("a,b\n\"{}\",1\n", cell.replace('"', "\"\""));
+    let parsed: Vec<BTreeMap<String, Value>> = csv::from_reader(text.as_bytes())
+        .collect::<Result<_>>()
+        .unwrap();
+    assert_eq!(parsed.len(), 1);
+    assert_eq!(parsed[0]["a"], Value::String(cell));
+    assert_eq!(parsed[0]["b"], Value::String("1".to_owned()));
+}
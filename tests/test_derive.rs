@@ -1,6 +1,7 @@
 #![allow(clippy::derive_partial_eq_without_eq)]
 
 use miniserde::{json, Deserialize, Serialize};
+use std::borrow::Cow;
 
 #[derive(PartialEq, Debug, Serialize, Deserialize)]
 enum Tag {
@@ -61,3 +62,24 @@ fn test_ser() {
         r#"{"x":"X","t1":"A","t2":"renamedB","t3":["enum"],"struct":{"y":["Y","Y"],"z":null}}"#;
     assert_eq!(actual, expected);
 }
+
+#[derive(PartialEq, Debug, Deserialize)]
+struct Borrowed<'a, T> {
+    name: &'a str,
+    tags: Vec<Cow<'a, str>>,
+    extra: T,
+}
+
+#[test]
+fn test_de_borrowed() {
+    let j = r#" {"name": "N", "tags": ["a", "b\n"], "extra": 1} "#;
+    let actual: Borrowed<u8> = json::from_str(j).unwrap();
+    let expected = Borrowed {
+        name: "N",
+        tags: vec![Cow::Borrowed("a"), Cow::Owned("b\n".to_owned())],
+        extra: 1,
+    };
+    assert_eq!(actual, expected);
+    assert!(matches!(actual.tags[0], Cow::Borrowed(_)));
+    assert!(matches!(actual.tags[1], Cow::Owned(_)));
+}
@@ -0,0 +1,131 @@
+use miniserde::json::{self, ArrayDiff, Value};
+
+fn value(j: &str) -> Value {
+    json::from_str(j).unwrap()
+}
+
+fn check_round_trip(source: &Value, target: &Value, arrays: ArrayDiff) -> Value {
+    let patch = json::diff_with(source, target, arrays);
+    let mut doc = source.clone();
+    json::patch::apply(&mut doc, &patch).unwrap();
+    assert_eq!(doc, *target);
+    patch
+}
+
+#[test]
+fn test_diff() {
+    let cases = [
+        (r#"{"a":1}"#, r#"{"a":1}"#, r"[]"),
+        (r"1", r"1.0", r"[]"),
+        (
+            r"1",
+            r#""1""#,
+            r#"[{"op":"replace","path":"","value":"1"}]"#,
+        ),
+        (
+            r#"{"a":1,"b":2}"#,
+            r#"{"b":3,"c":4}"#,
+            r#"[{"op":"remove","path":"/a"},{"op":"add","path":"/c","value":4},{"op":"replace","path":"/b","value":3}]"#,
+        ),
+        (
+            r#"{"a/b":{"m~n":true}}"#,
+            r#"{"a/b":{"m~n":false}}"#,
+            r#"[{"op":"replace","path":"/a~1b/m~0n","value":false}]"#,
+        ),
+        (
+            r"[1,2,3,4]",
+            r"[1,5]",
+            r#"[{"op":"remove","path":"/3"},{"op":"remove","path":"/2"},{"op":"replace","path":"/1","value":5}]"#,
+        ),
+        (
+            r"[1,[2]]",
+            r"[1,[2,3],4]",
+            r#"[{"op":"add","path":"/2","value":4},{"op":"add","path":"/1/1","value":3}]"#,
+        ),
+    ];
+
+    for (source, target, expected) in cases {
+        let patch = check_round_trip(&value(source), &value(target), ArrayDiff::ByIndex);
+        assert_eq!(json::to_string(&patch), expected);
+    }
+}
+
+#[test]
+fn test_diff_lcs() {
+    let cases = [
+        (
+            r"[1,2,3,4]",
+            r"[0,1,2,3,4]",
+            r#"[{"op":"add","path":"/0","value":0}]"#,
+        ),
+        (r"[1,2,3,4]", r"[1,3,4]", r#"[{"op":"remove","path":"/1"}]"#),
+        (
+            r"[1,2,3,4,5]",
+            r"[0,1,3,4,6]",
+            r#"[{"op":"add","path":"/0","value":0},{"op":"remove","path":"/2"},{"op":"replace","path":"/4","value":6}]"#,
+        ),
+        (
+            r#"[{"id":1},{"id":2,"x":1},{"id":3}]"#,
+            r#"[{"id":1},{"id":2,"x":2}]"#,
+            r#"[{"op":"remove","path":"/2"},{"op":"replace","path":"/1/x","value":2}]"#,
+        ),
+    ];
+
+    for (source, target, expected) in cases {
+        let patch = check_round_trip(&value(source), &value(target), ArrayDiff::Lcs);
+        assert_eq!(json::to_string(&patch), expected);
+    }
+}
+
+#[test]
+fn test_diff_round_trip() {
+    let documents = [
+        r"null",
+        r"[]",
+        r"{}",
+        r"[1,2,3]",
+        r"[3,2,1,0]",
+        r#"[[1,2],{"a":[3,4]},"x",null]"#,
+        r#"{"a":[1,{"b":2}],"c":{"d":[true,false]},"e":"f"}"#,
+        r#"{"a":[{"b":2},1,1],"c":{"d":[false]},"g":null}"#,
+        r#"{"":{"/":[{"~":0}]}}"#,
+    ];
+
+    for source in documents {
+        for target in documents {
+            let (source, target) = (value(source), value(target));
+            check_round_trip(&source, &target, ArrayDiff::ByIndex);
+            check_round_trip(&source, &target, ArrayDiff::Lcs);
+        }
+    }
+}
+
+#[test]
+fn test_diff_deeply_nested() {
+    fn nested(depth: usize, leaf: char) -> (Value, String) {
+        let mut j = String::new();
+        let mut pointer = String::new();
+        for _ in 0..depth {
+            j.push_str("{\"x\":[");
+            pointer.push_str("/x/0");
+        }
+        j.push(leaf);
+        for _ in 0..depth {
+            j.push_str("]}");
+        }
+        (value(&j), pointer)
+    }
+
+    // The LCS mode compares whole elements at each level, so it is quadratic
+    // in the depth and gets a shallower document.
+    let depth = if cfg!(miri) { 40 } else { 100_000 };
+    let lcs_depth = if cfg!(miri) { 40 } else { 1_000 };
+
+    for (depth, arrays) in [(depth, ArrayDiff::ByIndex), (lcs_depth, ArrayDiff::Lcs)] {
+        let (source, pointer) = nested(depth, '1');
+        let (target, _) = nested(depth, '2');
+        let patch = json::diff_with(&source, &target, arrays);
+        let expected = miniserde::json!([{"op": "replace", "path": pointer, "value": 2}]);
+        assert_eq!(patch, expected);
+    }
+}
@@ -0,0 +1,71 @@
+use miniserde::json::{self, DuplicateKeys, Options, Value};
+use miniserde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(Deserialize, Debug)]
+struct Request {
+    user: String,
+    admin: bool,
+}
+
+const J: &str = r#"{"user": "a", "admin": false, "admin": true, "user": "b"}"#;
+
+fn options(policy: DuplicateKeys) -> Options {
+    Options::new().duplicate_keys(policy)
+}
+
+#[test]
+fn test_last() {
+    let options = options(DuplicateKeys::Last);
+    assert_eq!(
+        json::from_str::<Value>(J).unwrap(),
+        json::from_str_with::<Value>(J, &options).unwrap(),
+    );
+
+    let value: Value = json::from_str_with(J, &options).unwrap();
+    assert_eq!(value["user"].as_str(), Some("b"));
+    let map: BTreeMap<String, Value> = json::from_str_with(J, &options).unwrap();
+    assert_eq!(map["admin"], Value::Bool(true));
+    let map: HashMap<String, Value> = json::from_str_with(J, &options).unwrap();
+    assert_eq!(map["admin"], Value::Bool(true));
+    let request: Request = json::from_str_with(J, &options).unwrap();
+    assert_eq!(request.user, "b");
+    assert!(request.admin);
+}
+
+#[test]
+fn test_first() {
+    let options = options(DuplicateKeys::First);
+    let value: Value = json::from_str_with(J, &options).unwrap();
+    assert_eq!(value["user"].as_str(), Some("a"));
+    let map: BTreeMap<String, Value> = json::from_str_with(J, &options).unwrap();
+    assert_eq!(map["admin"], Value::Bool(false));
+    let map: HashMap<String, Value> = json::from_str_with(J, &options).unwrap();
+    assert_eq!(map["admin"], Value::Bool(false));
+    let request: Request = json::from_str_with(J, &options).unwrap();
+    assert_eq!(request.user, "a");
+    assert!(!request.admin);
+
+    // The ignored value must still be valid.
+    let j = r#"{"user": "a", "user": [1,], "admin": false}"#;
+    json::from_str_with::<Request>(j, &options).unwrap_err();
+}
+
+#[test]
+fn test_error() {
+    let options = options(DuplicateKeys::Error);
+    json::from_str_with::<Value>(J, &options).unwrap_err();
+    json::from_str_with::<BTreeMap<String, Value>>(J, &options).unwrap_err();
+    json::from_str_with::<HashMap<String, Value>>(J, &options).unwrap_err();
+    json::from_str_with::<Request>(J, &options).unwrap_err();
+
+    // Keys are compared after decoding escapes.
+    let j = r#"{"user": "a", "us\u0065r": "b"}"#;
+    json::from_str_with::<Value>(j, &options).unwrap_err();
+
+    // Only keys within the same object are compared.
+    let j = r#"{"a": {"a": {"b": 1}, "b": [{"a": 1}, {"a": 2}]}}"#;
+    json::from_str_with::<Value>(j, &options).unwrap();
+    let j = r#"{"a": {"a": {"b": 1}, "b": [{"a": 1}, {"a": 2, "a": 3}]}}"#;
+    json::from_str_with::<Value>(j, &options).unwrap_err();
+}
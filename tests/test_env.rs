@@ -0,0 +1,152 @@
+use miniserde::json::Value;
+use miniserde::{env, Deserialize};
+use std::collections::BTreeMap;
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct Config {
+    name: String,
+    workers: u32,
+    offset: i32,
+    ratio: f64,
+    verbose: bool,
+    ports: Vec<u16>,
+    tags: Vec<String>,
+    timeout: Option<u64>,
+    db: Database,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct Database {
+    host: String,
+    pool: Pool,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct Pool {
+    size: usize,
+}
+
+fn vars(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+    vars.iter()
+        .map(|&(k, v)| (k.to_owned(), v.to_owned()))
+        .collect()
+}
+
+#[test]
+fn test_nested() {
+    let config: Config = env::from_iter(
+        "APP_",
+        vars(&[
+            ("APP_NAME", "svc"),
+            ("APP_WORKERS", "4"),
+            ("APP_OFFSET", "-3"),
+            ("APP_RATIO", "0.25"),
+            ("APP_VERBOSE", "true"),
+            ("APP_PORTS", "80,443"),
+            ("APP_TAGS", "a,b,,c"),
+            ("APP_TIMEOUT", ""),
+            ("APP_DB__HOST", "db.internal"),
+            ("APP_DB__POOL__SIZE", "16"),
+            ("HOME", "/root"),
+            ("OTHER_NAME", "x"),
+        ]),
+    )
+    .unwrap();
+    assert_eq!(
+        config,
+        Config {
+            name: "svc".to_owned(),
+            workers: 4,
+            offset: -3,
+            ratio: 0.25,
+            verbose: true,
+            ports: vec![80, 443],
+            tags: vec![
+                "a".to_owned(),
+                "b".to_owned(),
+                String::new(),
+                "c".to_owned(),
+            ],
+            timeout: None,
+            db: Database {
+                host: "db.internal".to_owned(),
+                pool: Pool { size: 16 },
+            },
+        },
+    );
+}
+
+#[test]
+fn test_seq_coercion() {
+    #[derive(Deserialize)]
+    struct Lists {
+        one: Vec<u8>,
+        none: Vec<u8>,
+        text: String,
+    }
+
+    let lists: Lists = env::from_iter(
+        "",
+        vars(&[("ONE", "7"), ("NONE", ""), ("TEXT", "no,split")]),
+    )
+    .unwrap();
+    assert_eq!(lists.one, [7]);
+    assert!(lists.none.is_empty());
+    // Only a field which needs a sequence gets the value split up.
+    assert_eq!(lists.text, "no,split");
+}
+
+#[test]
+fn test_untyped() {
+    let map: BTreeMap<String, String> =
+        env::from_iter("X_", vars(&[("X_A", "1"), ("X_B_C", "2")])).unwrap();
+    assert_eq!(map["a"], "1");
+    assert_eq!(map["b_c"], "2");
+
+    let value: Value = env::from_iter("X_", vars(&[("X_A__B", "1")])).unwrap();
+    assert_eq!(value["a"]["b"].as_str(), Some("1"));
+}
+
+#[test]
+fn test_errors() {
+    let parse = |v: &[(&str, &str)]| env::from_iter::<Value, _>("X_", vars(v));
+    assert!(parse(&[("X_", "1")]).is_err());
+    assert!(parse(&[("X_A____B", "1")]).is_err());
+    assert!(parse(&[("X_A__", "1")]).is_err());
+    assert!(parse(&[("X_A", "1"), ("X_A__B", "2")]).is_err());
+    assert!(parse(&[("X_A__B", "2"), ("X_A", "1")]).is_err());
+    assert!(parse(&[("X_A", "1"), ("X_a", "2")]).is_err());
+
+    let number = env::from_iter::<BTreeMap<String, u8>, _>("X_", vars(&[("X_N", "256")]));
+    assert!(number.is_err());
+    let list = env::from_iter::<BTreeMap<String, Vec<u8>>, _>("X_", vars(&[("X_N", "1,x")]));
+    assert!(list.is_err());
+}
+
+#[test]
+fn test_from_env() {
+    #[derive(Deserialize)]
+    struct Process {
+        level: u8,
+        label: String,
+    }
+
+    std::env::set_var("MINISERDE_TEST_ENV_LEVEL", "3");
+    std::env::set_var("MINISERDE_TEST_ENV_LABEL", "from env");
+    let process: Process = env::from_env("MINISERDE_TEST_ENV_").unwrap();
+    assert_eq!(process.level, 3);
+    assert_eq!(process.label, "from env");
+}
+
+#[test]
+fn test_deep_nesting() {
+    let depth = if cfg!(miri) { 40 } else { 200_000 };
+    let name = format!("X_{}A", "A__".repeat(depth));
+    let value: Value = env::from_iter("X_", vec![(name, "1".to_owned())]).unwrap();
+
+    let mut value = &value;
+    for _ in 0..depth {
+        value = &value["a"];
+    }
+    assert_eq!(value["a"].as_str(), Some("1"));
+}
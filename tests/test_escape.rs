@@ -0,0 +1,103 @@
+use miniserde::json::{self, RawValue, SerializeOptions, Value};
+use miniserde::Serialize;
+use std::collections::BTreeMap;
+
+fn serialize(s: &str, options: &SerializeOptions) -> String {
+    let j = json::to_string_with(s, options).unwrap();
+    // Every mode round-trips through the parser.
+    assert_eq!(json::from_str::<String>(&j).unwrap(), s);
+    j
+}
+
+#[test]
+fn test_default() {
+    let options = SerializeOptions::new();
+    assert_eq!(
+        serialize("a\"\\\n\u{1}\u{e9}<>&\u{2028}", &options),
+        "\"a\\\"\\\\\\n\\u0001\u{e9}<>&\u{2028}\"",
+    );
+}
+
+#[test]
+fn test_ascii_only() {
+    let options = SerializeOptions::new().ascii_only(true);
+    assert_eq!(serialize("plain", &options), r#""plain""#);
+    assert_eq!(serialize("\u{e9}", &options), r#""\u00e9""#);
+    assert_eq!(serialize("a\u{20ac}b", &options), r#""a\u20acb""#);
+    assert_eq!(serialize("\u{ffff}", &options), r#""\uffff""#);
+    assert_eq!(serialize("\u{1f600}!", &options), r#""\ud83d\ude00!""#);
+    assert_eq!(serialize("\u{10ffff}", &options), r#""\udbff\udfff""#);
+    assert_eq!(
+        serialize("\t\u{7f}\u{80}", &options),
+        "\"\\t\u{7f}\\u0080\""
+    );
+    assert_eq!(serialize("<&>", &options), r#""<&>""#);
+
+    let j = serialize("\u{65e5}\u{672c}\u{8a9e} text", &options);
+    assert!(j.is_ascii());
+}
+
+#[test]
+fn test_html_safe() {
+    let options = SerializeOptions::new().html_safe(true);
+    assert_eq!(
+        serialize("</script><!--&", &options),
+        r#""\u003c/script\u003e\u003c!--\u0026""#,
+    );
+    assert_eq!(
+        serialize("a\u{2028}b\u{2029}", &options),
+        r#""a\u2028b\u2029""#,
+    );
+    // Other characters beginning with the same byte are left alone.
+    let s = "\u{2027}\u{202a}\u{20ac}";
+    assert_eq!(serialize(s, &options), json::to_string(s));
+    assert_eq!(serialize("\u{e9}", &options), "\"\u{e9}\"");
+}
+
+#[test]
+fn test_both() {
+    let options = SerializeOptions::new().ascii_only(true).html_safe(true);
+    assert_eq!(
+        serialize("<\u{e9}\u{2028}>", &options),
+        r#""\u003c\u00e9\u2028\u003e""#,
+    );
+}
+
+#[test]
+fn test_keys() {
+    let mut map = BTreeMap::new();
+    map.insert("<k\u{e9}>".to_owned(), "v");
+    let options = SerializeOptions::new().ascii_only(true).html_safe(true);
+    let j = json::to_string_with(&map, &options).unwrap();
+    assert_eq!(j, r#"{"\u003ck\u00e9\u003e":"v"}"#);
+    let value: Value = json::from_str(&j).unwrap();
+    assert_eq!(value["<k\u{e9}>"].as_str(), Some("v"));
+}
+
+#[test]
+fn test_raw_value() {
+    #[derive(Serialize)]
+    struct Embedded {
+        raw: Box<RawValue>,
+    }
+
+    let raw = r#" {"<k>": ["</script>", "é\"\\é", 1e5, true]} "#;
+    let embedded = Embedded {
+        raw: RawValue::from_string(raw.to_owned()).unwrap(),
+    };
+
+    // Escapes already in the text are kept as they are.
+    let options = SerializeOptions::new().html_safe(true).ascii_only(true);
+    let j = json::to_string_with(&embedded, &options).unwrap();
+    let expected = concat!(
+        r#"{"raw": {"\u003ck\u003e": ["\u003c/script\u003e", "#,
+        r#""\u00e9\"\\\u00e9", 1e5, true]} }"#,
+    );
+    assert_eq!(j, expected);
+    let value: Value = json::from_str(&j).unwrap();
+    assert_eq!(value["raw"]["<k>"][1].as_str(), Some("\u{e9}\"\\\u{e9}"));
+
+    // Without either option the text is copied as it is.
+    let j = json::to_string(&embedded);
+    assert_eq!(j, format!(r#"{{"raw":{}}}"#, raw));
+}
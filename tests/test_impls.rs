@@ -1,4 +1,8 @@
-use miniserde::json;
+use miniserde::json::{self, Value};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+use std::sync::Arc;
 
 #[test]
 fn test_array() {
@@ -19,3 +23,35 @@ fn test_array_too_long() {
     let j = r#"["1","2","3","4"]"#;
     json::from_str::<[String; 3]>(j).unwrap_err();
 }
+
+#[test]
+fn test_borrowed_str() {
+    let s: &str = json::from_str(r#""plain""#).unwrap();
+    assert_eq!(s, "plain");
+
+    // A string containing escapes cannot be borrowed from the input.
+    json::from_str::<&str>(r#""esc\"aped""#).unwrap_err();
+
+    let cows: Vec<Cow<str>> = json::from_str(r#"["plain", "esc\"aped"]"#).unwrap();
+    assert!(matches!(cows[0], Cow::Borrowed("plain")));
+    assert!(matches!(&cows[1], Cow::Owned(s) if s == "esc\"aped"));
+}
+
+#[test]
+fn test_borrowed_from_value() {
+    // Strings in a Value are already unescaped, so can always be borrowed.
+    let value: Value = json::from_str(r#"{"k": "x\ny"}"#).unwrap();
+    let map: BTreeMap<String, &str> = json::from_value_ref(&value).unwrap();
+    assert_eq!(map["k"], "x\ny");
+}
+
+#[test]
+fn test_shared_str() {
+    let j = r#""sh\u00e4red""#;
+    let boxed: Box<str> = json::from_str(j).unwrap();
+    let rc: Rc<str> = json::from_str(j).unwrap();
+    let arc: Arc<str> = json::from_str(j).unwrap();
+    assert_eq!(&*boxed, "sh\u{e4}red");
+    assert_eq!(&*rc, "sh\u{e4}red");
+    assert_eq!(&*arc, "sh\u{e4}red");
+}
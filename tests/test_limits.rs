@@ -0,0 +1,84 @@
+use miniserde::json::{self, Options, RawValue, Value};
+use std::collections::BTreeMap;
+
+fn check(j: &str, options: &Options) -> bool {
+    json::from_str_with::<Value>(j, options).is_ok()
+}
+
+#[test]
+fn test_default_unlimited() {
+    let options = Options::default();
+    let depth = if cfg!(miri) { 40 } else { 10_000 };
+    let j = "[".repeat(depth) + &"]".repeat(depth);
+    assert!(check(&j, &options));
+}
+
+#[test]
+fn test_max_depth() {
+    let options = Options::new().max_depth(2);
+    assert!(check("1", &options));
+    assert!(check("[{}]", &options));
+    assert!(check(r#"{"a": [1], "b": {"c": 2}}"#, &options));
+    assert!(!check("[[[]]]", &options));
+    assert!(!check(r#"{"a": [{"b": 1}]}"#, &options));
+
+    let options = Options::new().max_depth(0);
+    assert!(check("\"scalar\"", &options));
+    assert!(!check("[]", &options));
+}
+
+#[test]
+fn test_max_input_len() {
+    let options = Options::new().max_input_len(6);
+    assert!(check(" [1] ", &options));
+    assert!(check("[1, 2]", &options));
+    assert!(!check("[1, 2] ", &options));
+}
+
+#[test]
+fn test_max_string_len() {
+    let options = Options::new().max_string_len(3);
+    assert!(check(r#"["abc", {"key": "é"}]"#, &options));
+    assert!(!check(r#""abcd""#, &options));
+    // Measured after decoding escapes.
+    assert!(check(r#""\n\n\n""#, &options));
+    assert!(!check(r#""éé""#, &options));
+    assert!(!check(r#""ab\ncd""#, &options));
+    assert!(!check(r#""abcd\n""#, &options));
+    assert!(!check(r#""\n\n\n\n""#, &options));
+    // Keys are strings too.
+    assert!(!check(r#"{"long": 1}"#, &options));
+}
+
+#[test]
+fn test_max_elements() {
+    let options = Options::new().max_elements(2);
+    assert!(check("[[1, 2], [3, 4]]", &options));
+    assert!(check(r#"{"a": 1, "b": [1, 2]}"#, &options));
+    assert!(!check("[1, 2, 3]", &options));
+    assert!(!check(r#"{"a": 1, "b": 2, "c": 3}"#, &options));
+    assert!(!check("[[1, 2], [3, 4, 5]]", &options));
+}
+
+#[test]
+fn test_max_values() {
+    let options = Options::new().max_values(4);
+    assert!(!check("[1, [2], 3]", &options));
+    assert!(check("[1, [2]]", &options));
+    assert!(check(r#"{"a": {"b": null}}"#, &options));
+    assert!(!check(r#"{"a": {"b": null}, "c": [1]}"#, &options));
+}
+
+#[test]
+fn test_raw_value() {
+    let options = Options::new().max_depth(3).max_values(5);
+    let j = r#"{"a": [[1]]}"#;
+    let raw = json::from_str_with::<BTreeMap<String, Box<RawValue>>>(j, &options).unwrap();
+    assert_eq!(raw["a"].get(), "[[1]]");
+
+    // The limits apply inside a RawValue, counted from where it appears.
+    let j = r#"{"a": [[[1]]]}"#;
+    json::from_str_with::<BTreeMap<String, Box<RawValue>>>(j, &options).unwrap_err();
+    let j = r#"{"a": [1, 2, 3, 4, 5]}"#;
+    json::from_str_with::<BTreeMap<String, Box<RawValue>>>(j, &options).unwrap_err();
+}
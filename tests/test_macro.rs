@@ -0,0 +1,66 @@
+use miniserde::json::{self, Value};
+use miniserde::Serialize;
+use std::collections::BTreeMap;
+
+#[test]
+fn test_literals() {
+    assert!(miniserde::json!(null).is_null());
+    assert_eq!(miniserde::json!(true).as_bool(), Some(true));
+    assert_eq!(miniserde::json!(-1).as_i64(), Some(-1));
+    assert_eq!(miniserde::json!(1.5).as_f64(), Some(1.5));
+    assert_eq!(miniserde::json!("s").as_str(), Some("s"));
+    assert_eq!(json::to_string(&miniserde::json!([])), "[]");
+    assert_eq!(json::to_string(&miniserde::json!({})), "{}");
+}
+
+#[test]
+fn test_nested() {
+    let value = miniserde::json!({
+        "null": null,
+        "array": [true, false, null, [1, 2,], {"k": "v"},],
+        "object": {
+            "nested": {"deeper": []}
+        },
+    });
+    let expected = r#"{"array":[true,false,null,[1,2],{"k":"v"}],"null":null,"object":{"nested":{"deeper":[]}}}"#;
+    assert_eq!(json::to_string(&value), expected);
+}
+
+#[test]
+fn test_interpolation() {
+    let key = "dynamic";
+    let n = 7u8;
+    let list = vec![1, 2, 3];
+    let mut map = BTreeMap::new();
+    map.insert("x", Some(-2i32));
+    map.insert("y", None);
+
+    let value = miniserde::json!({
+        key: n,
+        (key.to_uppercase()): n + 1,
+        "list": list,
+        "map": map,
+        "string": String::from("s"),
+        "cmp": n > 5,
+        "neg": -(n as i64),
+    });
+    let expected = r#"{"DYNAMIC":8,"cmp":true,"dynamic":7,"list":[1,2,3],"map":{"x":-2,"y":null},"neg":-7,"string":"s"}"#;
+    assert_eq!(json::to_string(&value), expected);
+
+    let array: Value = miniserde::json!([n, "x", value["list"][0].clone()]);
+    assert_eq!(json::to_string(&array), r#"[7,"x",1]"#);
+}
+
+#[test]
+fn test_interpolate_derive() {
+    #[derive(Serialize)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    let point = Point { x: 1, y: -2 };
+    let value = miniserde::json!({"point": point, "points": [point, Point { x: 0, y: 0 }]});
+    let expected = r#"{"point":{"x":1,"y":-2},"points":[{"x":1,"y":-2},{"x":0,"y":0}]}"#;
+    assert_eq!(json::to_string(&value), expected);
+}
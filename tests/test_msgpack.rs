@@ -0,0 +1,251 @@
+use miniserde::json::{self, Value};
+use miniserde::ser::{Fragment, Seq, Serialize};
+use miniserde::{msgpack, Deserialize};
+use std::collections::BTreeMap;
+
+fn hex(s: &str) -> Vec<u8> {
+    let s = s.replace(' ', "");
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+fn decode(s: &str) -> Value {
+    msgpack::from_slice(&hex(s)).unwrap()
+}
+
+#[test]
+fn test_integers() {
+    let cases: &[(i64, &str)] = &[
+        (0, "00"),
+        (127, "7f"),
+        (128, "cc80"),
+        (255, "ccff"),
+        (256, "cd0100"),
+        (65536, "ce00010000"),
+        (4294967296, "cf0000000100000000"),
+        (-1, "ff"),
+        (-32, "e0"),
+        (-33, "d0df"),
+        (-128, "d080"),
+        (-129, "d1ff7f"),
+        (-32769, "d2ffff7fff"),
+        (i64::MIN, "d38000000000000000"),
+    ];
+    for &(n, expected) in cases {
+        assert_eq!(msgpack::to_vec(&n), hex(expected), "{}", n);
+        assert_eq!(msgpack::from_slice::<i64>(&hex(expected)).unwrap(), n);
+    }
+    assert_eq!(msgpack::to_vec(&u64::MAX), hex("cfffffffffffffffff"));
+
+    // Signed encodings of nonnegative numbers.
+    assert_eq!(msgpack::from_slice::<u8>(&hex("d005")).unwrap(), 5);
+    assert_eq!(
+        msgpack::from_slice::<u64>(&hex("d30000000000000005")).unwrap(),
+        5
+    );
+}
+
+#[test]
+fn test_floats() {
+    assert_eq!(msgpack::to_vec(&1.5), hex("ca3fc00000"));
+    assert_eq!(msgpack::to_vec(&1.1), hex("cb3ff199999999999a"));
+    assert_eq!(msgpack::to_vec(&f64::INFINITY), hex("ca7f800000"));
+    assert_eq!(decode("ca3fc00000").as_f64(), Some(1.5));
+    assert_eq!(decode("cb3ff199999999999a").as_f64(), Some(1.1));
+    assert!(msgpack::from_slice::<f64>(&msgpack::to_vec(&f64::NAN))
+        .unwrap()
+        .is_nan());
+}
+
+#[test]
+fn test_simple() {
+    assert_eq!(msgpack::to_vec(&()), [0xc0]);
+    assert_eq!(msgpack::to_vec(&false), [0xc2]);
+    assert_eq!(msgpack::to_vec(&true), [0xc3]);
+    assert_eq!(decode("c0"), Value::Null);
+    msgpack::from_slice::<Value>(&hex("c1")).unwrap_err();
+    // Extension types.
+    msgpack::from_slice::<Value>(&hex("d4 01 00")).unwrap_err();
+}
+
+#[test]
+fn test_strings() {
+    let cases = [
+        (0, "a0"),
+        (31, "bf"),
+        (32, "d9 20"),
+        (255, "d9 ff"),
+        (256, "da 01 00"),
+        (65536, "db 00 01 00 00"),
+    ];
+    for (len, header) in cases {
+        let s = "x".repeat(len);
+        let bytes = msgpack::to_vec(&s);
+        let header = hex(header);
+        assert_eq!(bytes[..header.len()], header);
+        assert_eq!(bytes.len(), header.len() + len);
+        assert_eq!(msgpack::from_slice::<String>(&bytes).unwrap(), s);
+    }
+
+    // Strings are borrowed from the input.
+    let bytes = hex("a3 6b 65 79");
+    let s: &str = msgpack::from_slice(&bytes).unwrap();
+    assert_eq!(s, "key");
+
+    msgpack::from_slice::<String>(&hex("a2 c3 28")).unwrap_err();
+    msgpack::from_slice::<String>(&hex("a3 6b 65")).unwrap_err();
+}
+
+#[test]
+fn test_bytes() {
+    struct Blob(Vec<u8>);
+
+    impl Serialize for Blob {
+        fn begin(&self) -> Fragment<'_> {
+            Fragment::Bytes(self.0.as_slice().into())
+        }
+    }
+
+    assert_eq!(msgpack::to_vec(&Blob(vec![1, 2, 3])), hex("c4 03 010203"));
+    let long = msgpack::to_vec(&Blob(vec![0; 256]));
+    assert_eq!(long[..3], hex("c5 01 00"));
+    assert_eq!(decode("c4 03 010203"), Value::from("AQID"));
+}
+
+#[test]
+fn test_arrays_and_maps() {
+    assert_eq!(msgpack::to_vec(&Vec::<u8>::new()), hex("90"));
+    assert_eq!(msgpack::to_vec(&[1, 2, 3]), hex("93 01 02 03"));
+    assert_eq!(msgpack::to_vec(&vec![0u8; 15])[0], 0x9f);
+    assert_eq!(msgpack::to_vec(&vec![0u8; 16])[..3], hex("dc 00 10"));
+    assert_eq!(
+        msgpack::to_vec(&vec![0u8; 65536])[..5],
+        hex("dd 00 01 00 00")
+    );
+
+    let value: Value = json::from_str(r#"{"a": 1, "b": [true, null], "c": {}}"#).unwrap();
+    let expected = hex("83 a161 01 a162 92 c3 c0 a163 80");
+    assert_eq!(msgpack::to_vec(&value), expected);
+    assert_eq!(msgpack::from_slice::<Value>(&expected).unwrap(), value);
+
+    let mut map = BTreeMap::new();
+    for i in 0..20 {
+        map.insert(format!("{:02}", i), i);
+    }
+    let bytes = msgpack::to_vec(&map);
+    assert_eq!(bytes[..3], hex("de 00 14"));
+    assert_eq!(
+        msgpack::from_slice::<BTreeMap<String, i32>>(&bytes).unwrap(),
+        map
+    );
+
+    // Too few elements, too many, and a key which is not a string.
+    msgpack::from_slice::<Value>(&hex("93 01 02")).unwrap_err();
+    msgpack::from_slice::<Value>(&hex("92 01 02 03")).unwrap_err();
+    msgpack::from_slice::<Value>(&hex("81 01 02")).unwrap_err();
+}
+
+#[test]
+fn test_size_hint() {
+    // A sequence whose hint is wrong or missing still gets the right header.
+    struct Counting {
+        len: usize,
+        hint: Option<usize>,
+    }
+
+    impl Serialize for Counting {
+        fn begin(&self) -> Fragment<'_> {
+            Fragment::Seq(Box::new(CountingStream {
+                next: 0,
+                len: self.len,
+                hint: self.hint,
+            }))
+        }
+    }
+
+    struct CountingStream {
+        next: usize,
+        len: usize,
+        hint: Option<usize>,
+    }
+
+    impl Seq for CountingStream {
+        fn next(&mut self) -> Option<&dyn Serialize> {
+            if self.next == self.len {
+                return None;
+            }
+            self.next += 1;
+            Some(&0u8)
+        }
+
+        fn size_hint(&self) -> Option<usize> {
+            self.hint
+        }
+    }
+
+    for (len, hint) in [
+        (3, Some(3)),
+        (3, None),
+        (20, Some(3)),
+        (3, Some(20)),
+        (0, Some(70000)),
+    ] {
+        let counting = Counting { len, hint };
+        let bytes = msgpack::to_vec(&counting);
+        assert_eq!(
+            bytes,
+            msgpack::to_vec(&vec![0u8; len]),
+            "{} {:?}",
+            len,
+            hint
+        );
+    }
+
+    // Nested, with the outer header moving its contents after the inner one
+    // has been rewritten.
+    let nested = [
+        Counting {
+            len: 20,
+            hint: None,
+        },
+        Counting { len: 1, hint: None },
+    ];
+    let bytes = msgpack::to_vec(&nested);
+    let expected = msgpack::to_vec(&(vec![0u8; 20], vec![0u8]));
+    assert_eq!(bytes, expected);
+}
+
+#[test]
+fn test_derive() {
+    #[derive(miniserde::Serialize, Deserialize, Debug, PartialEq)]
+    struct Device {
+        id: u32,
+        name: String,
+        readings: Vec<f64>,
+        location: Option<(f32, f32)>,
+        tags: BTreeMap<String, i8>,
+    }
+
+    let mut tags = BTreeMap::new();
+    tags.insert("floor".to_owned(), -1);
+    let device = Device {
+        id: 70000,
+        name: "probe".to_owned(),
+        readings: vec![20.25, 1e-10, -3.0],
+        location: Some((1.5, -2.0)),
+        tags,
+    };
+
+    let bytes = msgpack::to_vec(&device);
+    let out: Device = msgpack::from_slice(&bytes).unwrap();
+    assert_eq!(out, device);
+
+    let value: Value = msgpack::from_slice(&bytes).unwrap();
+    assert_eq!(value, json::to_value(&device));
+
+    let mut bytes = bytes;
+    bytes.push(0);
+    msgpack::from_slice::<Device>(&bytes).unwrap_err();
+}
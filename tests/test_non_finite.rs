@@ -0,0 +1,80 @@
+use miniserde::json::{self, NonFinite, Options, SerializeOptions, Value};
+use miniserde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug)]
+struct Sample {
+    name: String,
+    value: f64,
+}
+
+fn samples() -> Vec<Sample> {
+    [f64::NAN, f64::INFINITY, f64::NEG_INFINITY, 0.5]
+        .into_iter()
+        .map(|value| Sample {
+            name: "NaN".to_owned(),
+            value,
+        })
+        .collect()
+}
+
+fn serialize(policy: NonFinite) -> miniserde::Result<String> {
+    let options = SerializeOptions::new().non_finite(policy);
+    json::to_string_with(&samples(), &options)
+}
+
+fn roundtrip(j: &str) -> Vec<Sample> {
+    let options = Options::new().relaxed(true);
+    let samples: Vec<Sample> = json::from_str_with(j, &options).unwrap();
+    assert!(samples.iter().all(|sample| sample.name == "NaN"));
+    samples
+}
+
+fn check(samples: &[Sample]) {
+    assert!(samples[0].value.is_nan());
+    assert_eq!(samples[1].value, f64::INFINITY);
+    assert_eq!(samples[2].value, f64::NEG_INFINITY);
+    assert_eq!(samples[3].value, 0.5);
+}
+
+#[test]
+fn test_null() {
+    let j = serialize(NonFinite::Null).unwrap();
+    assert_eq!(j, json::to_string(&samples()));
+    assert!(j.starts_with(r#"[{"name":"NaN","value":null},"#));
+}
+
+#[test]
+fn test_error() {
+    serialize(NonFinite::Error).unwrap_err();
+    json::try_to_string(&samples()).unwrap_err();
+    json::try_to_string(&[f32::INFINITY]).unwrap_err();
+    assert_eq!(json::try_to_string(&[0.5, -1.0]).unwrap(), "[0.5,-1.0]");
+}
+
+#[test]
+fn test_literal() {
+    let j = serialize(NonFinite::Literal).unwrap();
+    assert!(j.contains(r#""value":NaN}"#));
+    assert!(j.contains(r#""value":-Infinity}"#));
+    json::from_str::<Value>(&j).unwrap_err();
+    check(&roundtrip(&j));
+}
+
+#[test]
+fn test_string() {
+    let j = serialize(NonFinite::String).unwrap();
+    assert!(j.contains(r#""value":"Infinity"}"#));
+
+    // Valid JSON, in which the floats are strings.
+    let value: Value = json::from_str(&j).unwrap();
+    assert_eq!(value[0]["value"].as_str(), Some("NaN"));
+    json::from_str::<Vec<Sample>>(&j).unwrap_err();
+
+    // Relaxed mode reads them back as floats, and still as strings where a
+    // string is expected.
+    check(&roundtrip(&j));
+    let options = Options::new().relaxed(true);
+    let value: Value = json::from_str_with(&j, &options).unwrap();
+    assert_eq!(value[1]["value"].as_str(), Some("Infinity"));
+    json::from_str_with::<f64>(r#""nan""#, &options).unwrap_err();
+}
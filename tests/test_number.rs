@@ -16,3 +16,66 @@ fn test_ser() {
         assert_eq!(actual, *expected);
     }
 }
+
+#[test]
+fn test_eq_ord() {
+    use miniserde::json::Number;
+    use std::cmp::Ordering;
+
+    assert_eq!(Number::U64(1), Number::I64(1));
+    assert_eq!(Number::U64(1), Number::F64(1.0));
+    assert_eq!(Number::F64(0.0), Number::F64(-0.0));
+    assert_eq!(Number::F64(f64::NAN), Number::F64(f64::NAN));
+    assert_ne!(Number::U64(1), Number::F64(1.5));
+    assert_ne!(Number::U64(u64::MAX), Number::F64(u64::MAX as f64));
+
+    let cases = [
+        (Number::I64(-1), Number::U64(0), Ordering::Less),
+        (Number::F64(-0.5), Number::I64(-1), Ordering::Greater),
+        (Number::F64(1.5), Number::U64(2), Ordering::Less),
+        (
+            Number::U64(u64::MAX),
+            Number::F64(f64::INFINITY),
+            Ordering::Less,
+        ),
+        (
+            Number::I64(i64::MIN),
+            Number::F64(-1e300),
+            Ordering::Greater,
+        ),
+        (
+            Number::F64(f64::NAN),
+            Number::F64(f64::INFINITY),
+            Ordering::Greater,
+        ),
+        (
+            Number::F64(f64::NAN),
+            Number::U64(u64::MAX),
+            Ordering::Greater,
+        ),
+    ];
+    for (a, b, expected) in cases {
+        assert_eq!(a.cmp(&b), expected, "{:?} vs {:?}", a, b);
+        assert_eq!(b.cmp(&a), expected.reverse(), "{:?} vs {:?}", b, a);
+    }
+}
+
+#[test]
+fn test_hash() {
+    use miniserde::json::Number;
+    use std::collections::HashSet;
+
+    let set: HashSet<Number> = [
+        Number::U64(1),
+        Number::I64(1),
+        Number::F64(1.0),
+        Number::F64(0.0),
+        Number::F64(-0.0),
+        Number::F64(f64::NAN),
+        Number::F64(-f64::NAN),
+        Number::F64(0.5),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(set.len(), 4);
+}
@@ -0,0 +1,132 @@
+use miniserde::de::Deserialize;
+use miniserde::json::{self, Parser, Status, Value};
+
+fn parse_chunks(chunks: &[&[u8]]) -> miniserde::Result<Value> {
+    let mut out = None;
+    let mut parser = Parser::new(Value::begin(&mut out));
+    for chunk in chunks {
+        parser.feed(chunk)?;
+    }
+    parser.finish()?;
+    Ok(out.unwrap())
+}
+
+const DOCUMENTS: &[&str] = &[
+    "null",
+    " true ",
+    "false",
+    "0",
+    "-12",
+    "18446744073709551615",
+    "18446744073709551616",
+    "-1.5e-3",
+    r#""""#,
+    r#""a\"b\\c\/\n\u00e9\ud83d\ude00""#,
+    "\"caf\u{e9} \u{1f980}\"",
+    "[]",
+    "[ 1 , [2, [3, []]], {} ]",
+    r#"{"a": {"b": [true, null, "c"]}, "d\"": -0.0, "": 1}"#,
+    // Errors.
+    "",
+    "nul",
+    "nulll",
+    "01",
+    "1.",
+    "[1,]",
+    "[1 2]",
+    "{\"a\" 1}",
+    "{\"a\": 1,}",
+    "{1: 2}",
+    "[1}",
+    "\"unterminated",
+    "\"\\x\"",
+    "1 2",
+    "[] []",
+    "[1\"a\"]",
+];
+
+#[test]
+fn test_every_split() {
+    for j in DOCUMENTS {
+        let expected = json::from_str::<Value>(j).ok();
+        let bytes = j.as_bytes();
+
+        for i in 0..=bytes.len() {
+            for k in i..=bytes.len() {
+                let chunks = [&bytes[..i], &bytes[i..k], &bytes[k..]];
+                let actual = parse_chunks(&chunks).ok();
+                assert_eq!(actual, expected, "{:?} split at {} and {}", j, i, k);
+            }
+        }
+
+        let chunks: Vec<&[u8]> = bytes.chunks(1).collect();
+        assert_eq!(parse_chunks(&chunks).ok(), expected, "{:?} byte by byte", j);
+    }
+}
+
+#[test]
+fn test_status() {
+    let mut out = None;
+    let mut parser = Parser::new(Value::begin(&mut out));
+    assert_eq!(parser.feed(b"[1, ").unwrap(), Status::Incomplete);
+    assert_eq!(parser.feed(b"\"x").unwrap(), Status::Incomplete);
+    assert_eq!(parser.feed(b"\"]").unwrap(), Status::Complete);
+    assert_eq!(parser.feed(b" \n").unwrap(), Status::Complete);
+    parser.finish().unwrap();
+    assert_eq!(json::to_string(&out.unwrap()), r#"[1,"x"]"#);
+
+    // A top-level number is not complete until whitespace or the end.
+    let mut out = None;
+    let mut parser = Parser::new(Value::begin(&mut out));
+    assert_eq!(parser.feed(b"12").unwrap(), Status::Incomplete);
+    assert_eq!(parser.feed(b"3").unwrap(), Status::Incomplete);
+    parser.finish().unwrap();
+    assert_eq!(json::to_string(&out.unwrap()), "123");
+
+    let mut out = None;
+    let mut parser = Parser::new(Value::begin(&mut out));
+    assert_eq!(parser.feed(b"123 ").unwrap(), Status::Complete);
+    parser.finish().unwrap();
+}
+
+#[test]
+fn test_errors() {
+    // Trailing characters after the value.
+    let mut out = None;
+    let mut parser = Parser::new(Value::begin(&mut out));
+    assert_eq!(parser.feed(b"{}").unwrap(), Status::Complete);
+    parser.feed(b" x").unwrap_err();
+    // Failure is sticky.
+    parser.feed(b" ").unwrap_err();
+    parser.finish().unwrap_err();
+
+    // Incomplete input.
+    let mut out = None;
+    let mut parser = Parser::new(Value::begin(&mut out));
+    parser.feed(br#"{"a": [1"#).unwrap();
+    parser.finish().unwrap_err();
+
+    // Invalid UTF-8 split across chunks.
+    let mut out = None;
+    let mut parser = Parser::new(Value::begin(&mut out));
+    parser.feed(b"\"\xF0\x9F").unwrap();
+    parser.feed(b"\x80\"").unwrap_err();
+}
+
+#[test]
+fn test_deeply_nested() {
+    let depth = if cfg!(miri) { 40 } else { 100_000 };
+
+    let mut j = String::new();
+    for _ in 0..depth {
+        j.push_str("{\"x\":[");
+    }
+    j.push_str("\"leaf\"");
+    for _ in 0..depth {
+        j.push_str("]}");
+    }
+
+    let chunks: Vec<&[u8]> = j.as_bytes().chunks(7).collect();
+    let value = parse_chunks(&chunks).unwrap();
+    assert_eq!(value, json::from_str::<Value>(&j).unwrap());
+}
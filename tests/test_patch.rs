@@ -0,0 +1,207 @@
+use miniserde::json::{self, Value};
+
+fn value(j: &str) -> Value {
+    json::from_str(j).unwrap()
+}
+
+#[test]
+fn test_apply() {
+    let cases = [
+        // RFC 6902 Appendix A examples.
+        (
+            r#"{"foo":"bar"}"#,
+            r#"[{"op":"add","path":"/baz","value":"qux"}]"#,
+            r#"{"baz":"qux","foo":"bar"}"#,
+        ),
+        (
+            r#"{"foo":["bar","baz"]}"#,
+            r#"[{"op":"add","path":"/foo/1","value":"qux"}]"#,
+            r#"{"foo":["bar","qux","baz"]}"#,
+        ),
+        (
+            r#"{"baz":"qux","foo":"bar"}"#,
+            r#"[{"op":"remove","path":"/baz"}]"#,
+            r#"{"foo":"bar"}"#,
+        ),
+        (
+            r#"{"foo":["bar","qux","baz"]}"#,
+            r#"[{"op":"remove","path":"/foo/1"}]"#,
+            r#"{"foo":["bar","baz"]}"#,
+        ),
+        (
+            r#"{"baz":"qux","foo":"bar"}"#,
+            r#"[{"op":"replace","path":"/baz","value":"boo"}]"#,
+            r#"{"baz":"boo","foo":"bar"}"#,
+        ),
+        (
+            r#"{"foo":{"bar":"baz","waldo":"fred"},"qux":{"corge":"grault"}}"#,
+            r#"[{"op":"move","from":"/foo/waldo","path":"/qux/thud"}]"#,
+            r#"{"foo":{"bar":"baz"},"qux":{"corge":"grault","thud":"fred"}}"#,
+        ),
+        (
+            r#"{"foo":["all","grass","cows","eat"]}"#,
+            r#"[{"op":"move","from":"/foo/1","path":"/foo/3"}]"#,
+            r#"{"foo":["all","cows","eat","grass"]}"#,
+        ),
+        (
+            r#"{"foo":"bar"}"#,
+            r#"[{"op":"add","path":"/child","value":{"grandchild":{}}}]"#,
+            r#"{"child":{"grandchild":{}},"foo":"bar"}"#,
+        ),
+        (
+            r#"{"foo":["bar"]}"#,
+            r#"[{"op":"add","path":"/foo/-","value":["abc","def"]}]"#,
+            r#"{"foo":["bar",["abc","def"]]}"#,
+        ),
+        (
+            r#"{"/":9,"~1":10}"#,
+            r#"[{"op":"test","path":"/~01","value":10}]"#,
+            r#"{"/":9,"~1":10}"#,
+        ),
+        (
+            r#"{"a":{"b":[1]}}"#,
+            r#"[{"op":"copy","from":"/a/b","path":"/c"}]"#,
+            r#"{"a":{"b":[1]},"c":[1]}"#,
+        ),
+        (
+            r#"{"a":1}"#,
+            r#"[{"op":"replace","path":"","value":[true]}]"#,
+            r#"[true]"#,
+        ),
+        (
+            r#"{"a":1}"#,
+            r#"[{"op":"move","from":"/a","path":"/a"}]"#,
+            r#"{"a":1}"#,
+        ),
+    ];
+
+    for (doc, patch, expected) in cases {
+        let mut doc = value(doc);
+        json::patch::apply(&mut doc, &value(patch)).unwrap();
+        assert_eq!(json::to_string(&doc), expected, "{}", patch);
+    }
+}
+
+#[test]
+fn test_apply_error() {
+    let cases = [
+        (
+            r#"{"foo":"bar"}"#,
+            r#"[{"op":"add","path":"/baz/bat","value":"qux"}]"#,
+        ),
+        (
+            r#"{"baz":"qux","foo":["a",2,"c"]}"#,
+            r#"[{"op":"test","path":"/baz","value":"qux"},{"op":"test","path":"/foo/1","value":"2"}]"#,
+        ),
+        (r#"{"foo":"bar"}"#, r#"[{"op":"remove","path":"/baz"}]"#),
+        (
+            r#"{"foo":[]}"#,
+            r#"[{"op":"add","path":"/foo/1","value":0}]"#,
+        ),
+        (
+            r#"{"foo":[]}"#,
+            r#"[{"op":"add","path":"/foo/01","value":0}]"#,
+        ),
+        (
+            r#"{"foo":{}}"#,
+            r#"[{"op":"move","from":"/foo","path":"/foo/bar"}]"#,
+        ),
+        (r#"{"foo":1}"#, r#"[{"op":"frobnicate","path":"/foo"}]"#),
+        (r#"{"foo":1}"#, r#"[{"op":"add","path":"/bar"}]"#),
+        (r#"{"foo":1}"#, r#"{"op":"remove","path":"/foo"}"#),
+    ];
+
+    for (doc, patch) in cases {
+        let mut doc = value(doc);
+        let original = doc.clone();
+        json::patch::apply(&mut doc, &value(patch)).unwrap_err();
+        assert_eq!(doc, original, "{}", patch);
+    }
+}
+
+#[test]
+fn test_apply_rollback() {
+    let original = value(r#"{"a":[1,2,3],"b":{"c":"d"},"e":"f"}"#);
+    let patch = value(
+        r#"[
+            {"op":"add","path":"/a/1","value":"x"},
+            {"op":"remove","path":"/a/0"},
+            {"op":"replace","path":"/b/c","value":"y"},
+            {"op":"move","from":"/e","path":"/a/-"},
+            {"op":"move","from":"/b","path":"/g"},
+            {"op":"copy","from":"/g","path":"/a/0"},
+            {"op":"add","path":"/g","value":null},
+            {"op":"replace","path":"","value":{"h":[]}},
+            {"op":"add","path":"/h/0","value":0},
+            {"op":"test","path":"/h","value":[]}
+        ]"#,
+    );
+    let mut doc = original.clone();
+    json::patch::apply(&mut doc, &patch).unwrap_err();
+    assert_eq!(doc, original);
+}
+
+#[test]
+fn test_merge_patch() {
+    // RFC 7396 Appendix A examples.
+    let cases = [
+        (r#"{"a":"b"}"#, r#"{"a":"c"}"#, r#"{"a":"c"}"#),
+        (r#"{"a":"b"}"#, r#"{"b":"c"}"#, r#"{"a":"b","b":"c"}"#),
+        (r#"{"a":"b"}"#, r#"{"a":null}"#, r#"{}"#),
+        (r#"{"a":"b","b":"c"}"#, r#"{"a":null}"#, r#"{"b":"c"}"#),
+        (r#"{"a":["b"]}"#, r#"{"a":"c"}"#, r#"{"a":"c"}"#),
+        (r#"{"a":"c"}"#, r#"{"a":["b"]}"#, r#"{"a":["b"]}"#),
+        (
+            r#"{"a":{"b":"c"}}"#,
+            r#"{"a":{"b":"d","c":null}}"#,
+            r#"{"a":{"b":"d"}}"#,
+        ),
+        (r#"{"a":[{"b":"c"}]}"#, r#"{"a":[1]}"#, r#"{"a":[1]}"#),
+        (r#"["a","b"]"#, r#"["c","d"]"#, r#"["c","d"]"#),
+        (r#"{"a":"b"}"#, r#"["c"]"#, r#"["c"]"#),
+        (r#"{"a":"foo"}"#, r#"null"#, r#"null"#),
+        (r#"{"a":"foo"}"#, r#""bar""#, r#""bar""#),
+        (r#"{"e":null}"#, r#"{"a":1}"#, r#"{"a":1,"e":null}"#),
+        (r#"[1,2]"#, r#"{"a":"b","c":null}"#, r#"{"a":"b"}"#),
+        (
+            r#"{}"#,
+            r#"{"a":{"bb":{"ccc":null}}}"#,
+            r#"{"a":{"bb":{}}}"#,
+        ),
+    ];
+
+    for (target, patch, expected) in cases {
+        let mut target = value(target);
+        json::merge_patch(&mut target, &value(patch));
+        assert_eq!(json::to_string(&target), expected, "{}", patch);
+    }
+}
+
+#[test]
+fn test_patch_deeply_nested() {
+    let depth = if cfg!(miri) { 40 } else { 100_000 };
+
+    let mut j = String::new();
+    let mut pointer = String::new();
+    for _ in 0..depth {
+        j.push_str("{\"x\":");
+        pointer.push_str("/x");
+    }
+    j.push_str("{}");
+    for _ in 0..depth {
+        j.push('}');
+    }
+
+    let mut doc = value(&j);
+    let mut merge = doc.clone();
+    json::merge_patch(&mut merge, &doc);
+    assert_eq!(merge, doc);
+
+    let patch = miniserde::json!([
+        {"op": "add", "path": format!("{}/y", pointer), "value": 1},
+        {"op": "copy", "from": "/x", "path": "/z"},
+        {"op": "test", "path": "/x", "value": "nope"},
+    ]);
+    json::patch::apply(&mut doc, &patch).unwrap_err();
+    assert_eq!(doc, merge);
+}
@@ -0,0 +1,76 @@
+use miniserde::json::{self, Parser, RawValue, Value};
+use miniserde::{de, Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Debug)]
+struct Envelope {
+    id: u32,
+    payload: Box<RawValue>,
+    extra: Option<Box<RawValue>>,
+}
+
+#[test]
+fn test_roundtrip() {
+    let j = r#"{"id": 1, "payload": [ 1.50 , "\u00e9", {"x": null} ], "extra": "a\nb"}"#;
+    let envelope: Envelope = json::from_str(j).unwrap();
+    assert_eq!(envelope.id, 1);
+    assert_eq!(
+        envelope.payload.get(),
+        r#"[ 1.50 , "\u00e9", {"x": null} ]"#,
+    );
+    assert_eq!(envelope.extra.as_ref().unwrap().get(), r#""a\nb""#);
+
+    let out = json::to_string(&envelope);
+    let expected = r#"{"id":1,"payload":[ 1.50 , "\u00e9", {"x": null} ],"extra":"a\nb"}"#;
+    assert_eq!(out, expected);
+}
+
+#[test]
+fn test_null_option() {
+    let j = r#"{"id": 1, "payload": null, "extra": null}"#;
+    let envelope: Envelope = json::from_str(j).unwrap();
+    assert_eq!(envelope.payload.get(), "null");
+    assert!(envelope.extra.is_none());
+}
+
+#[test]
+fn test_invalid() {
+    for j in ["[1,]", "{\"a\"}", "01", "\"\\x\"", "[1] 2", ""] {
+        json::from_str::<Box<RawValue>>(j).unwrap_err();
+    }
+
+    let j = r#"{"id": 1, "payload": [1 2], "extra": null}"#;
+    json::from_str::<Envelope>(j).unwrap_err();
+}
+
+#[test]
+fn test_top_level() {
+    let raw: Box<RawValue> = json::from_str(" \n-1e5\t").unwrap();
+    assert_eq!(raw.get(), "-1e5");
+    assert_eq!(json::to_string(&raw), "-1e5");
+}
+
+#[test]
+fn test_from_string() {
+    let raw = RawValue::from_string("{\"a\": [true]}".to_owned()).unwrap();
+    assert_eq!(raw.to_string(), "{\"a\": [true]}");
+    assert_eq!(format!("{:?}", raw), "RawValue({\"a\": [true]})");
+    RawValue::from_string("{\"a\": [true]".to_owned()).unwrap_err();
+
+    let value = json::to_value(&raw);
+    assert_eq!(value, json::from_str::<Value>("{\"a\": [true]}").unwrap());
+}
+
+#[test]
+fn test_from_value() {
+    let value: Value = json::from_str(r#"{"id": 2, "payload": {"k": [1, "v"]}}"#).unwrap();
+    let envelope: Envelope = json::from_value(value).unwrap();
+    assert_eq!(envelope.payload.get(), r#"{"k":[1,"v"]}"#);
+    assert!(envelope.extra.is_none());
+}
+
+#[test]
+fn test_parser_unsupported() {
+    let mut out = None::<Envelope>;
+    let mut parser = Parser::new(de::Deserialize::begin(&mut out));
+    parser.feed(br#"{"id": 1, "payload": [1]}"#).unwrap_err();
+}
@@ -0,0 +1,110 @@
+use miniserde::json::{self, Options, RawValue, Value};
+use miniserde::Deserialize;
+
+fn relaxed(j: &str) -> Option<Value> {
+    json::from_str_with(j, &Options::new().relaxed(true)).ok()
+}
+
+fn strict(j: &str) -> Option<Value> {
+    json::from_str(j).ok()
+}
+
+fn check(j: &str, expected: &str) {
+    assert!(strict(j).is_none(), "strict mode accepted {:?}", j);
+    let expected = json::from_str::<Value>(expected).unwrap();
+    assert_eq!(relaxed(j), Some(expected), "relaxed mode on {:?}", j);
+}
+
+#[test]
+fn test_comments() {
+    check("// leading\n[1, /* inner */ 2] // trailing", "[1, 2]");
+    check("{/**/\"a\"/**/:/**/1/**/}", r#"{"a": 1}"#);
+    check("[1 // no newline at the end\n]", "[1]");
+    check("1 /* star * and / slash */", "1");
+
+    assert!(relaxed("[1 /* unterminated ]").is_none());
+    assert!(relaxed("[1 / 2]").is_none());
+    assert!(relaxed("/").is_none());
+}
+
+#[test]
+fn test_trailing_commas() {
+    check("[1, 2,]", "[1, 2]");
+    check(r#"{"a": [{},], "b": 2,}"#, r#"{"a": [{}], "b": 2}"#);
+
+    assert!(relaxed("[,]").is_none());
+    assert!(relaxed("{,}").is_none());
+    assert!(relaxed("[1,,]").is_none());
+    assert!(relaxed("[1,,2]").is_none());
+}
+
+#[test]
+fn test_single_quotes() {
+    check(r#"'it\'s'"#, r#""it's""#);
+    check(r#"'say "hi"'"#, r#""say \"hi\"""#);
+    check(r#"{'a': 'b'}"#, r#"{"a": "b"}"#);
+    check(r#""it\'s""#, r#""it's""#);
+
+    assert!(relaxed("'unterminated").is_none());
+    assert_eq!(relaxed(r#""don't""#), strict(r#""don't""#));
+}
+
+#[test]
+fn test_unquoted_keys() {
+    check("{a: 1, _b2: 2, $c: 3}", r#"{"a": 1, "_b2": 2, "$c": 3}"#);
+
+    assert!(relaxed("{2a: 1}").is_none());
+    assert!(relaxed("{a-b: 1}").is_none());
+    assert!(relaxed("[a]").is_none());
+}
+
+#[test]
+fn test_hex() {
+    check("[0x1F, 0XfF, -0x10]", "[31, 255, -16]");
+    check("0xFFFFFFFFFFFFFFFF", "18446744073709551615");
+
+    assert!(relaxed("0x").is_none());
+    assert!(relaxed("0xG").is_none());
+    assert!(relaxed("0x10000000000000000").is_none());
+}
+
+#[test]
+fn test_non_finite() {
+    let value = relaxed("[NaN, Infinity, -Infinity]").unwrap();
+    assert!(value[0].as_f64().unwrap().is_nan());
+    assert_eq!(value[1].as_f64(), Some(f64::INFINITY));
+    assert_eq!(value[2].as_f64(), Some(f64::NEG_INFINITY));
+
+    assert!(strict("NaN").is_none());
+    assert!(strict("Infinity").is_none());
+    assert!(strict("-Infinity").is_none());
+    assert!(relaxed("Inf").is_none());
+    assert!(relaxed("nan").is_none());
+}
+
+#[test]
+fn test_derive() {
+    #[derive(Deserialize, Debug)]
+    struct Config {
+        name: String,
+        ports: Vec<u16>,
+    }
+
+    let j = "{
+        /* Settings */
+        name: 'server',
+        ports: [0x50, 443,], // http, https
+    }";
+    let config: Config = json::from_str_with(j, &Options::new().relaxed(true)).unwrap();
+    assert_eq!(config.name, "server");
+    assert_eq!(config.ports, [80, 443]);
+}
+
+#[test]
+fn test_raw_value_stays_strict() {
+    let options = Options::new().relaxed(true);
+    let raw: Box<RawValue> = json::from_str_with("/* c */ [1, 2] // c", &options).unwrap();
+    assert_eq!(raw.get(), "[1, 2]");
+
+    json::from_str_with::<Box<RawValue>>("[1, 2,]", &options).unwrap_err();
+}
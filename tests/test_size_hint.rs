@@ -0,0 +1,79 @@
+use miniserde::json::{self, Value};
+use miniserde::ser::Fragment;
+use miniserde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+fn seq_hint(value: &dyn Serialize) -> Option<usize> {
+    match value.begin() {
+        Fragment::Seq(seq) => seq.size_hint(),
+        _ => panic!("expected a sequence"),
+    }
+}
+
+fn map_hint(value: &dyn Serialize) -> Option<usize> {
+    match value.begin() {
+        Fragment::Map(map) => map.size_hint(),
+        _ => panic!("expected a map"),
+    }
+}
+
+#[test]
+fn test_sequences() {
+    assert_eq!(seq_hint(&vec![1, 2, 3]), Some(3));
+    assert_eq!(seq_hint(&[0u8; 5]), Some(5));
+    assert_eq!(seq_hint(&&[1, 2][..]), Some(2));
+    assert_eq!(seq_hint(&Vec::<u8>::new()), Some(0));
+    assert_eq!(seq_hint(&(1, "two")), Some(2));
+
+    let value: Value = json::from_str("[1, [2], 3, 4]").unwrap();
+    assert_eq!(seq_hint(&value), Some(4));
+}
+
+#[test]
+fn test_maps() {
+    let mut btree = BTreeMap::new();
+    btree.insert("a", 1);
+    btree.insert("b", 2);
+    assert_eq!(map_hint(&btree), Some(2));
+
+    let hash: HashMap<_, _> = btree.into_iter().collect();
+    assert_eq!(map_hint(&hash), Some(2));
+
+    let value: Value = json::from_str(r#"{"a": 1, "b": 2, "c": 3}"#).unwrap();
+    assert_eq!(map_hint(&value), Some(3));
+}
+
+#[test]
+fn test_derive() {
+    #[derive(Serialize)]
+    struct Three {
+        a: u8,
+        b: Option<u8>,
+        c: String,
+    }
+
+    let three = Three {
+        a: 1,
+        b: None,
+        c: String::new(),
+    };
+    let Fragment::Map(mut map) = three.begin() else {
+        panic!("expected a map");
+    };
+    for remaining in (0..=3).rev() {
+        assert_eq!(map.size_hint(), Some(remaining));
+        assert_eq!(map.next().is_some(), remaining > 0);
+    }
+    assert!(map.next().is_none());
+    assert_eq!(map.size_hint(), Some(0));
+}
+
+#[test]
+fn test_remaining() {
+    let vec = vec![1, 2, 3];
+    let Fragment::Seq(mut seq) = vec.begin() else {
+        panic!("expected a sequence");
+    };
+    seq.next();
+    assert_eq!(seq.size_hint(), Some(2));
+}
@@ -0,0 +1,160 @@
+use miniserde::json::{self, StreamDeserializer, Value};
+use std::io::{self, Read};
+
+fn collect<'a>(stream: StreamDeserializer<'a, Value>) -> Vec<Option<String>> {
+    stream
+        .map(|result| result.ok().map(|value| json::to_string(&value)))
+        .collect()
+}
+
+fn ok(j: &str) -> Option<String> {
+    Some(j.to_owned())
+}
+
+#[test]
+fn test_concatenated() {
+    let j = r#"{"a":1}[2] "three"4 true	null {}"#;
+    let expected = [
+        ok(r#"{"a":1}"#),
+        ok("[2]"),
+        ok(r#""three""#),
+        ok("4"),
+        ok("true"),
+        ok("null"),
+        ok("{}"),
+    ];
+    assert_eq!(collect(StreamDeserializer::new(j)), expected);
+}
+
+#[test]
+fn test_json_lines() {
+    let j = "{\"a\": [1, 2]}\n\n\"x\\\"]\"\r\n-1.5\n";
+    let expected = [ok(r#"{"a":[1,2]}"#), ok(r#""x\"]""#), ok("-1.5")];
+    assert_eq!(collect(StreamDeserializer::new(j)), expected);
+
+    assert!(collect(StreamDeserializer::new("")).is_empty());
+    assert!(collect(StreamDeserializer::new(" \n ")).is_empty());
+}
+
+#[test]
+fn test_byte_offset() {
+    let mut stream = StreamDeserializer::<Value>::new(" 1 [2]\n{} ");
+    assert_eq!(stream.byte_offset(), 0);
+    stream.next().unwrap().unwrap();
+    assert_eq!(stream.byte_offset(), 2);
+    stream.next().unwrap().unwrap();
+    assert_eq!(stream.byte_offset(), 6);
+    stream.next().unwrap().unwrap();
+    assert_eq!(stream.byte_offset(), 9);
+    assert!(stream.next().is_none());
+    assert_eq!(stream.byte_offset(), 10);
+}
+
+#[test]
+fn test_error_ends_stream() {
+    let j = "1 [2,] 3";
+    assert_eq!(collect(StreamDeserializer::new(j)), [ok("1"), None]);
+
+    // A scalar must be followed by whitespace.
+    let j = "1[2] 3";
+    assert_eq!(collect(StreamDeserializer::new(j)), [None]);
+
+    let j = "{\"a\": 1";
+    assert_eq!(collect(StreamDeserializer::new(j)), [None]);
+
+    let j = "] 1";
+    assert_eq!(collect(StreamDeserializer::new(j)), [None]);
+}
+
+#[test]
+fn test_json_text_sequence() {
+    let j = "\x1E{\"a\":1}\n\x1E[1,\n\x1E123\x1E\"b\"\n\x1Etrue junk\n\x1E{}\n";
+    let expected = [
+        ok(r#"{"a":1}"#),
+        // Truncated array, skipped up to the next record.
+        None,
+        // Number not followed by whitespace, treated as truncated.
+        None,
+        ok(r#""b""#),
+        ok("true"),
+        None,
+        ok("{}"),
+    ];
+    assert_eq!(collect(StreamDeserializer::new(j)), expected);
+}
+
+#[test]
+fn test_from_slice() {
+    let j = b"\"a\" \"\xFF\" \"b\"";
+    assert_eq!(
+        collect(StreamDeserializer::from_slice(j)),
+        [ok(r#""a""#), None],
+    );
+}
+
+// Hands out the input a few bytes at a time.
+struct Trickle<'a> {
+    input: &'a [u8],
+    chunk: usize,
+}
+
+impl<'a> Read for Trickle<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.chunk.min(buf.len()).min(self.input.len());
+        buf[..n].copy_from_slice(&self.input[..n]);
+        self.input = &self.input[n..];
+        Ok(n)
+    }
+}
+
+#[test]
+fn test_from_reader() {
+    let j =
+        "{\"key\": \"value with } and ]\"}\n[[1], [2, [3]]]\n12345 \"\\\\\"\n\x1E[oops\n\x1E0\n";
+    let expected = [
+        ok(r#"{"key":"value with } and ]"}"#),
+        ok("[[1],[2,[3]]]"),
+        ok("12345"),
+        ok(r#""\\""#),
+        None,
+        ok("0"),
+    ];
+
+    for chunk in [1, 2, 3, 7, 1000] {
+        let reader = Trickle {
+            input: j.as_bytes(),
+            chunk,
+        };
+        let mut stream = StreamDeserializer::<Value>::from_reader(reader);
+        let mut actual = Vec::new();
+        let mut offsets = Vec::new();
+        while let Some(result) = stream.next() {
+            actual.push(result.ok().map(|value| json::to_string(&value)));
+            offsets.push(stream.byte_offset());
+        }
+        assert_eq!(actual, expected);
+        assert_eq!(stream.byte_offset(), j.len());
+
+        let mut slice_stream = StreamDeserializer::<Value>::new(j);
+        let mut slice_offsets = Vec::new();
+        while slice_stream.next().is_some() {
+            slice_offsets.push(slice_stream.byte_offset());
+        }
+        assert_eq!(offsets, slice_offsets);
+    }
+}
+
+#[test]
+fn test_reader_error() {
+    struct Failing;
+
+    impl Read for Failing {
+        fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::other("broken"))
+        }
+    }
+
+    let mut stream = StreamDeserializer::<Value>::from_reader(Failing);
+    assert!(stream.next().unwrap().is_err());
+    assert!(stream.next().is_none());
+}
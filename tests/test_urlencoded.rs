@@ -0,0 +1,141 @@
+use miniserde::json::Value;
+use miniserde::{urlencoded, Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Form {
+    name: String,
+    age: u8,
+    balance: i64,
+    ratio: f64,
+    admin: bool,
+    tags: Vec<String>,
+    note: Option<String>,
+}
+
+#[test]
+fn test_typed_fields() {
+    let form: Form = urlencoded::from_str(
+        "name=Jo+Smith&age=30&balance=-12&ratio=0.5&admin=true&tags=a&tags=b%26c",
+    )
+    .unwrap();
+    assert_eq!(
+        form,
+        Form {
+            name: "Jo Smith".to_owned(),
+            age: 30,
+            balance: -12,
+            ratio: 0.5,
+            admin: true,
+            tags: vec!["a".to_owned(), "b&c".to_owned()],
+            note: None,
+        },
+    );
+}
+
+#[test]
+fn test_round_trip() {
+    let form = Form {
+        name: "ünïcode + symbols/?=&".to_owned(),
+        age: 7,
+        balance: 0,
+        ratio: 1e-7,
+        admin: false,
+        tags: vec!["x".to_owned()],
+        note: Some(String::new()),
+    };
+    let query = urlencoded::to_string(&form).unwrap();
+    assert_eq!(
+        query,
+        "name=%C3%BCn%C3%AFcode+%2B+symbols%2F%3F%3D%26&age=7&balance=0&ratio=1e-7&admin=false&tags=x&note=",
+    );
+    assert_eq!(urlencoded::from_str::<Form>(&query).unwrap(), form);
+}
+
+#[test]
+fn test_single_value_into_vec() {
+    #[derive(Deserialize)]
+    struct Ids {
+        id: Vec<u32>,
+    }
+
+    let ids: Ids = urlencoded::from_str("id=5").unwrap();
+    assert_eq!(ids.id, [5]);
+
+    let ids: Ids = urlencoded::from_str("id=5&id=6&id=7").unwrap();
+    assert_eq!(ids.id, [5, 6, 7]);
+}
+
+#[test]
+fn test_empty_value() {
+    #[derive(Deserialize)]
+    struct Optional {
+        n: Option<u32>,
+        s: Option<String>,
+    }
+
+    let optional: Optional = urlencoded::from_str("n=&s=").unwrap();
+    assert_eq!(optional.n, None);
+    assert_eq!(optional.s.as_deref(), Some(""));
+
+    // A key without `=` has an empty value too.
+    let optional: Optional = urlencoded::from_str("n&s").unwrap();
+    assert_eq!(optional.n, None);
+}
+
+#[test]
+fn test_map() {
+    let map: BTreeMap<String, String> = urlencoded::from_str("b=2&&a=1&c").unwrap();
+    let expected = [("a", "1"), ("b", "2"), ("c", "")];
+    assert_eq!(map.len(), expected.len());
+    for (k, v) in expected {
+        assert_eq!(map[k], v);
+    }
+
+    // Without a type to guide it, every value is text.
+    let value: Value = urlencoded::from_str("n=1&b=true").unwrap();
+    assert_eq!(value["n"].as_str(), Some("1"));
+    assert_eq!(value["b"].as_str(), Some("true"));
+}
+
+#[test]
+fn test_borrowed() {
+    #[derive(Deserialize)]
+    struct Borrowed<'a> {
+        s: &'a str,
+    }
+
+    let query = String::from("s=plain");
+    let borrowed: Borrowed = urlencoded::from_str(&query).unwrap();
+    assert_eq!(borrowed.s, "plain");
+
+    // Anything which needs decoding cannot be borrowed.
+    assert!(urlencoded::from_str::<Borrowed>("s=a+b").is_err());
+}
+
+#[test]
+fn test_negative_zero() {
+    let map: BTreeMap<String, u8> = urlencoded::from_str("a=-0&b=-00").unwrap();
+    assert_eq!(map["a"], 0);
+    assert_eq!(map["b"], 0);
+}
+
+#[test]
+fn test_errors() {
+    assert!(urlencoded::from_str::<Form>("name=%zz").is_err());
+    assert!(urlencoded::from_str::<Form>("name=%4").is_err());
+    assert!(urlencoded::from_str::<BTreeMap<String, String>>("%FF=x").is_err());
+    assert!(urlencoded::from_str::<BTreeMap<String, u8>>("a=300").is_err());
+    assert!(urlencoded::from_str::<BTreeMap<String, u8>>("a=x").is_err());
+
+    assert!(urlencoded::to_string(&1).is_err());
+    assert!(urlencoded::to_string(&[1, 2]).is_err());
+
+    let mut nested = BTreeMap::new();
+    nested.insert("a", vec![vec![1]]);
+    assert!(urlencoded::to_string(&nested).is_err());
+
+    let mut non_finite = BTreeMap::new();
+    non_finite.insert("a", f64::NAN);
+    assert!(urlencoded::to_string(&non_finite).is_err());
+}
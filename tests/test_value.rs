@@ -2,6 +2,7 @@
 
 use indoc::indoc;
 use miniserde::json::{self, Value};
+use miniserde::{Deserialize, Serialize};
 
 #[test]
 fn test_round_trip_deeply_nested() {
@@ -53,3 +54,225 @@ fn test_debug() {
 
     assert_eq!(debug, expected);
 }
+
+#[test]
+fn test_index() {
+    let j = r#" {"data": [{"id": 7, "name": "x"}, -1, 2.5], "ok": true} "#;
+    let value: Value = json::from_str(j).unwrap();
+
+    assert_eq!(value["data"][0]["id"].as_u64(), Some(7));
+    assert_eq!(value["data"][0]["name"].as_str(), Some("x"));
+    assert_eq!(value["data"][1].as_i64(), Some(-1));
+    assert_eq!(value["data"][1].as_u64(), None);
+    assert_eq!(value["data"][2].as_f64(), Some(2.5));
+    assert_eq!(value["ok"].as_bool(), Some(true));
+    assert!(value["data"][3].is_null());
+    assert!(value["missing"]["deeper"][0].is_null());
+    assert!(value["data"]["id"].is_null());
+    assert!(value.get("data").unwrap().is_array());
+    assert!(value.get(String::from("missing")).is_none());
+    assert!(value.get(0).is_none());
+}
+
+#[test]
+fn test_get_mut_and_take() {
+    let mut value: Value = json::from_str(r#" {"a": [1, 2]} "#).unwrap();
+    *value.get_mut("a").unwrap().get_mut(1).unwrap() = Value::Bool(false);
+    let taken = value["a"].clone();
+    assert_eq!(json::to_string(&taken), "[1,false]");
+
+    let taken = value.get_mut("a").unwrap().take();
+    assert_eq!(json::to_string(&taken), "[1,false]");
+    assert_eq!(json::to_string(&value), r#"{"a":null}"#);
+}
+
+#[test]
+fn test_pointer() {
+    let j = r#" {"foo": ["bar", "baz"], "": 0, "a/b": 1, "m~n": 2, "01": 3} "#;
+    let mut value: Value = json::from_str(j).unwrap();
+
+    assert!(value.pointer("").unwrap().is_object());
+    assert_eq!(value.pointer("/foo/0").unwrap().as_str(), Some("bar"));
+    assert_eq!(value.pointer("/").unwrap().as_u64(), Some(0));
+    assert_eq!(value.pointer("/a~1b").unwrap().as_u64(), Some(1));
+    assert_eq!(value.pointer("/m~0n").unwrap().as_u64(), Some(2));
+    assert_eq!(value.pointer("/01").unwrap().as_u64(), Some(3));
+    assert!(value.pointer("/foo/01").is_none());
+    assert!(value.pointer("/foo/-").is_none());
+    assert!(value.pointer("/foo/2").is_none());
+    assert!(value.pointer("/m~2n").is_none());
+    assert!(value.pointer("foo").is_none());
+
+    *value.pointer_mut("/foo/1").unwrap() = Value::Null;
+    assert!(value["foo"][1].is_null());
+}
+
+#[test]
+fn test_to_value() {
+    #[derive(Serialize)]
+    struct Example {
+        code: i32,
+        message: &'static str,
+        list: Vec<Option<f64>>,
+    }
+
+    let example = Example {
+        code: 200,
+        message: "ok",
+        list: vec![Some(1.5), None, Some(f64::NAN)],
+    };
+    let value = json::to_value(&example);
+    assert_eq!(value["code"].as_u64(), Some(200));
+    assert_eq!(
+        json::to_string(&value),
+        r#"{"code":200,"list":[1.5,null,null],"message":"ok"}"#,
+    );
+}
+
+#[test]
+fn test_from_value() {
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Example {
+        code: u8,
+        neg: i64,
+        message: String,
+        list: Vec<Option<f64>>,
+    }
+
+    let j = r#" {"code": 200, "neg": -1, "message": "ok", "list": [1.5, null], "extra": {}} "#;
+    let value: Value = json::from_str(j).unwrap();
+    let expected = Example {
+        code: 200,
+        neg: -1,
+        message: "ok".to_owned(),
+        list: vec![Some(1.5), None],
+    };
+    assert_eq!(json::from_value_ref::<Example>(&value).unwrap(), expected);
+    assert_eq!(json::from_value::<Example>(value).unwrap(), expected);
+
+    let value: Value = json::from_str(r#" {"code": 256} "#).unwrap();
+    json::from_value::<Example>(value).unwrap_err();
+}
+
+#[test]
+fn test_value_conversion_deeply_nested() {
+    let depth = if cfg!(miri) { 40 } else { 100_000 };
+
+    let mut j = String::new();
+    for _ in 0..depth {
+        j.push_str("{\"x\":[");
+    }
+    for _ in 0..depth {
+        j.push_str("]}");
+    }
+
+    let value: Value = json::from_str(&j).unwrap();
+    let value = json::to_value(&value);
+    let value: Value = json::from_value(value).unwrap();
+    assert_eq!(json::to_string(&value), j);
+}
+
+#[test]
+fn test_eq_ord_hash() {
+    use std::collections::HashSet;
+
+    let a: Value = json::from_str(r#" {"a": [1, 2.0, null], "b": {"c": "d"}} "#).unwrap();
+    let b: Value = json::from_str(r#" {"b": {"c": "d"}, "a": [1.0, 2, null]} "#).unwrap();
+    let c: Value = json::from_str(r#" {"a": [1, 2, null, false], "b": {"c": "d"}} "#).unwrap();
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+    assert!(a < c);
+
+    let ordered: Vec<Value> = [
+        "null",
+        "false",
+        "true",
+        "-1",
+        "0.5",
+        "1",
+        r#""""#,
+        r#""a""#,
+        "[]",
+        "[0]",
+        "[0,0]",
+        "[1]",
+        "{}",
+        r#"{"a":1}"#,
+        r#"{"a":2}"#,
+        r#"{"b":0}"#,
+    ]
+    .iter()
+    .map(|j| json::from_str(j).unwrap())
+    .collect();
+    for window in ordered.windows(2) {
+        assert!(window[0] < window[1], "{:?} < {:?}", window[0], window[1]);
+    }
+
+    let set: HashSet<&Value> = [&a, &b, &c].into_iter().collect();
+    assert_eq!(set.len(), 2);
+}
+
+#[test]
+fn test_eq_deeply_nested() {
+    let depth = if cfg!(miri) { 40 } else { 100_000 };
+
+    let mut j = String::new();
+    for _ in 0..depth {
+        j.push_str("{\"x\":[");
+    }
+    for _ in 0..depth {
+        j.push_str("]}");
+    }
+
+    let a: Value = json::from_str(&j).unwrap();
+    let b: Value = json::from_str(&j).unwrap();
+    assert_eq!(a, b);
+    assert_eq!(a.cmp(&b), std::cmp::Ordering::Equal);
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    std::hash::Hash::hash(&a, &mut hasher);
+}
+
+#[test]
+fn test_debug_compact() {
+    let j = r#" {"a": [1, [], {}, {"b": null}], "c": "d"} "#;
+    let value: Value = json::from_str(j).unwrap();
+    let debug = format!("{:?}", value);
+    let expected = r#"Object {"a": Array [Number(1), Array [], Object {}, Object {"b": Null}], "c": String("d")}"#;
+    assert_eq!(debug, expected);
+
+    let debug = format!("{:#?}", value["a"].as_array().unwrap());
+    let expected = indoc! {r#"
+        Array [
+            Number(1),
+            Array [],
+            Object {},
+            Object {
+                "b": Null,
+            },
+        ]"#
+    };
+    assert_eq!(debug, expected);
+}
+
+#[test]
+fn test_clone_debug_deeply_nested() {
+    let depth = if cfg!(miri) { 40 } else { 100_000 };
+
+    let mut value = Value::Null;
+    for _ in 0..depth {
+        let mut array = json::Array::new();
+        array.push(value);
+        value = Value::Array(array);
+    }
+
+    let clone = value.clone();
+    assert_eq!(clone, value);
+
+    let mut clone_from = Value::String("...".to_owned());
+    clone_from.clone_from(&value);
+    assert_eq!(clone_from, value);
+
+    let debug = format!("{:?}", value);
+    assert_eq!(debug.len(), depth * "Array []".len() + "Null".len());
+}